@@ -0,0 +1,230 @@
+// Command line tools
+//
+// `web-simulant import <format> <file>` converts an API description, another
+// mock server's stubs, a traffic capture or an access log into a configuration
+// file, and
+// `export` goes the other way, without starting the servers
+
+use crate::config::{parse_auto, validate, ConfigError, Configuration};
+use crate::export::{export_openapi, DEFAULT_ENGINE_URL};
+use crate::import::{
+	fit_access_log, import_har, import_openapi, import_postman, import_wiremock, merge_config, refit_latencies,
+	AccessLogOptions, ConversionReport, HarOptions, ImportOptions, Imported,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+const USAGE: &str = "\
+Usage:
+  web-simulant                             Start the engine and control plane
+  web-simulant import openapi <file> [options]
+  web-simulant import wiremock <file or mappings directory> [options]
+  web-simulant import postman <collection> [options]
+  web-simulant import har <file> [--host <host>]... [--template-paths false] [--output <file>] [--merge <config>]
+  web-simulant import access-log <file> [access log options] [--output <file>] [--merge <config>]
+  web-simulant export openapi <config> [--output <file>] [--server <url>]
+
+Import options:
+  --output <file>             Write the configuration here instead of stdout
+  --merge <config>            Merge the imported endpoints into this configuration
+  --latency-ms <ms>           Latency for every endpoint (default 50); for WireMock, stubs without a delay
+  --latency-stddev-ms <ms>    Make the latency normal with this spread
+  --error-rate <0..1>         Injected error rate (default 0)
+  --error-codes <codes>       Comma separated statuses; default: the documented error responses
+
+HAR options (latency and errors are fitted from the entries):
+  --host <host>               Only import entries for this host; may be repeated
+  --template-paths <bool>     Turn numeric and UUID segments into parameters (default true)
+
+Access log options (latency is fitted per route; with --merge only latencies change):
+  --log-format <format>       nginx (default), envoy, json, or a log_format/envoy template
+  --duration-field <name>     Field holding the response time; default: the format's timing field
+  --duration-unit <unit>      s, ms or us; default: the field's usual unit
+  --quantiles <list>          Comma separated quantiles to report (default 0.5,0.9,0.95,0.99)
+  --template-paths <bool>     Turn numeric and UUID segments into parameters (default true)
+
+Export options:
+  --output <file>             Write the document here instead of stdout
+  --server <url>              Server URL in the document (default http://localhost:8080)";
+
+const IMPORT_FLAGS: &[&str] = &["output", "merge", "latency_ms", "latency_stddev_ms", "error_rate", "error_codes"];
+const HAR_FLAGS: &[&str] = &["output", "merge", "host", "template_paths"];
+const ACCESS_LOG_FLAGS: &[&str] = &[
+	"output",
+	"merge",
+	"log_format",
+	"duration_field",
+	"duration_unit",
+	"quantiles",
+	"template_paths",
+];
+const EXPORT_FLAGS: &[&str] = &["output", "server"];
+
+/// Run a command. Returns `false` when there is none and the servers should start.
+pub fn run(args: &[String]) -> Result<bool> {
+	match args.first().map(String::as_str) {
+		None => Ok(false),
+		Some("import") => import(&args[1..]).map(|_| true),
+		Some("export") => export(&args[1..]).map(|_| true),
+		Some("help") | Some("--help") | Some("-h") => {
+			println!("{}", USAGE);
+			Ok(true)
+		}
+		Some(other) => bail!("unknown command '{}'\n\n{}", other, USAGE),
+	}
+}
+
+fn import(args: &[String]) -> Result<()> {
+	let (Some(format), Some(path)) = (args.first(), args.get(1)) else {
+		bail!("import needs a format and a file\n\n{}", USAGE);
+	};
+	let known = match format.as_str() {
+		"openapi" | "wiremock" | "postman" => IMPORT_FLAGS,
+		"har" => HAR_FLAGS,
+		"access-log" => ACCESS_LOG_FLAGS,
+		other => bail!("unknown import format '{}'", other),
+	};
+	let flags = flags(&args[2..], known)?;
+	let pairs = || flags.iter().map(|(name, value)| (name.as_str(), value.as_str()));
+	let options = || ImportOptions::from_pairs(pairs()).map_err(|message| anyhow!(message));
+	let content = match format.as_str() {
+		"wiremock" if Path::new(path).is_dir() => wiremock_directory(Path::new(path))?,
+		_ => fs::read_to_string(path).with_context(|| format!("reading {}", path))?,
+	};
+
+	let active = match flag(&flags, "merge") {
+		Some(active) => Some(parse_auto(&fs::read_to_string(active).with_context(|| format!("reading {}", active))?)?),
+		None => None,
+	};
+
+	let Imported { config, warnings, report } = match format.as_str() {
+		"har" => import_har(&content, &HarOptions::from_pairs(pairs()).map_err(|message| anyhow!(message))?)?,
+		"access-log" => access_log(&content, pairs(), active.as_ref())?,
+		"wiremock" => import_wiremock(&content, &options()?)?,
+		"postman" => import_postman(&content, &options()?)?,
+		_ => import_openapi(&content, &options()?)?,
+	};
+	for warning in &warnings {
+		eprintln!("warning: {}", warning);
+	}
+	for line in report.iter().flat_map(ConversionReport::lines) {
+		eprintln!("report: {}", line);
+	}
+
+	let config = match active {
+		Some(active) => merge_config(active, config),
+		None => config,
+	};
+	if let Err(ConfigError::ValidationError(_, errors)) = validate(&config) {
+		for error in &errors {
+			eprintln!("error: {}", error);
+		}
+		bail!("imported configuration failed validation with {} error(s)", errors.len());
+	}
+
+	write_output(flag(&flags, "output"), &serde_yaml::to_string(&config)?, config.endpoints.len())
+}
+
+/// Endpoints fitted from an access log, printing each route's fit. Against
+/// an existing configuration, requests are grouped by its routes and only
+/// the latencies of its endpoints change.
+fn access_log<'a>(
+	content: &str,
+	pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
+	active: Option<&Configuration>,
+) -> Result<Imported> {
+	let mut options = AccessLogOptions::from_pairs(pairs).map_err(|message| anyhow!(message))?;
+	if let Some(active) = active {
+		options.routes = active.endpoints.iter().map(|endpoint| (endpoint.method.clone(), endpoint.path.clone())).collect();
+	}
+	let fitted = fit_access_log(content, &options)?;
+	for route in &fitted.routes {
+		eprintln!("fit: {}", route.summary());
+	}
+	let config = match active {
+		Some(active) => refit_latencies(active, fitted.config),
+		None => fitted.config,
+	};
+	Ok(Imported {
+		config,
+		warnings: fitted.warnings,
+		report: None,
+	})
+}
+
+/// The stubs of every `.json` file in a WireMock `mappings` directory, as
+/// one `{"mappings": [...]}` document.
+fn wiremock_directory(dir: &Path) -> Result<String> {
+	let mut files: Vec<_> = fs::read_dir(dir)
+		.with_context(|| format!("reading {}", dir.display()))?
+		.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+		.filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+		.collect();
+	files.sort();
+
+	let mut mappings = Vec::new();
+	for file in files {
+		let content = fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+		let document: Value = serde_json::from_str(&content).with_context(|| format!("parsing {}", file.display()))?;
+		match document {
+			Value::Array(stubs) => mappings.extend(stubs),
+			Value::Object(mut object) => match object.remove("mappings") {
+				Some(Value::Array(stubs)) => mappings.extend(stubs),
+				_ => mappings.push(Value::Object(object)),
+			},
+			_ => eprintln!("warning: {} is not a WireMock mapping, skipped", file.display()),
+		}
+	}
+	Ok(json!({ "mappings": mappings }).to_string())
+}
+
+fn export(args: &[String]) -> Result<()> {
+	let (Some(format), Some(path)) = (args.first(), args.get(1)) else {
+		bail!("export needs a format and a configuration file\n\n{}", USAGE);
+	};
+	let flags = flags(&args[2..], EXPORT_FLAGS)?;
+	let content = fs::read_to_string(path).with_context(|| format!("reading {}", path))?;
+	let config = parse_auto(&content)?;
+	validate(&config)?;
+
+	let document = match format.as_str() {
+		"openapi" => export_openapi(&config, flag(&flags, "server").unwrap_or(DEFAULT_ENGINE_URL)),
+		other => bail!("unknown export format '{}'", other),
+	};
+	write_output(flag(&flags, "output"), &serde_yaml::to_string(&document)?, config.endpoints.len())
+}
+
+fn write_output(output: Option<&str>, content: &str, endpoints: usize) -> Result<()> {
+	match output {
+		Some(output) => {
+			fs::write(output, content).with_context(|| format!("writing {}", output))?;
+			eprintln!("wrote {} endpoint(s) to {}", endpoints, output);
+		}
+		None => print!("{}", content),
+	}
+	Ok(())
+}
+
+/// `--name value` pairs, with dashes in names turned into underscores.
+fn flags(args: &[String], known: &[&str]) -> Result<Vec<(String, String)>> {
+	let mut flags = Vec::new();
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		let Some(name) = arg.strip_prefix("--") else {
+			bail!("unexpected argument '{}'", arg);
+		};
+		let name = name.replace('-', "_");
+		if !known.contains(&name.as_str()) {
+			bail!("unknown option '{}'\n\n{}", arg, USAGE);
+		}
+		let value = args.next().with_context(|| format!("{} needs a value", arg))?;
+		flags.push((name, value.clone()));
+	}
+	Ok(flags)
+}
+
+fn flag<'a>(flags: &'a [(String, String)], name: &str) -> Option<&'a str> {
+	flags.iter().find(|(flag, _)| flag == name).map(|(_, value)| value.as_str())
+}
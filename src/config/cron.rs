@@ -0,0 +1,45 @@
+// Cron schedules
+//
+// Wall-clock schedules for behavior windows, parsed once and shared by the
+// validator and the engine
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use thiserror::Error;
+
+/// A parsed cron expression and the time zone it is evaluated in.
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    cron: Cron,
+    timezone: Tz,
+}
+
+#[derive(Debug, Error)]
+pub enum CronError {
+    #[error("invalid cron expression: {0}")]
+    Expression(String),
+    #[error("unknown time zone")]
+    TimeZone,
+}
+
+impl CronSchedule {
+    /// Parse `expression`, evaluated in `timezone` (UTC when unset).
+    pub fn parse(expression: &str, timezone: Option<&str>) -> Result<Self, CronError> {
+        let cron = Cron::new(expression)
+            .parse()
+            .map_err(|err| CronError::Expression(err.to_string()))?;
+        let timezone = match timezone {
+            Some(timezone) => timezone.parse::<Tz>().map_err(|_| CronError::TimeZone)?,
+            None => Tz::UTC,
+        };
+        Ok(Self { cron, timezone })
+    }
+
+    /// Start times at or after `from`, in order.
+    pub fn starts_from(&self, from: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> {
+        self.cron
+            .iter_from(from.with_timezone(&self.timezone))
+            .map(|start| start.with_timezone(&Utc))
+    }
+}
@@ -6,19 +6,18 @@ mod schema;
 mod parser;
 mod validator;
 mod error;
+mod cron;
 
 pub use schema::*;
 pub use parser::*;
 pub use validator::*;
 pub use error::*;
+pub use cron::*;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_placeholder() {
         // TODO: Phase 1.2 - Add configuration tests
-        assert!(true);
     }
 }
@@ -2,10 +2,11 @@
 //
 // Rust structs that match the YAML/JSON schema
 
+use super::CronSchedule;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Top-level configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,14 +17,50 @@ pub struct Configuration {
     pub endpoints: Vec<Endpoint>,
     #[serde(default)]
     pub endpoint_groups: Vec<EndpointGroup>,
+    /// One bucket shared by every endpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Simulated OAuth2 clients, users and token settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+    /// Forward requests that match no endpoint to an upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<FallthroughProxy>,
     #[serde(default)]
     pub behavior_windows: Vec<BehaviorWindow>,
     #[serde(default)]
     pub burst_events: Vec<BurstEvent>,
     #[serde(default)]
+    pub behavior_profiles: Vec<BehaviorProfile>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    #[serde(default)]
+    pub jobs: Vec<Job>,
+    #[serde(default)]
     pub workflows: Vec<Workflow>,
 }
 
+impl Configuration {
+    /// Configuration holding only `endpoints`, as produced from recorded traffic.
+    pub fn from_endpoints(metadata: Metadata, endpoints: Vec<Endpoint>) -> Self {
+        Self {
+            version: "1.0".to_string(),
+            metadata,
+            endpoints,
+            endpoint_groups: vec![],
+            rate_limit: None,
+            auth: None,
+            proxy: None,
+            behavior_windows: vec![],
+            burst_events: vec![],
+            behavior_profiles: vec![],
+            resources: vec![],
+            jobs: vec![],
+            workflows: vec![],
+        }
+    }
+}
+
 /// Configuration metadata
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Metadata {
@@ -53,10 +90,79 @@ pub struct Endpoint {
     pub rate_limit: Option<RateLimit>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bandwidth_cap: Option<BandwidthCap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<CapacityModel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency_limit: Option<ConcurrencyLimit>,
+    /// Serve a resource collection operation instead of the static response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<ResourceBinding>,
+    /// Pick the response by workflow state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<WorkflowBinding>,
+    /// Submit or poll an asynchronous job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job: Option<JobBinding>,
+    /// Require a bearer token, or issue tokens from this endpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<EndpointAuth>,
+    /// Scopes a token must carry when `auth` is `required`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_scopes: Vec<String>,
+    /// Replay responses for repeated `Idempotency-Key`s and fail early attempts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency: Option<Idempotency>,
+    /// Other simulated endpoints called before responding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Dependencies>,
+    /// Outbound HTTP callbacks scheduled after a successful response.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub callbacks: Vec<Callback>,
+    /// Serve the upstream's responses instead of the static response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyTarget>,
     #[serde(skip)]
     pub loaded_at: Option<Instant>,
     #[serde(skip)]
-    pub rate_limiter: Option<Arc<Mutex<TokenBucket>>>,
+    pub rate_limiter: Option<Arc<Mutex<RateLimiter>>>,
+}
+
+impl Endpoint {
+    /// Plain endpoint serving `response`, with no optional behavior set.
+    pub fn new(id: String, method: HttpMethod, path: String, latency: LatencyConfig, response: Response) -> Self {
+        Self {
+            id,
+            method,
+            path,
+            request: None,
+            latency,
+            response,
+            error_profile: ErrorProfile::default(),
+            rate_limit: None,
+            bandwidth_cap: None,
+            capacity: None,
+            concurrency_limit: None,
+            resource: None,
+            workflow: None,
+            job: None,
+            auth: None,
+            required_scopes: vec![],
+            idempotency: None,
+            dependencies: None,
+            callbacks: vec![],
+            proxy: None,
+            loaded_at: None,
+            rate_limiter: None,
+        }
+    }
+
+    /// Names of the `{param}` segments in the path, in order.
+    pub fn path_params(&self) -> Vec<&str> {
+        self.path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+            .collect()
+    }
 }
 
 /// Endpoint group definition.
@@ -64,6 +170,18 @@ pub struct Endpoint {
 pub struct EndpointGroup {
     pub id: String,
     pub endpoint_ids: Vec<String>,
+    /// Shared by every endpoint in the group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<CapacityModel>,
+    /// Shared by every endpoint in the group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency_limit: Option<ConcurrencyLimit>,
+    /// One bucket shared by every endpoint in the group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Upstream for member endpoints without a proxy of their own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyTarget>,
 }
 
 /// Scope for behavior rules.
@@ -92,14 +210,27 @@ pub struct BehaviorSchedule {
     pub max_occurrences: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_delay_ms: Option<f64>,
+    /// Cron expression (5-field, minute granularity) for wall-clock schedules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    /// IANA time zone used to evaluate `cron` (defaults to UTC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// `cron` and `timezone`, parsed when the configuration is loaded.
+    #[serde(skip)]
+    pub parsed_cron: Option<Arc<CronSchedule>>,
 }
 
 /// Schedule modes.
+///
+/// `fixed` and `recurring` are relative to config load time; `cron` follows
+/// the wall clock.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ScheduleMode {
     Fixed,
     Recurring,
+    Cron,
 }
 
 /// Ramp configuration.
@@ -141,6 +272,12 @@ pub struct BehaviorWindow {
     pub id: Option<String>,
     pub scope: BehaviorScope,
     pub schedule: BehaviorSchedule,
+    /// Higher priority windows are applied last and win latency overrides.
+    #[serde(default)]
+    pub priority: i32,
+    /// Stack on top of lower priority active windows instead of shadowing them.
+    #[serde(default)]
+    pub stack: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ramp: Option<RampConfig>,
     #[serde(default = "default_error_mix")]
@@ -159,6 +296,15 @@ pub struct BurstEvent {
     pub scope: BehaviorScope,
     pub frequency: BurstFrequency,
     pub duration_ms: f64,
+    /// Optional per-burst duration distribution; samples are capped at `duration_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_distribution: Option<LatencyConfig>,
+    /// Higher priority bursts are applied last and win latency spikes.
+    #[serde(default)]
+    pub priority: i32,
+    /// Stack on top of lower priority active bursts instead of shadowing them.
+    #[serde(default)]
+    pub stack: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ramp: Option<RampConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -168,11 +314,26 @@ pub struct BurstEvent {
 }
 
 /// Burst frequency settings.
+///
+/// For `poisson` arrivals `every_ms` is the mean interval between bursts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BurstFrequency {
+    #[serde(default)]
+    pub arrival: BurstArrival,
     pub every_ms: f64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub jitter_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// Burst arrival processes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BurstArrival {
+    #[default]
+    Periodic,
+    Poisson,
 }
 
 /// Error spike settings for bursts.
@@ -183,12 +344,178 @@ pub struct ErrorSpike {
     pub error_profile: ErrorProfile,
 }
 
+/// Long-running behavior shape defined by interpolated keyframes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BehaviorProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub scope: BehaviorScope,
+    pub keyframes: Vec<Keyframe>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpolation: Option<RampCurve>,
+    /// Loop the keyframes, restarting at the first keyframe after the last.
+    #[serde(default)]
+    pub repeat: bool,
+    #[serde(default = "default_error_mix")]
+    pub error_mix: ErrorMix,
+    #[serde(default)]
+    pub error_codes: Vec<u16>,
+    #[serde(default)]
+    pub error_body: String,
+}
+
+/// Profile keyframe, relative to config load time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub at_ms: f64,
+    #[serde(default = "default_factor")]
+    pub latency_multiplier: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_rate: Option<f64>,
+    #[serde(default = "default_factor")]
+    pub rate_limit_factor: f64,
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
+
 /// Request rate limiting configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimit {
-    pub requests_per_second: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_second: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_hour: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub burst: Option<f64>,
+    /// Give each client its own bucket instead of one for all callers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<RateLimitKey>,
+    /// Response returned when the limit is exceeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<RateLimitResponse>,
+}
+
+/// Custom rejection for an exceeded rate limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitResponse {
+    #[serde(default = "default_rate_limit_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default = "default_rate_limit_body")]
+    pub body: String,
+}
+
+fn default_rate_limit_status() -> u16 {
+    429
+}
+
+fn default_rate_limit_body() -> String {
+    "Rate limit exceeded".to_string()
+}
+
+/// Client key selector for per-client rate limiting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitKey {
+    pub source: KeySource,
+    /// Header or query parameter name; required for `header` and `query`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default = "default_max_keys")]
+    pub max_keys: usize,
+    #[serde(default = "default_idle_timeout_ms")]
+    pub idle_timeout_ms: f64,
+    #[serde(default)]
+    pub missing: MissingKeyPolicy,
+}
+
+fn default_max_keys() -> usize {
+    10_000
+}
+
+fn default_idle_timeout_ms() -> f64 {
+    300_000.0
+}
+
+/// Where the client key is read from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    Header,
+    ApiKey,
+    Query,
+    RemoteIp,
+}
+
+/// Handling of requests that carry no client key
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MissingKeyPolicy {
+    /// Keyless requests share one bucket.
+    #[default]
+    Shared,
+    /// Keyless requests are not limited.
+    Allow,
+    /// Keyless requests are rejected as over the limit.
+    Reject,
+}
+
+impl RateLimit {
+    /// Sustained refill rate in requests per second.
+    pub fn refill_per_second(&self) -> f64 {
+        self.requests_per_second
+            .or(self.requests_per_hour.map(|per_hour| per_hour / 3600.0))
+            .unwrap_or(0.0)
+    }
+
+    /// Bucket size: `burst`, else one second (or one hour) worth of requests.
+    pub fn capacity(&self) -> f64 {
+        self.burst
+            .or(self.requests_per_second)
+            .or(self.requests_per_hour)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Capacity model: worker slots, a bounded wait queue and load-driven latency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityModel {
+    pub workers: u32,
+    #[serde(default)]
+    pub queue_size: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub load_curve: Vec<LoadPoint>,
+    #[serde(default = "default_overflow_status")]
+    pub overflow_status: u16,
+    #[serde(default)]
+    pub overflow_body: String,
+}
+
+fn default_overflow_status() -> u16 {
+    503
+}
+
+/// Maximum concurrent requests, e.g. the size of a connection pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyLimit {
+    pub max_in_flight: u32,
+    #[serde(default)]
+    pub queue_size: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_timeout_ms: Option<f64>,
+    #[serde(default = "default_overflow_status")]
+    pub rejection_status: u16,
+    #[serde(default)]
+    pub rejection_body: String,
+}
+
+/// Latency multiplier at a given number of in-flight requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadPoint {
+    pub in_flight: u32,
+    pub latency_multiplier: f64,
 }
 
 /// Bandwidth cap configuration
@@ -248,6 +575,7 @@ pub enum DistributionType {
     Normal,
     Exponential,
     Uniform,
+    #[serde(rename = "log_normal", alias = "lognormal")]
     LogNormal,
     Mixture,
 }
@@ -293,6 +621,7 @@ pub struct Response {
     pub status: u16,
     #[serde(default = "default_headers")]
     pub headers: HashMap<String, String>,
+    #[serde(default)]
     pub body: String,
 }
 
@@ -348,6 +677,129 @@ impl Default for ErrorProfile {
     }
 }
 
+/// Outcome of a rate limit check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    Limited,
+    Exempt,
+}
+
+/// Snapshot of a token bucket, as reported in rate limit headers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketStatus {
+    pub limit: f64,
+    pub remaining: f64,
+    /// Seconds until the bucket is full again.
+    pub reset_secs: f64,
+    /// Seconds until the next token is available.
+    pub retry_after_secs: f64,
+}
+
+/// Runtime rate limiter: one bucket for all callers, or one per client key.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    shared: TokenBucket,
+    clients: HashMap<String, TokenBucket>,
+    last_sweep: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(limit: &RateLimit) -> Self {
+        Self {
+            limit: limit.clone(),
+            shared: TokenBucket::from_limit(limit),
+            clients: HashMap::new(),
+            last_sweep: Instant::now(),
+        }
+    }
+
+    pub fn key(&self) -> Option<&RateLimitKey> {
+        self.limit.key.as_ref()
+    }
+
+    pub fn response(&self) -> Option<&RateLimitResponse> {
+        self.limit.response.as_ref()
+    }
+
+    /// State of the caller's bucket as of the last `check`, if it has one.
+    pub fn status(&self, client: Option<&str>, refill_factor: f64) -> Option<BucketStatus> {
+        let bucket = match (self.limit.key.as_ref(), client) {
+            (Some(_), Some(client)) => self.clients.get(client)?,
+            (Some(key), None) if key.missing != MissingKeyPolicy::Shared => return None,
+            _ => &self.shared,
+        };
+        Some(bucket.status(refill_factor))
+    }
+
+    /// Refill the caller's bucket and report whether a token is available.
+    pub fn check(&mut self, client: Option<&str>, refill_factor: f64) -> RateDecision {
+        let Some(bucket) = self.bucket(client) else {
+            return match self.key().map(|key| &key.missing) {
+                Some(MissingKeyPolicy::Allow) => RateDecision::Exempt,
+                _ => RateDecision::Limited,
+            };
+        };
+
+        bucket.refill(refill_factor);
+        if bucket.has_token() {
+            RateDecision::Allowed
+        } else {
+            RateDecision::Limited
+        }
+    }
+
+    /// Take the token found by a preceding `check`.
+    pub fn take(&mut self, client: Option<&str>) {
+        if let Some(bucket) = self.bucket(client) {
+            bucket.take();
+        }
+    }
+
+    /// Bucket for a client, created on first use; `None` when a keyless
+    /// request is not served from the shared bucket.
+    fn bucket(&mut self, client: Option<&str>) -> Option<&mut TokenBucket> {
+        let Some(key) = self.limit.key.clone() else {
+            return Some(&mut self.shared);
+        };
+        let Some(client) = client else {
+            return (key.missing == MissingKeyPolicy::Shared).then_some(&mut self.shared);
+        };
+
+        let idle_timeout = Duration::from_secs_f64(key.idle_timeout_ms.max(0.0) / 1000.0);
+        let now = Instant::now();
+        if now.duration_since(self.last_sweep) >= idle_timeout {
+            self.evict_idle(now, idle_timeout);
+        }
+
+        if !self.clients.contains_key(client) {
+            if self.clients.len() >= key.max_keys.max(1) {
+                self.evict_idle(now, idle_timeout);
+            }
+            if self.clients.len() >= key.max_keys.max(1) {
+                let oldest = self
+                    .clients
+                    .iter()
+                    .min_by_key(|(_, bucket)| bucket.last_refill)
+                    .map(|(client, _)| client.clone());
+                if let Some(oldest) = oldest {
+                    self.clients.remove(&oldest);
+                }
+            }
+            self.clients
+                .insert(client.to_string(), TokenBucket::from_limit(&self.limit));
+        }
+        self.clients.get_mut(client)
+    }
+
+    fn evict_idle(&mut self, now: Instant, idle_timeout: Duration) {
+        self.clients
+            .retain(|_, bucket| now.duration_since(bucket.last_refill) < idle_timeout);
+        self.last_sweep = now;
+    }
+}
+
 /// Runtime token bucket for rate limiting (not serialized).
 #[derive(Debug)]
 pub struct TokenBucket {
@@ -369,28 +821,513 @@ impl TokenBucket {
         }
     }
 
-    pub fn try_take(&mut self) -> bool {
+    pub fn from_limit(limit: &RateLimit) -> Self {
+        Self::new(limit.refill_per_second(), Some(limit.capacity()))
+    }
+
+    /// Add the tokens accrued since the last refill at `refill_factor` times the configured rate.
+    pub fn refill(&mut self, refill_factor: f64) {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_refill).as_secs_f64();
         self.last_refill = now;
 
-        if elapsed > 0.0 && self.refill_rate > 0.0 {
-            let refill = elapsed * self.refill_rate;
+        let refill_rate = self.refill_rate * refill_factor.max(0.0);
+        if elapsed > 0.0 && refill_rate > 0.0 {
+            let refill = elapsed * refill_rate;
             self.tokens = (self.tokens + refill).min(self.capacity);
         }
+    }
 
-        if self.tokens >= 1.0 {
-            self.tokens -= 1.0;
-            true
-        } else {
-            false
+    pub fn status(&self, refill_factor: f64) -> BucketStatus {
+        let refill_rate = self.refill_rate * refill_factor.max(0.0);
+        let seconds_until = |tokens: f64| {
+            if tokens <= self.tokens {
+                0.0
+            } else if refill_rate > 0.0 {
+                (tokens - self.tokens) / refill_rate
+            } else {
+                f64::INFINITY
+            }
+        };
+        BucketStatus {
+            limit: self.capacity,
+            remaining: self.tokens.max(0.0),
+            reset_secs: seconds_until(self.capacity),
+            retry_after_secs: seconds_until(1.0),
         }
     }
+
+    pub fn has_token(&self) -> bool {
+        self.tokens >= 1.0
+    }
+
+    pub fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
 }
 
-/// Workflow definition (Phase 2)
+/// In-memory collection of JSON items, served by endpoints bound to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resource {
+    pub id: String,
+    /// Item field holding the item id.
+    #[serde(default = "default_id_field")]
+    pub id_field: String,
+    #[serde(default)]
+    pub id_strategy: IdStrategy,
+    /// Items present at load and after a reset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seed: Vec<serde_json::Value>,
+}
+
+fn default_id_field() -> String {
+    "id".to_string()
+}
+
+/// How ids are assigned to created items that don't carry one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdStrategy {
+    /// Increasing integers, continuing after the largest numeric id.
+    #[default]
+    Sequence,
+    Uuid,
+}
+
+/// Binds an endpoint to an operation on a resource collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceBinding {
+    pub collection: String,
+    pub operation: ResourceOperation,
+    /// Path parameter holding the item id; defaults to the last one in the path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_param: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceOperation {
+    Create,
+    Read,
+    List,
+    Update,
+    Delete,
+}
+
+impl ResourceOperation {
+    /// Whether the operation addresses a single existing item.
+    pub fn needs_id(self) -> bool {
+        matches!(self, Self::Read | Self::Update | Self::Delete)
+    }
+}
+
+/// OAuth2 settings shared by token endpoints and protected endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    #[serde(default)]
+    pub token_format: TokenFormat,
+    /// HMAC key for `jwt` tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_secret: Option<String>,
+    #[serde(default = "default_issuer")]
+    pub issuer: String,
+    #[serde(default = "default_expires_in_secs")]
+    pub expires_in_secs: u64,
+    /// Accepted for the client credentials grant, and to authenticate password grants.
+    #[serde(default)]
+    pub clients: Vec<OAuthClient>,
+    /// Accepted for the password grant.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<OAuthUser>,
+}
+
+fn default_issuer() -> String {
+    "web-simulant".to_string()
+}
+
+fn default_expires_in_secs() -> u64 {
+    3600
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenFormat {
+    /// Random tokens, tracked by the simulator.
+    #[default]
+    Opaque,
+    /// HS256-signed JWTs, validated from their claims.
+    Jwt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthClient {
+    pub client_id: String,
+    pub client_secret: String,
+    /// Scopes the client may request; tokens get all of them by default.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthUser {
+    pub username: String,
+    pub password: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointAuth {
+    /// Reject requests without a valid bearer token.
+    Required,
+    /// Issue tokens (client credentials and password grants).
+    TokenEndpoint,
+}
+
+/// Per-key request tracking: replay of the first response, scripted retry failures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Idempotency {
+    #[serde(default = "default_idempotency_header")]
+    pub header: String,
+    /// How long a key is remembered after its first request.
+    #[serde(default = "default_idempotency_ttl_ms")]
+    pub ttl_ms: f64,
+    /// Keys tracked at once; the oldest are dropped beyond this.
+    #[serde(default = "default_max_keys")]
+    pub max_keys: usize,
+    /// Return the stored response for repeated keys instead of running the endpoint again.
+    #[serde(default = "default_replay")]
+    pub replay: bool,
+    /// Fail the first attempts of every key, then behave normally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_attempts: Option<AttemptFailure>,
+}
+
+fn default_idempotency_header() -> String {
+    "Idempotency-Key".to_string()
+}
+
+fn default_idempotency_ttl_ms() -> f64 {
+    86_400_000.0
+}
+
+fn default_replay() -> bool {
+    true
+}
+
+fn default_attempt_failure_status() -> u16 {
+    503
+}
+
+/// Response for the scripted failures of a key's first attempts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttemptFailure {
+    pub attempts: u32,
+    #[serde(default = "default_attempt_failure_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// Downstream calls made through the engine, so their latency, errors and
+/// limits carry over to the caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependencies {
+    #[serde(default)]
+    pub mode: DependencyMode,
+    pub calls: Vec<DependencyCall>,
+    /// Returned when a call without a fallback fails.
+    #[serde(default = "default_dependency_error_status")]
+    pub error_status: u16,
+    /// Returned when a call without a fallback times out.
+    #[serde(default = "default_dependency_timeout_status")]
+    pub timeout_status: u16,
+    /// Body for propagated failures; a JSON error naming the dependency when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_body: Option<String>,
+}
+
+fn default_dependency_error_status() -> u16 {
+    502
+}
+
+fn default_dependency_timeout_status() -> u16 {
+    504
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyMode {
+    /// One after another; stops at the first failure without a fallback.
+    #[default]
+    Sequential,
+    /// All at once; takes as long as the slowest call.
+    Parallel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyCall {
+    pub endpoint_id: String,
+    /// Give up on the call after this long.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<f64>,
+    /// Served instead of an error when the call fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<Response>,
+}
+
+/// Outbound webhook sent some time after the endpoint responds.
+///
+/// `url`, header values and `body` are templates: `{{request.body.<field>}}`,
+/// `{{request.path.<param>}}`, `{{request.query.<name>}}`,
+/// `{{request.header.<name>}}`, `{{response.body.<field>}}`, `{{callback.id}}`
+/// and `{{now}}` are replaced; nested fields use dots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Callback {
+    pub url: String,
+    #[serde(default = "default_callback_method")]
+    pub method: HttpMethod,
+    /// Time from the response to the first attempt.
+    pub delay: LatencyConfig,
+    #[serde(default = "default_headers")]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub retry: CallbackRetry,
+}
+
+fn default_callback_method() -> HttpMethod {
+    HttpMethod::Post
+}
+
+/// Attempts and exponential backoff for failed deliveries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallbackRetry {
+    /// Total attempts, including the first.
+    #[serde(default = "default_callback_attempts")]
+    pub max_attempts: u32,
+    /// Wait before the second attempt; later waits grow by `multiplier`.
+    #[serde(default = "default_callback_backoff_ms")]
+    pub backoff_ms: f64,
+    #[serde(default = "default_callback_multiplier")]
+    pub multiplier: f64,
+    /// Per-attempt timeout.
+    #[serde(default = "default_callback_timeout_ms")]
+    pub timeout_ms: f64,
+}
+
+impl Default for CallbackRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_callback_attempts(),
+            backoff_ms: default_callback_backoff_ms(),
+            multiplier: default_callback_multiplier(),
+            timeout_ms: default_callback_timeout_ms(),
+        }
+    }
+}
+
+fn default_callback_attempts() -> u32 {
+    3
+}
+
+fn default_callback_backoff_ms() -> f64 {
+    1_000.0
+}
+
+fn default_callback_multiplier() -> f64 {
+    2.0
+}
+
+fn default_callback_timeout_ms() -> f64 {
+    5_000.0
+}
+
+/// Real service whose responses are forwarded, with faults applied on top.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyTarget {
+    /// Base URL; the request path and query are appended.
+    pub upstream: String,
+    #[serde(default = "default_proxy_timeout_ms")]
+    pub timeout_ms: f64,
+    /// Capture forwarded traffic so it can be exported as a configuration.
+    #[serde(default)]
+    pub record: bool,
+}
+
+fn default_proxy_timeout_ms() -> f64 {
+    30_000.0
+}
+
+/// Catch-all proxy for paths no endpoint matches. Global behavior windows,
+/// bursts and profiles apply to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallthroughProxy {
+    pub upstream: String,
+    #[serde(default = "default_proxy_timeout_ms")]
+    pub timeout_ms: f64,
+    /// Added to the upstream's own latency.
+    #[serde(default = "default_fallthrough_latency")]
+    pub latency: LatencyConfig,
+    #[serde(default)]
+    pub error_profile: ErrorProfile,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth_cap: Option<BandwidthCap>,
+    /// Capture forwarded traffic so it can be exported as a configuration.
+    #[serde(default)]
+    pub record: bool,
+}
+
+fn default_fallthrough_latency() -> LatencyConfig {
+    LatencyConfig {
+        distribution: DistributionType::Fixed,
+        params: DistributionParams::Fixed { delay_ms: 0.0 },
+    }
+}
+
+/// Id of the endpoint standing in for the catch-all proxy.
+pub const FALLTHROUGH_ENDPOINT_ID: &str = "*";
+
+impl FallthroughProxy {
+    pub fn target(&self) -> ProxyTarget {
+        ProxyTarget {
+            upstream: self.upstream.clone(),
+            timeout_ms: self.timeout_ms,
+            record: self.record,
+        }
+    }
+
+    /// Endpoint that serves unmatched requests through the proxy.
+    pub fn endpoint(&self) -> Endpoint {
+        let response = Response {
+            status: 200,
+            headers: HashMap::new(),
+            body: String::new(),
+        };
+        Endpoint {
+            error_profile: self.error_profile.clone(),
+            bandwidth_cap: self.bandwidth_cap.clone(),
+            proxy: Some(self.target()),
+            ..Endpoint::new(
+                FALLTHROUGH_ENDPOINT_ID.to_string(),
+                HttpMethod::Get,
+                "/".to_string(),
+                self.latency.clone(),
+                response,
+            )
+        }
+    }
+}
+
+/// Long-running job type: submissions complete after a sampled duration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    /// Time from submission to completion.
+    pub duration: LatencyConfig,
+    /// Jobs report `pending` this long, then `running` until complete.
+    #[serde(default)]
+    pub pending_ms: f64,
+    /// Returned by the status endpoint once a job succeeded.
+    pub result: Response,
+    /// Share and shape of jobs that fail.
+    #[serde(default)]
+    pub error_profile: ErrorProfile,
+    /// How long finished jobs stay queryable.
+    #[serde(default = "default_job_ttl_ms")]
+    pub ttl_ms: f64,
+    /// Jobs tracked at once; the oldest are dropped beyond this.
+    #[serde(default = "default_max_jobs")]
+    pub max_jobs: usize,
+}
+
+fn default_job_ttl_ms() -> f64 {
+    3_600_000.0
+}
+
+fn default_max_jobs() -> usize {
+    10_000
+}
+
+/// Binds an endpoint to a job type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobBinding {
+    pub job: String,
+    pub operation: JobOperation,
+    /// Path parameter holding the job id; defaults to the last one in the path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_param: Option<String>,
+    /// Status URL template for the `Location` header, with `{id}` for the job id.
+    /// Defaults to the submit path followed by `/{id}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobOperation {
+    Submit,
+    Status,
+}
+
+/// Named state machine; endpoint variants can require a state and move to another.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workflow {
     pub id: String,
-    // TODO: Phase 2 - Define workflow structure
+    #[serde(default = "default_initial_state")]
+    pub initial_state: String,
+    /// Track state per session instead of once for all clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<WorkflowSession>,
+}
+
+fn default_initial_state() -> String {
+    "started".to_string()
+}
+
+/// Where a request's session key comes from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSession {
+    pub source: SessionSource,
+    /// Header or cookie name.
+    pub name: String,
+    /// Sessions tracked at once; the least recently used is dropped beyond this.
+    #[serde(default = "default_max_sessions")]
+    pub max_sessions: usize,
+}
+
+fn default_max_sessions() -> usize {
+    10_000
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionSource {
+    Header,
+    Cookie,
+}
+
+/// Binds an endpoint to a workflow with state-dependent responses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowBinding {
+    pub workflow: String,
+    /// Checked in order; the first whose `required_state` matches is used.
+    pub variants: Vec<StateVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateVariant {
+    /// Matches any state when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_state: Option<String>,
+    /// State to move to after responding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_state: Option<String>,
+    /// Response for this variant; the endpoint response when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<Response>,
 }
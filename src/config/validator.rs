@@ -4,12 +4,24 @@
 
 use super::error::ConfigError;
 use super::{
-    BandwidthCap, BehaviorSchedule, BehaviorScope, BehaviorWindow, BodyMatchType, BurstEvent,
-    Configuration, DistributionParams, DistributionType, Endpoint, EndpointGroup, ErrorMix,
-    ErrorProfile, HttpMethod, LatencyConfig, MixtureComponent, RampConfig,
-    RateLimit, RequestMatch, Response, ScheduleMode, ValidationError,
+    AuthConfig, Callback, EndpointAuth, TokenFormat,     BandwidthCap, BehaviorProfile, BehaviorSchedule, BehaviorScope, BehaviorWindow, BodyMatchType, BurstArrival, BurstEvent,
+    CapacityModel, ConcurrencyLimit, Configuration, CronError, CronSchedule, DistributionParams, DistributionType, Endpoint, EndpointGroup, ErrorMix,
+    ErrorProfile, HttpMethod, Idempotency, Job, ProxyTarget, JobOperation, KeySource, LatencyConfig, MixtureComponent, RampConfig,
+    RateLimit, RequestMatch, Resource, Response, ScheduleMode, ValidationError, Workflow,
 };
-use std::collections::HashSet;
+use chrono::{TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Occurrences enumerated when comparing two repeating schedules; a recurring
+/// schedule limited to more is compared by its periods while it runs.
+const MAX_ENUMERATED_OCCURRENCES: u32 = 10_000;
+/// Two cron schedules are compared over this (leap) year, so that validation
+/// does not depend on when it runs.
+const CRON_OVERLAP_YEAR: i32 = 2024;
+const CRON_OVERLAP_HORIZON_MS: f64 = 366.0 * 24.0 * 3_600_000.0;
+/// Mean intervals a poisson burst may last; every request samples the arrivals
+/// of each interval a burst could still be running from.
+const MAX_POISSON_BURST_INTERVALS: f64 = 100.0;
 
 /// Validate a configuration
 pub fn validate(config: &Configuration) -> Result<(), ConfigError> {
@@ -19,7 +31,8 @@ pub fn validate(config: &Configuration) -> Result<(), ConfigError> {
         push_error(&mut errors, "version", "must be '1.0'", None);
     }
 
-    if config.endpoints.is_empty() {
+    // A catch-all proxy serves requests on its own, e.g. while recording.
+    if config.endpoints.is_empty() && config.proxy.is_none() {
         push_error(&mut errors, "endpoints", "must contain at least one endpoint", None);
     }
 
@@ -50,8 +63,16 @@ pub fn validate(config: &Configuration) -> Result<(), ConfigError> {
     }
 
     validate_endpoint_groups(&config.endpoint_groups, &ids, &mut errors);
+    validate_rate_limit(config.rate_limit.as_ref(), &mut errors, Some("global".to_string()));
     validate_behavior_windows(&config.behavior_windows, &config.endpoint_groups, &ids, &mut errors);
     validate_burst_events(&config.burst_events, &config.endpoint_groups, &ids, &mut errors);
+    validate_behavior_profiles(&config.behavior_profiles, &config.endpoint_groups, &ids, &mut errors);
+    validate_resources(&config.resources, &config.endpoints, &mut errors);
+    validate_workflows(&config.workflows, &config.endpoints, &mut errors);
+    validate_jobs(&config.jobs, &config.endpoints, &mut errors);
+    validate_auth(config.auth.as_ref(), &config.endpoints, &mut errors);
+    validate_dependencies(&config.endpoints, &mut errors);
+    validate_proxies(config, &mut errors);
 
     if !errors.is_empty() {
         let count = errors.len();
@@ -82,9 +103,47 @@ fn validate_endpoint(endpoint: &Endpoint, errors: &mut Vec<ValidationError>) {
     validate_error_profile(&endpoint.error_profile, errors, location.clone());
     validate_rate_limit(endpoint.rate_limit.as_ref(), errors, location.clone());
     validate_bandwidth_cap(endpoint.bandwidth_cap.as_ref(), errors, location.clone());
+    validate_capacity(endpoint.capacity.as_ref(), errors, location.clone());
+    validate_concurrency_limit(endpoint.concurrency_limit.as_ref(), errors, location.clone());
+    validate_idempotency(endpoint.idempotency.as_ref(), errors, location.clone());
+    validate_callbacks(&endpoint.callbacks, errors, location.clone());
     validate_request_match(endpoint.request.as_ref(), errors, location);
 }
 
+fn validate_idempotency(
+    idempotency: Option<&Idempotency>,
+    errors: &mut Vec<ValidationError>,
+    location: Option<String>,
+) {
+    let Some(idempotency) = idempotency else {
+        return;
+    };
+
+    if idempotency.header.trim().is_empty() {
+        push_error(errors, "idempotency.header", "header must not be empty", location.clone());
+    }
+    if !idempotency.ttl_ms.is_finite() || idempotency.ttl_ms <= 0.0 {
+        push_error(errors, "idempotency.ttl_ms", "must be > 0", location.clone());
+    }
+    if idempotency.max_keys == 0 {
+        push_error(errors, "idempotency.max_keys", "must be >= 1", location.clone());
+    }
+
+    if let Some(failure) = &idempotency.fail_attempts {
+        if failure.attempts == 0 {
+            push_error(errors, "idempotency.fail_attempts.attempts", "must be >= 1", location.clone());
+        }
+        if !is_valid_status(failure.status) {
+            push_error(
+                errors,
+                "idempotency.fail_attempts.status",
+                "invalid HTTP status code",
+                location,
+            );
+        }
+    }
+}
+
 fn validate_endpoint_groups(
     groups: &[EndpointGroup],
     endpoint_ids: &HashSet<String>,
@@ -105,6 +164,10 @@ fn validate_endpoint_groups(
             push_error(errors, "endpoint_groups.endpoint_ids", "must include at least one endpoint id", Some(group.id.clone()));
         }
 
+        validate_capacity(group.capacity.as_ref(), errors, Some(group.id.clone()));
+        validate_concurrency_limit(group.concurrency_limit.as_ref(), errors, Some(group.id.clone()));
+        validate_rate_limit(group.rate_limit.as_ref(), errors, Some(group.id.clone()));
+
         for endpoint_id in &group.endpoint_ids {
             if !endpoint_ids.contains(endpoint_id) {
                 push_error(
@@ -127,18 +190,62 @@ fn validate_rate_limit(
         return;
     };
 
-    if !rate_limit.requests_per_second.is_finite() || rate_limit.requests_per_second <= 0.0 {
-        push_error(
+    match (rate_limit.requests_per_second, rate_limit.requests_per_hour) {
+        (Some(_), Some(_)) | (None, None) => push_error(
+            errors,
+            "rate_limit",
+            "must define exactly one of requests_per_second or requests_per_hour",
+            location.clone(),
+        ),
+        (Some(per_second), None) if !per_second.is_finite() || per_second <= 0.0 => push_error(
             errors,
             "rate_limit.requests_per_second",
             "must be > 0",
             location.clone(),
-        );
+        ),
+        (None, Some(per_hour)) if !per_hour.is_finite() || per_hour <= 0.0 => push_error(
+            errors,
+            "rate_limit.requests_per_hour",
+            "must be > 0",
+            location.clone(),
+        ),
+        _ => {}
     }
 
     if let Some(burst) = rate_limit.burst {
         if !burst.is_finite() || burst <= 0.0 {
-            push_error(errors, "rate_limit.burst", "must be > 0", location);
+            push_error(errors, "rate_limit.burst", "must be > 0", location.clone());
+        }
+    }
+
+    if let Some(response) = &rate_limit.response {
+        if !is_valid_status(response.status) {
+            push_error(
+                errors,
+                "rate_limit.response.status",
+                "invalid HTTP status code",
+                location.clone(),
+            );
+        }
+    }
+
+    if let Some(key) = &rate_limit.key {
+        let needs_name = matches!(key.source, KeySource::Header | KeySource::Query);
+        if needs_name && key.name.as_ref().is_none_or(|name| name.trim().is_empty()) {
+            push_error(
+                errors,
+                "rate_limit.key.name",
+                "name is required for header and query keys",
+                location.clone(),
+            );
+        }
+
+        if key.max_keys == 0 {
+            push_error(errors, "rate_limit.key.max_keys", "must be >= 1", location.clone());
+        }
+
+        if !key.idle_timeout_ms.is_finite() || key.idle_timeout_ms <= 0.0 {
+            push_error(errors, "rate_limit.key.idle_timeout_ms", "must be > 0", location);
         }
     }
 }
@@ -162,6 +269,103 @@ fn validate_bandwidth_cap(
     }
 }
 
+fn validate_capacity(
+    capacity: Option<&CapacityModel>,
+    errors: &mut Vec<ValidationError>,
+    location: Option<String>,
+) {
+    let Some(capacity) = capacity else {
+        return;
+    };
+
+    if capacity.workers == 0 {
+        push_error(errors, "capacity.workers", "must be >= 1", location.clone());
+    }
+
+    if !is_valid_status(capacity.overflow_status) {
+        push_error(
+            errors,
+            "capacity.overflow_status",
+            "invalid HTTP status code",
+            location.clone(),
+        );
+    }
+
+    let mut previous: Option<u32> = None;
+    for point in &capacity.load_curve {
+        if previous.is_some_and(|previous| point.in_flight <= previous) {
+            push_error(
+                errors,
+                "capacity.load_curve.in_flight",
+                "in_flight must be strictly increasing",
+                location.clone(),
+            );
+        }
+        previous = Some(point.in_flight);
+
+        if !point.latency_multiplier.is_finite() || point.latency_multiplier < 0.0 {
+            push_error(
+                errors,
+                "capacity.load_curve.latency_multiplier",
+                "must be >= 0",
+                location.clone(),
+            );
+        }
+    }
+}
+
+fn validate_concurrency_limit(
+    limit: Option<&ConcurrencyLimit>,
+    errors: &mut Vec<ValidationError>,
+    location: Option<String>,
+) {
+    let Some(limit) = limit else {
+        return;
+    };
+
+    if limit.max_in_flight == 0 {
+        push_error(
+            errors,
+            "concurrency_limit.max_in_flight",
+            "must be >= 1",
+            location.clone(),
+        );
+    }
+
+    if let Some(timeout_ms) = limit.queue_timeout_ms {
+        if !timeout_ms.is_finite() || timeout_ms <= 0.0 {
+            push_error(
+                errors,
+                "concurrency_limit.queue_timeout_ms",
+                "must be > 0",
+                location.clone(),
+            );
+        } else if limit.queue_size == 0 {
+            push_error(
+                errors,
+                "concurrency_limit.queue_timeout_ms",
+                "requires queue_size > 0",
+                location.clone(),
+            );
+        }
+    }
+
+    if !is_valid_status(limit.rejection_status) {
+        push_error(
+            errors,
+            "concurrency_limit.rejection_status",
+            "invalid HTTP status code",
+            location,
+        );
+    }
+}
+
+struct ScheduledWindow<'a> {
+    label: String,
+    scope_key: String,
+    priority: i32,
+    schedule: &'a BehaviorSchedule,
+}
 
 fn validate_behavior_windows(
     windows: &[BehaviorWindow],
@@ -169,8 +373,7 @@ fn validate_behavior_windows(
     endpoint_ids: &HashSet<String>,
     errors: &mut Vec<ValidationError>,
 ) {
-    let mut scope_fixed: Vec<(String, f64, f64)> = Vec::new();
-    let mut scope_recurring = HashSet::new();
+    let mut scheduled = Vec::new();
     let group_ids: HashSet<String> = groups.iter().map(|group| group.id.clone()).collect();
 
     for (index, window) in windows.iter().enumerate() {
@@ -197,35 +400,17 @@ fn validate_behavior_windows(
             );
         }
 
-        match window.schedule.mode {
-            ScheduleMode::Fixed => {
-                let start_ms = window.schedule.start_offset_ms.unwrap_or(0.0);
-                let end_ms = start_ms + window.schedule.duration_ms;
-                if let Some(scope_key) = scope_key.as_ref() {
-                    scope_fixed.push((scope_key.clone(), start_ms, end_ms));
-                }
-            }
-            ScheduleMode::Recurring => {
-                if let Some(scope_key) = scope_key.as_ref() {
-                    if !scope_recurring.insert(scope_key.clone()) {
-                        push_error(
-                            errors,
-                            "behavior_windows.schedule",
-                            "multiple recurring windows for the same scope",
-                            Some(scope_key.clone()),
-                        );
-                    }
-                }
-            }
+        if let Some(scope_key) = scope_key.as_ref() {
+            scheduled.push(ScheduledWindow {
+                label: window.id.clone().unwrap_or_else(|| format!("#{}", index)),
+                scope_key: scope_key.clone(),
+                priority: window.priority,
+                schedule: &window.schedule,
+            });
         }
 
-        if window.ramp.is_some() {
-            validate_ramp(
-                window.ramp.as_ref().unwrap(),
-                "behavior_windows.ramp",
-                errors,
-                scope_key.clone(),
-            );
+        if let Some(ramp) = &window.ramp {
+            validate_ramp(ramp, "behavior_windows.ramp", errors, scope_key.clone());
         }
 
         if scope_key.is_none() {
@@ -238,19 +423,179 @@ fn validate_behavior_windows(
         }
     }
 
-    scope_fixed.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal)));
-    for window in scope_fixed.windows(2) {
-        let (scope_a, _start_a, end_a) = (&window[0].0, window[0].1, window[0].2);
-        let (scope_b, start_b, _end_b) = (&window[1].0, window[1].1, window[1].2);
-        if scope_a == scope_b && start_b < end_a {
-            push_error(
-                errors,
-                "behavior_windows.schedule",
-                "fixed windows overlap for the same scope",
-                Some(scope_a.clone()),
-            );
+    // Overlapping windows are only well defined when their priorities differ.
+    for (index, first) in scheduled.iter().enumerate() {
+        for second in &scheduled[index + 1..] {
+            if first.scope_key != second.scope_key || first.priority != second.priority {
+                continue;
+            }
+            if schedules_overlap(first.schedule, second.schedule) {
+                push_error(
+                    errors,
+                    "behavior_windows.schedule",
+                    &format!(
+                        "windows '{}' and '{}' overlap for the same scope with equal priority",
+                        first.label, second.label
+                    ),
+                    Some(first.scope_key.clone()),
+                );
+            }
+        }
+    }
+}
+
+fn schedules_overlap(first: &BehaviorSchedule, second: &BehaviorSchedule) -> bool {
+    match (&first.mode, &second.mode) {
+        (ScheduleMode::Fixed, ScheduleMode::Fixed) => {
+            let (start_a, end_a) = fixed_interval(first);
+            let (start_b, end_b) = fixed_interval(second);
+            start_a < end_b && start_b < end_a
+        }
+        (ScheduleMode::Fixed, ScheduleMode::Recurring) => {
+            let (start_ms, end_ms) = fixed_interval(first);
+            recurring_overlaps(second, start_ms, end_ms)
+        }
+        (ScheduleMode::Recurring, ScheduleMode::Fixed) => {
+            let (start_ms, end_ms) = fixed_interval(second);
+            recurring_overlaps(first, start_ms, end_ms)
+        }
+        (ScheduleMode::Recurring, ScheduleMode::Recurring) => recurring_pair_overlaps(first, second),
+        (ScheduleMode::Cron, ScheduleMode::Cron) => cron_overlaps(first, second),
+        // Load-relative and wall-clock schedules cannot be compared statically.
+        (ScheduleMode::Fixed | ScheduleMode::Recurring, ScheduleMode::Cron)
+        | (ScheduleMode::Cron, ScheduleMode::Fixed | ScheduleMode::Recurring) => false,
+    }
+}
+
+/// Whether two recurring schedules (widened by their jitter) ever collide.
+/// A schedule with a few occurrences is checked occurrence by occurrence;
+/// otherwise the gap between two starts,
+/// `min_delay_b - min_delay_a + m * every_b - k * every_a`, takes every value of
+/// that residue modulo gcd(every_a, every_b) within one lcm(every_a, every_b) of
+/// both running, as long as neither ends first.
+fn recurring_pair_overlaps(first: &BehaviorSchedule, second: &BehaviorSchedule) -> bool {
+    let (every_a, every_b) = (first.every_ms.unwrap_or(0.0), second.every_ms.unwrap_or(0.0));
+    if every_a <= 0.0 || every_b <= 0.0 {
+        return false;
+    }
+
+    let bounded = [(first, second), (second, first)]
+        .into_iter()
+        .filter_map(|(bounded, other)| bounded.max_occurrences.map(|max| (max, bounded, other)))
+        .min_by_key(|(max, _, _)| *max);
+    let enumerate = |max_occurrences: u32, bounded: &BehaviorSchedule, other: &BehaviorSchedule| {
+        let every_ms = bounded.every_ms.unwrap_or(0.0);
+        let min_delay_ms = bounded.min_delay_ms.unwrap_or(0.0);
+        let jitter_ms = bounded.jitter_ms.unwrap_or(0.0);
+        (0..max_occurrences).any(|occurrence| {
+            let start_ms = min_delay_ms + occurrence as f64 * every_ms;
+            recurring_overlaps(other, start_ms - jitter_ms, start_ms + bounded.duration_ms + jitter_ms)
+        })
+    };
+    if let Some((max_occurrences, bounded, other)) = bounded {
+        if max_occurrences <= MAX_ENUMERATED_OCCURRENCES {
+            return enumerate(max_occurrences, bounded, other);
+        }
+    }
+
+    // Periods are compared in whole microseconds.
+    let gcd_ms = gcd((every_a * 1000.0).round() as u64, (every_b * 1000.0).round() as u64) as f64 / 1000.0;
+    if gcd_ms <= 0.0 {
+        return false;
+    }
+    let jitter_ms = first.jitter_ms.unwrap_or(0.0) + second.jitter_ms.unwrap_or(0.0);
+    let (delay_a, delay_b) = (first.min_delay_ms.unwrap_or(0.0), second.min_delay_ms.unwrap_or(0.0));
+    let ends_ms = |schedule: &BehaviorSchedule| {
+        schedule.max_occurrences.map_or(f64::INFINITY, |max| {
+            schedule.min_delay_ms.unwrap_or(0.0) + max as f64 * schedule.every_ms.unwrap_or(0.0)
+        })
+    };
+    let settled_ms = delay_a.max(delay_b) + second.duration_ms.max(first.duration_ms) + jitter_ms + every_a / gcd_ms * every_b;
+    if ends_ms(first) < settled_ms || ends_ms(second) < settled_ms {
+        // A finite schedule ends before every gap has come up; only what its
+        // first occurrences hit is certain.
+        return bounded.is_some_and(|(_, bounded, other)| enumerate(MAX_ENUMERATED_OCCURRENCES, bounded, other));
+    }
+
+    let offset_ms = (delay_b - delay_a).rem_euclid(gcd_ms);
+    // The second starts `offset_ms` after the first, or `gcd_ms - offset_ms` before it.
+    offset_ms < first.duration_ms + jitter_ms || gcd_ms - offset_ms < second.duration_ms + jitter_ms
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Whether two cron schedules have occurrences that intersect within the
+/// comparison year. Only collisions that are found are reported.
+fn cron_overlaps(first: &BehaviorSchedule, second: &BehaviorSchedule) -> bool {
+    let epoch = Utc.with_ymd_and_hms(CRON_OVERLAP_YEAR, 1, 1, 0, 0, 0).unwrap();
+    let occurrences = |schedule: &BehaviorSchedule| -> Vec<(f64, f64)> {
+        let Some(Ok(cron)) = schedule
+            .cron
+            .as_deref()
+            .map(|cron| CronSchedule::parse(cron, schedule.timezone.as_deref()))
+        else {
+            return Vec::new();
+        };
+        cron.starts_from(epoch)
+            .map(|start| (start - epoch).num_milliseconds() as f64)
+            .take_while(|start_ms| *start_ms < CRON_OVERLAP_HORIZON_MS)
+            .take(MAX_ENUMERATED_OCCURRENCES as usize)
+            .map(|start_ms| (start_ms, start_ms + schedule.duration_ms))
+            .collect()
+    };
+    let (first, second) = (occurrences(first), occurrences(second));
+
+    // Both lists are sorted by start and, with one duration each, by end.
+    let (mut a, mut b) = (0, 0);
+    while a < first.len() && b < second.len() {
+        if first[a].0 < second[b].1 && second[b].0 < first[a].1 {
+            return true;
+        }
+        if first[a].1 <= second[b].1 {
+            a += 1;
+        } else {
+            b += 1;
+        }
+    }
+    false
+}
+
+fn fixed_interval(schedule: &BehaviorSchedule) -> (f64, f64) {
+    let start_ms = schedule.start_offset_ms.unwrap_or(0.0);
+    (start_ms, start_ms + schedule.duration_ms)
+}
+
+/// Whether any occurrence of a recurring schedule (widened by its jitter)
+/// intersects `[start_ms, end_ms)`.
+fn recurring_overlaps(schedule: &BehaviorSchedule, start_ms: f64, end_ms: f64) -> bool {
+    let every_ms = schedule.every_ms.unwrap_or(0.0);
+    if every_ms <= 0.0 {
+        return false;
+    }
+    let min_delay_ms = schedule.min_delay_ms.unwrap_or(0.0);
+    let jitter_ms = schedule.jitter_ms.unwrap_or(0.0);
+
+    let first = ((start_ms - min_delay_ms - schedule.duration_ms - jitter_ms) / every_ms)
+        .floor()
+        .max(0.0) as u64;
+    let mut last = ((end_ms - min_delay_ms + jitter_ms) / every_ms).floor().max(0.0) as u64;
+    if let Some(max_occurrences) = schedule.max_occurrences {
+        if max_occurrences == 0 {
+            return false;
         }
+        last = last.min(max_occurrences as u64 - 1);
     }
+
+    (first..=last).any(|occurrence| {
+        let base_ms = min_delay_ms + occurrence as f64 * every_ms;
+        base_ms - jitter_ms < end_ms && base_ms + schedule.duration_ms + jitter_ms > start_ms
+    })
 }
 
 fn validate_burst_events(
@@ -282,48 +627,554 @@ fn validate_burst_events(
             );
         }
 
-        if let Some(jitter) = burst.frequency.jitter_ms {
-            if !jitter.is_finite() || jitter < 0.0 {
+        match burst.frequency.arrival {
+            BurstArrival::Periodic => {
+                if let Some(jitter) = burst.frequency.jitter_ms {
+                    if !jitter.is_finite() || jitter < 0.0 {
+                        push_error(
+                            errors,
+                            "burst_events.frequency.jitter_ms",
+                            "jitter_ms must be >= 0",
+                            scope_key.clone(),
+                        );
+                    }
+                    if jitter > 0.0 && jitter > (burst.frequency.every_ms - burst.duration_ms) {
+                        push_error(
+                            errors,
+                            "burst_events.frequency.jitter_ms",
+                            "jitter_ms must be <= every_ms - duration_ms",
+                            scope_key.clone(),
+                        );
+                    }
+                }
+
+                if burst.duration_ms > burst.frequency.every_ms {
+                    push_error(
+                        errors,
+                        "burst_events.duration_ms",
+                        "duration_ms must be <= every_ms",
+                        scope_key.clone(),
+                    );
+                }
+            }
+            BurstArrival::Poisson => {
+                if burst.frequency.jitter_ms.is_some() {
+                    push_error(
+                        errors,
+                        "burst_events.frequency.jitter_ms",
+                        "jitter_ms is not supported for poisson arrivals",
+                        scope_key.clone(),
+                    );
+                }
+
+                if burst.duration_ms > burst.frequency.every_ms * MAX_POISSON_BURST_INTERVALS {
+                    push_error(
+                        errors,
+                        "burst_events.duration_ms",
+                        &format!("duration_ms must be <= {} * every_ms for poisson arrivals", MAX_POISSON_BURST_INTERVALS),
+                        scope_key.clone(),
+                    );
+                }
+            }
+        }
+
+        if let Some(duration) = &burst.duration_distribution {
+            validate_latency(duration, errors, scope_key.clone());
+        }
+
+        if let Some(latency) = &burst.latency_spike {
+            validate_latency(latency, errors, scope_key.clone());
+        }
+
+        if let Some(error_spike) = &burst.error_spike {
+            validate_error_profile(&error_spike.error_profile, errors, scope_key.clone());
+        }
+
+        if let Some(ramp) = &burst.ramp {
+            validate_ramp(ramp, "burst_events.ramp", errors, scope_key.clone());
+        }
+
+        if scope_key.is_none() {
+            push_error(errors, "burst_events.scope", "scope is invalid", None);
+        }
+    }
+}
+
+fn validate_behavior_profiles(
+    profiles: &[BehaviorProfile],
+    groups: &[EndpointGroup],
+    endpoint_ids: &HashSet<String>,
+    errors: &mut Vec<ValidationError>,
+) {
+    let group_ids: HashSet<String> = groups.iter().map(|group| group.id.clone()).collect();
+
+    for profile in profiles {
+        let scope_key = validate_scope(&profile.scope, &group_ids, endpoint_ids, errors);
+        let location = profile.id.clone().or(scope_key.clone());
+
+        if profile.keyframes.is_empty() {
+            push_error(
+                errors,
+                "behavior_profiles.keyframes",
+                "must include at least one keyframe",
+                location.clone(),
+            );
+        }
+
+        let mut previous_at_ms: Option<f64> = None;
+        for (index, keyframe) in profile.keyframes.iter().enumerate() {
+            if !keyframe.at_ms.is_finite() || keyframe.at_ms < 0.0 {
+                push_error(
+                    errors,
+                    "behavior_profiles.keyframes.at_ms",
+                    &format!("keyframe {} at_ms must be >= 0", index),
+                    location.clone(),
+                );
+            } else if previous_at_ms.is_some_and(|previous| keyframe.at_ms <= previous) {
+                push_error(
+                    errors,
+                    "behavior_profiles.keyframes.at_ms",
+                    &format!("keyframe {} at_ms must be greater than the previous keyframe", index),
+                    location.clone(),
+                );
+            }
+            previous_at_ms = Some(keyframe.at_ms);
+
+            if !keyframe.latency_multiplier.is_finite() || keyframe.latency_multiplier < 0.0 {
+                push_error(
+                    errors,
+                    "behavior_profiles.keyframes.latency_multiplier",
+                    &format!("keyframe {} latency_multiplier must be >= 0", index),
+                    location.clone(),
+                );
+            }
+
+            if let Some(rate) = keyframe.error_rate {
+                if !rate.is_finite() || !(0.0..=1.0).contains(&rate) {
+                    push_error(
+                        errors,
+                        "behavior_profiles.keyframes.error_rate",
+                        &format!("keyframe {} error_rate must be between 0.0 and 1.0", index),
+                        location.clone(),
+                    );
+                }
+            }
+
+            if !keyframe.rate_limit_factor.is_finite() || keyframe.rate_limit_factor <= 0.0 {
+                push_error(
+                    errors,
+                    "behavior_profiles.keyframes.rate_limit_factor",
+                    &format!("keyframe {} rate_limit_factor must be > 0", index),
+                    location.clone(),
+                );
+            }
+        }
+
+        let with_error_rate = profile.keyframes.iter().filter(|k| k.error_rate.is_some()).count();
+        if with_error_rate > 0 && with_error_rate < profile.keyframes.len() {
+            push_error(
+                errors,
+                "behavior_profiles.keyframes.error_rate",
+                "error_rate must be set on every keyframe or none",
+                location.clone(),
+            );
+        }
+
+        if profile.error_codes.iter().any(|code| !is_valid_status(*code)) {
+            push_error(
+                errors,
+                "behavior_profiles.error_codes",
+                "invalid HTTP status code",
+                location.clone(),
+            );
+        }
+    }
+}
+
+fn validate_resources(resources: &[Resource], endpoints: &[Endpoint], errors: &mut Vec<ValidationError>) {
+    let mut resource_ids = HashSet::new();
+
+    for resource in resources {
+        let location = Some(resource.id.clone());
+        if resource.id.trim().is_empty() {
+            push_error(errors, "resources.id", "id must not be empty", location.clone());
+        }
+        if !resource_ids.insert(resource.id.as_str()) {
+            push_error(errors, "resources.id", "duplicate resource id", location.clone());
+        }
+        if resource.id_field.trim().is_empty() {
+            push_error(errors, "resources.id_field", "id_field must not be empty", location.clone());
+        }
+
+        let mut seed_ids = HashSet::new();
+        for (index, item) in resource.seed.iter().enumerate() {
+            let id = match item.get(&resource.id_field) {
+                Some(serde_json::Value::String(id)) => id.clone(),
+                Some(serde_json::Value::Number(id)) => id.to_string(),
+                _ => {
+                    push_error(
+                        errors,
+                        "resources.seed",
+                        &format!("item {} must be an object with a string or number '{}'", index, resource.id_field),
+                        location.clone(),
+                    );
+                    continue;
+                }
+            };
+            if !seed_ids.insert(id) {
+                push_error(errors, "resources.seed", &format!("item {} has a duplicate id", index), location.clone());
+            }
+        }
+    }
+
+    for endpoint in endpoints {
+        let Some(binding) = &endpoint.resource else {
+            continue;
+        };
+        let location = Some(endpoint.id.clone());
+
+        if !resource_ids.contains(binding.collection.as_str()) {
+            push_error(errors, "resource.collection", "resource does not exist", location.clone());
+        }
+
+        let params = endpoint.path_params();
+        match &binding.id_param {
+            Some(name) if !params.contains(&name.as_str()) => {
+                push_error(errors, "resource.id_param", "must name a {param} segment of the path", location);
+            }
+            None if binding.operation.needs_id() && params.is_empty() => {
                 push_error(
                     errors,
-                    "burst_events.frequency.jitter_ms",
-                    "jitter_ms must be >= 0",
-                    scope_key.clone(),
+                    "resource.operation",
+                    "read, update and delete need a {param} path segment for the item id",
+                    location,
                 );
             }
-            if jitter > 0.0 && jitter > (burst.frequency.every_ms - burst.duration_ms) {
+            _ => {}
+        }
+    }
+}
+
+fn validate_workflows(workflows: &[Workflow], endpoints: &[Endpoint], errors: &mut Vec<ValidationError>) {
+    let mut workflow_ids = HashSet::new();
+
+    for workflow in workflows {
+        let location = Some(workflow.id.clone());
+        if workflow.id.trim().is_empty() {
+            push_error(errors, "workflows.id", "id must not be empty", location.clone());
+        }
+        if !workflow_ids.insert(workflow.id.as_str()) {
+            push_error(errors, "workflows.id", "duplicate workflow id", location.clone());
+        }
+        if workflow.initial_state.trim().is_empty() {
+            push_error(errors, "workflows.initial_state", "initial_state must not be empty", location.clone());
+        }
+        if let Some(session) = &workflow.session {
+            if session.name.trim().is_empty() {
+                push_error(errors, "workflows.session.name", "name must not be empty", location.clone());
+            }
+            if session.max_sessions == 0 {
+                push_error(errors, "workflows.session.max_sessions", "max_sessions must be >= 1", location);
+            }
+        }
+    }
+
+    for endpoint in endpoints {
+        let Some(binding) = &endpoint.workflow else {
+            continue;
+        };
+        let location = Some(endpoint.id.clone());
+
+        if !workflow_ids.contains(binding.workflow.as_str()) {
+            push_error(errors, "workflow.workflow", "workflow does not exist", location.clone());
+        }
+        if endpoint.resource.is_some() {
+            push_error(errors, "workflow", "cannot be combined with resource", location.clone());
+        }
+        if binding.variants.is_empty() {
+            push_error(errors, "workflow.variants", "must include at least one variant", location.clone());
+        }
+
+        for (index, variant) in binding.variants.iter().enumerate() {
+            let states = [&variant.required_state, &variant.new_state];
+            if states.iter().any(|state| state.as_ref().is_some_and(|state| state.trim().is_empty())) {
                 push_error(
                     errors,
-                    "burst_events.frequency.jitter_ms",
-                    "jitter_ms must be <= every_ms - duration_ms",
-                    scope_key.clone(),
+                    "workflow.variants",
+                    &format!("variant {} states must not be empty", index),
+                    location.clone(),
                 );
             }
+            if let Some(response) = &variant.response {
+                validate_response(response, errors, location.clone());
+            }
+        }
+    }
+}
+
+fn validate_jobs(jobs: &[Job], endpoints: &[Endpoint], errors: &mut Vec<ValidationError>) {
+    let mut job_ids = HashSet::new();
+
+    for job in jobs {
+        let location = Some(job.id.clone());
+        if job.id.trim().is_empty() {
+            push_error(errors, "jobs.id", "id must not be empty", location.clone());
+        }
+        if !job_ids.insert(job.id.as_str()) {
+            push_error(errors, "jobs.id", "duplicate job id", location.clone());
+        }
+        validate_latency(&job.duration, errors, location.clone());
+        validate_response(&job.result, errors, location.clone());
+        validate_error_profile(&job.error_profile, errors, location.clone());
+        if !job.pending_ms.is_finite() || job.pending_ms < 0.0 {
+            push_error(errors, "jobs.pending_ms", "pending_ms must be >= 0", location.clone());
+        }
+        if !job.ttl_ms.is_finite() || job.ttl_ms <= 0.0 {
+            push_error(errors, "jobs.ttl_ms", "ttl_ms must be > 0", location.clone());
+        }
+        if job.max_jobs == 0 {
+            push_error(errors, "jobs.max_jobs", "max_jobs must be >= 1", location);
+        }
+    }
+
+    for endpoint in endpoints {
+        let Some(binding) = &endpoint.job else {
+            continue;
+        };
+        let location = Some(endpoint.id.clone());
+
+        if !job_ids.contains(binding.job.as_str()) {
+            push_error(errors, "job.job", "job does not exist", location.clone());
+        }
+        if endpoint.resource.is_some() || endpoint.workflow.is_some() {
+            push_error(errors, "job", "cannot be combined with resource or workflow", location.clone());
+        }
+
+        let params = endpoint.path_params();
+        match &binding.id_param {
+            Some(name) if !params.contains(&name.as_str()) => {
+                push_error(errors, "job.id_param", "must name a {param} segment of the path", location);
+            }
+            None if binding.operation == JobOperation::Status && params.is_empty() => {
+                push_error(errors, "job.operation", "status needs a {param} path segment for the job id", location);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_callbacks(callbacks: &[Callback], errors: &mut Vec<ValidationError>, location: Option<String>) {
+    for callback in callbacks {
+        let url = callback.url.trim();
+        let is_absolute = url.starts_with("http://") || url.starts_with("https://");
+        if url.is_empty() {
+            push_error(errors, "callbacks.url", "url must not be empty", location.clone());
+        } else if !is_absolute && !url.contains("{{") {
+            push_error(errors, "callbacks.url", "url must start with http:// or https://", location.clone());
+        }
+        validate_latency(&callback.delay, errors, location.clone());
+
+        let retry = &callback.retry;
+        if retry.max_attempts == 0 {
+            push_error(errors, "callbacks.retry.max_attempts", "must be >= 1", location.clone());
+        }
+        if !retry.backoff_ms.is_finite() || retry.backoff_ms < 0.0 {
+            push_error(errors, "callbacks.retry.backoff_ms", "must be >= 0", location.clone());
+        }
+        if !retry.multiplier.is_finite() || retry.multiplier < 1.0 {
+            push_error(errors, "callbacks.retry.multiplier", "must be >= 1", location.clone());
+        }
+        if !retry.timeout_ms.is_finite() || retry.timeout_ms <= 0.0 {
+            push_error(errors, "callbacks.retry.timeout_ms", "must be > 0", location.clone());
+        }
+    }
+}
+
+fn validate_proxies(config: &Configuration, errors: &mut Vec<ValidationError>) {
+    if let Some(proxy) = &config.proxy {
+        let location = Some("proxy".to_string());
+        validate_proxy_target(&proxy.target(), "proxy", errors, location.clone());
+        validate_latency(&proxy.latency, errors, location.clone());
+        validate_error_profile(&proxy.error_profile, errors, location.clone());
+        validate_bandwidth_cap(proxy.bandwidth_cap.as_ref(), errors, location);
+    }
+
+    for group in &config.endpoint_groups {
+        if let Some(target) = &group.proxy {
+            validate_proxy_target(target, "endpoint_groups.proxy", errors, Some(group.id.clone()));
+        }
+    }
+
+    for endpoint in &config.endpoints {
+        let location = Some(endpoint.id.clone());
+        if let Some(target) = &endpoint.proxy {
+            validate_proxy_target(target, "proxy", errors, location.clone());
+        }
+
+        let proxied = endpoint.proxy.is_some()
+            || config
+                .endpoint_groups
+                .iter()
+                .any(|group| group.proxy.is_some() && group.endpoint_ids.contains(&endpoint.id));
+        let stateful = endpoint.resource.is_some()
+            || endpoint.workflow.is_some()
+            || endpoint.job.is_some()
+            || endpoint.auth == Some(EndpointAuth::TokenEndpoint);
+        if proxied && stateful {
+            push_error(
+                errors,
+                "proxy",
+                "cannot be combined with resource, workflow, job or token_endpoint",
+                location,
+            );
+        }
+    }
+}
+
+fn validate_proxy_target(
+    target: &ProxyTarget,
+    field: &str,
+    errors: &mut Vec<ValidationError>,
+    location: Option<String>,
+) {
+    let upstream = target.upstream.trim();
+    if !(upstream.starts_with("http://") || upstream.starts_with("https://")) {
+        push_error(
+            errors,
+            &format!("{}.upstream", field),
+            "upstream must start with http:// or https://",
+            location.clone(),
+        );
+    }
+    if !target.timeout_ms.is_finite() || target.timeout_ms <= 0.0 {
+        push_error(errors, &format!("{}.timeout_ms", field), "must be > 0", location);
+    }
+}
+
+fn validate_dependencies(endpoints: &[Endpoint], errors: &mut Vec<ValidationError>) {
+    let graph: HashMap<&str, Vec<&str>> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let calls = endpoint
+                .dependencies
+                .iter()
+                .flat_map(|dependencies| &dependencies.calls)
+                .map(|call| call.endpoint_id.as_str())
+                .collect();
+            (endpoint.id.as_str(), calls)
+        })
+        .collect();
+
+    for endpoint in endpoints {
+        let Some(dependencies) = &endpoint.dependencies else {
+            continue;
+        };
+        let location = Some(endpoint.id.clone());
+
+        if dependencies.calls.is_empty() {
+            push_error(errors, "dependencies.calls", "must include at least one call", location.clone());
+        }
+        for (field, status) in [
+            ("dependencies.error_status", dependencies.error_status),
+            ("dependencies.timeout_status", dependencies.timeout_status),
+        ] {
+            if !is_valid_status(status) {
+                push_error(errors, field, "invalid HTTP status code", location.clone());
+            }
         }
 
-        if burst.duration_ms > burst.frequency.every_ms {
-            push_error(
-                errors,
-                "burst_events.duration_ms",
-                "duration_ms must be <= every_ms",
-                scope_key.clone(),
-            );
+        let caller_params = endpoint.path_params();
+        for call in &dependencies.calls {
+            match endpoints.iter().find(|target| target.id == call.endpoint_id) {
+                None => push_error(errors, "dependencies.calls.endpoint_id", "endpoint does not exist", location.clone()),
+                Some(target) => {
+                    // Calls fill the target's path parameters from the caller's.
+                    for param in target.path_params() {
+                        if !caller_params.contains(&param) {
+                            push_error(
+                                errors,
+                                "dependencies.calls.endpoint_id",
+                                &format!("path parameter '{}' of '{}' is not in the caller's path", param, target.id),
+                                location.clone(),
+                            );
+                        }
+                    }
+                }
+            }
+            if let Some(timeout_ms) = call.timeout_ms {
+                if !timeout_ms.is_finite() || timeout_ms <= 0.0 {
+                    push_error(errors, "dependencies.calls.timeout_ms", "must be > 0", location.clone());
+                }
+            }
+            if let Some(fallback) = &call.fallback {
+                validate_response(fallback, errors, location.clone());
+            }
         }
 
-        if let Some(latency) = &burst.latency_spike {
-            validate_latency(latency, errors, scope_key.clone());
+        if reaches(&graph, &endpoint.id, &endpoint.id, &mut HashSet::new()) {
+            push_error(errors, "dependencies", "endpoint depends on itself", location);
         }
+    }
+}
 
-        if let Some(error_spike) = &burst.error_spike {
-            validate_error_profile(&error_spike.error_profile, errors, scope_key.clone());
+/// Whether `target` can be reached by following calls from `from`.
+fn reaches<'a>(
+    graph: &HashMap<&'a str, Vec<&'a str>>,
+    from: &'a str,
+    target: &str,
+    seen: &mut HashSet<&'a str>,
+) -> bool {
+    let Some(calls) = graph.get(from) else {
+        return false;
+    };
+    calls.iter().any(|next| {
+        *next == target || (seen.insert(next) && reaches(graph, next, target, seen))
+    })
+}
+
+fn validate_auth(auth: Option<&AuthConfig>, endpoints: &[Endpoint], errors: &mut Vec<ValidationError>) {
+    if let Some(auth) = auth {
+        let location = Some("auth".to_string());
+        let has_secret = auth.signing_secret.as_deref().is_some_and(|secret| !secret.is_empty());
+        if auth.token_format == TokenFormat::Jwt && !has_secret {
+            push_error(errors, "auth.signing_secret", "jwt tokens require a signing_secret", location.clone());
+        }
+        if auth.expires_in_secs == 0 {
+            push_error(errors, "auth.expires_in_secs", "expires_in_secs must be >= 1", location.clone());
         }
 
-        if let Some(ramp) = &burst.ramp {
-            validate_ramp(ramp, "burst_events.ramp", errors, scope_key.clone());
+        let mut client_ids = HashSet::new();
+        for client in &auth.clients {
+            if client.client_id.trim().is_empty() || !client_ids.insert(client.client_id.as_str()) {
+                push_error(errors, "auth.clients.client_id", "client_id must be unique and not empty", Some(client.client_id.clone()));
+            }
+        }
+        let mut usernames = HashSet::new();
+        for user in &auth.users {
+            if user.username.trim().is_empty() || !usernames.insert(user.username.as_str()) {
+                push_error(errors, "auth.users.username", "username must be unique and not empty", Some(user.username.clone()));
+            }
         }
+    }
 
-        if scope_key.is_none() {
-            push_error(errors, "burst_events.scope", "scope is invalid", None);
+    for endpoint in endpoints {
+        let location = Some(endpoint.id.clone());
+        match endpoint.auth {
+            Some(_) if auth.is_none() => {
+                push_error(errors, "auth", "requires a top-level auth section", location.clone());
+            }
+            Some(EndpointAuth::TokenEndpoint) => {
+                if endpoint.method != HttpMethod::Post {
+                    push_error(errors, "auth", "token_endpoint must use POST", location.clone());
+                }
+                if endpoint.resource.is_some() || endpoint.workflow.is_some() || endpoint.job.is_some() {
+                    push_error(errors, "auth", "token_endpoint cannot be combined with resource, workflow or job", location.clone());
+                }
+            }
+            _ => {}
+        }
+        if !endpoint.required_scopes.is_empty() && endpoint.auth != Some(EndpointAuth::Required) {
+            push_error(errors, "required_scopes", "requires auth: required", location);
         }
     }
 }
@@ -335,7 +1186,7 @@ fn validate_scope(
     errors: &mut Vec<ValidationError>,
 ) -> Option<String> {
     let mut count = 0;
-    if scope.endpoint_id.as_ref().map_or(false, |v| !v.trim().is_empty()) {
+    if scope.endpoint_id.as_ref().is_some_and(|v| !v.trim().is_empty()) {
         count += 1;
         if let Some(endpoint_id) = &scope.endpoint_id {
             if !endpoint_ids.contains(endpoint_id) {
@@ -343,7 +1194,7 @@ fn validate_scope(
             }
         }
     }
-    if scope.group_id.as_ref().map_or(false, |v| !v.trim().is_empty()) {
+    if scope.group_id.as_ref().is_some_and(|v| !v.trim().is_empty()) {
         count += 1;
         if let Some(group_id) = &scope.group_id {
             if !group_ids.contains(group_id) {
@@ -481,6 +1332,26 @@ fn validate_schedule(
                 }
             }
         }
+        ScheduleMode::Cron => {
+            let Some(expression) = schedule.cron.as_deref() else {
+                push_error(
+                    errors,
+                    "behavior_windows.schedule.cron",
+                    "cron is required for cron schedules",
+                    location.map(|value| value.to_string()),
+                );
+                return false;
+            };
+
+            if let Err(err) = CronSchedule::parse(expression, schedule.timezone.as_deref()) {
+                let field = match err {
+                    CronError::Expression(_) => "behavior_windows.schedule.cron",
+                    CronError::TimeZone => "behavior_windows.schedule.timezone",
+                };
+                push_error(errors, field, &err.to_string(), location.map(|value| value.to_string()));
+                return false;
+            }
+        }
     }
 
     true
@@ -541,7 +1412,12 @@ fn validate_distribution(
                 push_error(errors, "latency.params.max_ms", "must be > min_ms", location);
             }
         }
-        (DistributionType::LogNormal, DistributionParams::LogNormal { mean_ms, stddev_ms }) => {
+        // Log-normal params have the same shape as normal ones, so untagged
+        // parsing yields either variant.
+        (
+            DistributionType::LogNormal,
+            DistributionParams::LogNormal { mean_ms, stddev_ms } | DistributionParams::Normal { mean_ms, stddev_ms },
+        ) => {
             if !mean_ms.is_finite() || *mean_ms <= 0.0 {
                 push_error(errors, "latency.params.mean_ms", "must be > 0", location.clone());
             }
@@ -720,6 +1596,17 @@ mod tests {
             error_profile: ErrorProfile::default(),
             rate_limit: None,
             bandwidth_cap: None,
+            capacity: None,
+            concurrency_limit: None,
+            resource: None,
+            workflow: None,
+            job: None,
+            auth: None,
+            required_scopes: vec![],
+            idempotency: None,
+            dependencies: None,
+            callbacks: vec![],
+            proxy: None,
             loaded_at: None,
             rate_limiter: None,
         }
@@ -739,8 +1626,14 @@ mod tests {
                 },
             )],
             endpoint_groups: vec![],
+            rate_limit: None,
+            auth: None,
+            proxy: None,
             behavior_windows: vec![],
             burst_events: vec![],
+            behavior_profiles: vec![],
+            resources: vec![],
+            jobs: vec![],
             workflows: vec![],
         }
     }
@@ -879,8 +1772,11 @@ mod tests {
     fn test_validate_rate_limit_requests_per_second() {
         let mut config = base_config();
         config.endpoints[0].rate_limit = Some(crate::config::RateLimit {
-            requests_per_second: 0.0,
+            requests_per_second: Some(0.0),
+            requests_per_hour: None,
             burst: Some(5.0),
+            key: None,
+            response: None,
         });
         let errors = validation_errors(&config);
         assert!(errors.iter().any(|e| e.field == "rate_limit.requests_per_second"));
@@ -890,13 +1786,52 @@ mod tests {
     fn test_validate_rate_limit_burst() {
         let mut config = base_config();
         config.endpoints[0].rate_limit = Some(crate::config::RateLimit {
-            requests_per_second: 10.0,
+            requests_per_second: Some(10.0),
+            requests_per_hour: None,
             burst: Some(-1.0),
+            key: None,
+            response: None,
         });
         let errors = validation_errors(&config);
         assert!(errors.iter().any(|e| e.field == "rate_limit.burst"));
     }
 
+    #[test]
+    fn test_validate_rate_limit_requires_one_rate() {
+        let mut config = base_config();
+        config.rate_limit = Some(crate::config::RateLimit {
+            requests_per_second: Some(10.0),
+            requests_per_hour: Some(3600.0),
+            burst: None,
+            key: None,
+            response: None,
+        });
+        let errors = validation_errors(&config);
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "rate_limit" && e.location.as_deref() == Some("global")));
+    }
+
+    #[test]
+    fn test_validate_rate_limit_header_key_requires_name() {
+        let mut config = base_config();
+        config.endpoints[0].rate_limit = Some(crate::config::RateLimit {
+            requests_per_second: Some(10.0),
+            requests_per_hour: None,
+            burst: None,
+            key: Some(crate::config::RateLimitKey {
+                source: KeySource::Header,
+                name: None,
+                max_keys: 100,
+                idle_timeout_ms: 60_000.0,
+                missing: crate::config::MissingKeyPolicy::Shared,
+            }),
+            response: None,
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "rate_limit.key.name"));
+    }
+
     #[test]
     fn test_validate_bandwidth_cap() {
         let mut config = base_config();
@@ -1073,4 +2008,577 @@ mod tests {
         assert!(errors.iter().any(|e| e.field == "request.body"));
     }
 
+    fn cron_window(cron: Option<&str>, timezone: Option<&str>) -> BehaviorWindow {
+        BehaviorWindow {
+            id: Some("nightly-batch".to_string()),
+            scope: BehaviorScope {
+                endpoint_id: Some("health".to_string()),
+                group_id: None,
+                global: false,
+            },
+            schedule: BehaviorSchedule {
+                mode: ScheduleMode::Cron,
+                start_offset_ms: None,
+                duration_ms: 1_800_000.0,
+                every_ms: None,
+                jitter_ms: None,
+                max_occurrences: None,
+                min_delay_ms: None,
+                cron: cron.map(str::to_string),
+                timezone: timezone.map(str::to_string),
+                parsed_cron: None,
+            },
+            priority: 0,
+            stack: false,
+            ramp: None,
+            error_mix: ErrorMix::Override,
+            latency_override: None,
+            error_profile_override: None,
+        }
+    }
+
+    fn fixed_window(id: &str, start_offset_ms: f64, duration_ms: f64, priority: i32) -> BehaviorWindow {
+        let mut window = cron_window(None, None);
+        window.id = Some(id.to_string());
+        window.priority = priority;
+        window.schedule.mode = ScheduleMode::Fixed;
+        window.schedule.start_offset_ms = Some(start_offset_ms);
+        window.schedule.duration_ms = duration_ms;
+        window
+    }
+
+    #[test]
+    fn test_validate_overlapping_windows_report_both_ids() {
+        let mut config = base_config();
+        config.behavior_windows.push(fixed_window("outage", 0.0, 10_000.0, 0));
+        config.behavior_windows.push(fixed_window("slowdown", 5_000.0, 10_000.0, 0));
+        let errors = validation_errors(&config);
+        let conflict = errors
+            .iter()
+            .find(|e| e.field == "behavior_windows.schedule")
+            .expect("overlap error");
+        assert!(conflict.error.contains("'outage'") && conflict.error.contains("'slowdown'"));
+    }
+
+    #[test]
+    fn test_validate_overlapping_windows_with_priorities() {
+        let mut config = base_config();
+        config.behavior_windows.push(fixed_window("outage", 0.0, 10_000.0, 1));
+        config.behavior_windows.push(fixed_window("slowdown", 5_000.0, 10_000.0, 0));
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_fixed_window_overlaps_recurring() {
+        let mut config = base_config();
+        let mut recurring = fixed_window("flaky", 0.0, 1_000.0, 0);
+        recurring.schedule.mode = ScheduleMode::Recurring;
+        recurring.schedule.every_ms = Some(60_000.0);
+        recurring.schedule.max_occurrences = Some(2);
+        config.behavior_windows.push(recurring);
+
+        config.behavior_windows.push(fixed_window("late", 120_500.0, 1_000.0, 0));
+        assert!(validate(&config).is_ok());
+
+        config.behavior_windows.push(fixed_window("early", 60_500.0, 1_000.0, 0));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.error.contains("'flaky' and 'early'")));
+    }
+
+    #[test]
+    fn test_validate_cron_windows_overlap_only_when_they_meet() {
+        let cron = |id: &str, expression: &str| {
+            let mut window = cron_window(Some(expression), None);
+            window.id = Some(id.to_string());
+            window
+        };
+        let mut config = base_config();
+        config.behavior_windows.push(cron("weeknights", "0 2 * * 1-5"));
+        config.behavior_windows.push(cron("weekend-afternoons", "0 14 * * 6,0"));
+        assert!(validate(&config).is_ok());
+
+        config.behavior_windows.push(cron("monday-backup", "15 2 * * 1"));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.error.contains("'weeknights' and 'monday-backup'")));
+    }
+
+    #[test]
+    fn test_validate_recurring_windows_overlap_by_period() {
+        let recurring = |id: &str, every_ms: f64, min_delay_ms: f64| {
+            let mut window = fixed_window(id, 0.0, 1_000.0, 0);
+            window.schedule.mode = ScheduleMode::Recurring;
+            window.schedule.every_ms = Some(every_ms);
+            window.schedule.min_delay_ms = Some(min_delay_ms);
+            window
+        };
+        let mut config = base_config();
+        config.behavior_windows.push(recurring("even", 10_000.0, 0.0));
+        config.behavior_windows.push(recurring("odd", 10_000.0, 5_000.0));
+        // Starts differ by multiples of gcd(10s, 15s) = 5s from 0, so never by less than 1s.
+        config.behavior_windows.push(recurring("slow", 15_000.0, 2_500.0));
+        assert!(validate(&config).is_ok());
+
+        config.behavior_windows.push(recurring("drifting", 7_000.0, 2_500.0));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.error.contains("'even' and 'drifting'")));
+
+        // Limited to occurrences that end before the others start.
+        let mut config = base_config();
+        config.behavior_windows.push(recurring("even", 10_000.0, 0.0));
+        let mut short = recurring("warmup", 3_000.0, 1_000.0);
+        short.schedule.max_occurrences = Some(3);
+        config.behavior_windows.push(short);
+        assert!(validate(&config).is_ok());
+
+        // Too many occurrences to enumerate, but over long before the other starts.
+        let mut config = base_config();
+        let mut early = recurring("early", 1_000.0, 0.0);
+        early.schedule.duration_ms = 100.0;
+        early.schedule.max_occurrences = Some(20_000);
+        config.behavior_windows.push(early.clone());
+        let mut late = recurring("late", 1_000.0, 50_000_000.0);
+        late.schedule.duration_ms = 100.0;
+        config.behavior_windows.push(late);
+        assert!(validate(&config).is_ok());
+
+        let mut config = base_config();
+        config.behavior_windows.push(early);
+        let mut concurrent = recurring("concurrent", 3_000.0, 10_000_050.0);
+        concurrent.schedule.duration_ms = 100.0;
+        config.behavior_windows.push(concurrent);
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.error.contains("'early' and 'concurrent'")));
+    }
+
+    fn poisson_burst(jitter_ms: Option<f64>) -> BurstEvent {
+        BurstEvent {
+            id: Some("incident".to_string()),
+            scope: BehaviorScope {
+                endpoint_id: Some("health".to_string()),
+                group_id: None,
+                global: false,
+            },
+            frequency: crate::config::BurstFrequency {
+                arrival: BurstArrival::Poisson,
+                every_ms: 1_000.0,
+                jitter_ms,
+                seed: Some(7),
+            },
+            duration_ms: 5_000.0,
+            duration_distribution: None,
+            priority: 0,
+            stack: false,
+            ramp: None,
+            latency_spike: None,
+            error_spike: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_poisson_burst_allows_long_duration() {
+        let mut config = base_config();
+        config.burst_events.push(poisson_burst(None));
+        assert!(validate(&config).is_ok());
+
+        config.burst_events[0].duration_ms = 3_600_000.0;
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "burst_events.duration_ms"));
+    }
+
+    #[test]
+    fn test_validate_poisson_burst_rejects_jitter() {
+        let mut config = base_config();
+        config.burst_events.push(poisson_burst(Some(100.0)));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "burst_events.frequency.jitter_ms"));
+    }
+
+    #[test]
+    fn test_validate_cron_schedule_valid() {
+        let mut config = base_config();
+        config.behavior_windows.push(cron_window(Some("0 2 * * 1-5"), Some("Europe/London")));
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_cron_schedule_requires_expression() {
+        let mut config = base_config();
+        config.behavior_windows.push(cron_window(None, None));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "behavior_windows.schedule.cron"));
+    }
+
+    #[test]
+    fn test_validate_cron_schedule_invalid_expression() {
+        let mut config = base_config();
+        config.behavior_windows.push(cron_window(Some("0 25 * * *"), None));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "behavior_windows.schedule.cron"));
+    }
+
+    #[test]
+    fn test_validate_cron_schedule_unknown_timezone() {
+        let mut config = base_config();
+        config.behavior_windows.push(cron_window(Some("0 2 * * *"), Some("Mars/Olympus")));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "behavior_windows.schedule.timezone"));
+    }
+
+    fn profile(keyframes: Vec<(f64, Option<f64>)>) -> BehaviorProfile {
+        BehaviorProfile {
+            id: Some("leak".to_string()),
+            scope: BehaviorScope {
+                endpoint_id: Some("health".to_string()),
+                group_id: None,
+                global: false,
+            },
+            keyframes: keyframes
+                .into_iter()
+                .map(|(at_ms, error_rate)| crate::config::Keyframe {
+                    at_ms,
+                    latency_multiplier: 1.0,
+                    error_rate,
+                    rate_limit_factor: 1.0,
+                })
+                .collect(),
+            interpolation: None,
+            repeat: false,
+            error_mix: ErrorMix::Additive,
+            error_codes: vec![503],
+            error_body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate_behavior_profile_valid() {
+        let mut config = base_config();
+        config
+            .behavior_profiles
+            .push(profile(vec![(0.0, Some(0.0)), (60_000.0, Some(0.2))]));
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_behavior_profile_requires_increasing_keyframes() {
+        let mut config = base_config();
+        config
+            .behavior_profiles
+            .push(profile(vec![(1_000.0, None), (1_000.0, None)]));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "behavior_profiles.keyframes.at_ms"));
+    }
+
+    #[test]
+    fn test_validate_behavior_profile_requires_consistent_error_rate() {
+        let mut config = base_config();
+        config
+            .behavior_profiles
+            .push(profile(vec![(0.0, None), (1_000.0, Some(0.5))]));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "behavior_profiles.keyframes.error_rate"));
+    }
+
+    #[test]
+    fn test_validate_behavior_profile_requires_keyframes() {
+        let mut config = base_config();
+        config.behavior_profiles.push(profile(vec![]));
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "behavior_profiles.keyframes"));
+    }
+
+    #[test]
+    fn test_validate_capacity_rejects_zero_workers_and_unsorted_curve() {
+        let mut config = base_config();
+        config.endpoints[0].capacity = Some(crate::config::CapacityModel {
+            workers: 0,
+            queue_size: 10,
+            load_curve: vec![
+                crate::config::LoadPoint {
+                    in_flight: 4,
+                    latency_multiplier: 2.0,
+                },
+                crate::config::LoadPoint {
+                    in_flight: 2,
+                    latency_multiplier: 1.0,
+                },
+            ],
+            overflow_status: 503,
+            overflow_body: String::new(),
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "capacity.workers"));
+        assert!(errors.iter().any(|e| e.field == "capacity.load_curve.in_flight"));
+    }
+
+    #[test]
+    fn test_validate_concurrency_limit() {
+        let mut config = base_config();
+        config.endpoints[0].concurrency_limit = Some(crate::config::ConcurrencyLimit {
+            max_in_flight: 0,
+            queue_size: 0,
+            queue_timeout_ms: Some(500.0),
+            rejection_status: 503,
+            rejection_body: String::new(),
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "concurrency_limit.max_in_flight"));
+        assert!(errors.iter().any(|e| e.field == "concurrency_limit.queue_timeout_ms"));
+    }
+
+    #[test]
+    fn test_validate_resources() {
+        let mut config = base_config();
+        config.resources = vec![crate::config::Resource {
+            id: "users".to_string(),
+            id_field: "id".to_string(),
+            id_strategy: crate::config::IdStrategy::Sequence,
+            seed: vec![serde_json::json!({"id": 1}), serde_json::json!({"id": "1"}), serde_json::json!({"name": "x"})],
+        }];
+        config.endpoints[0].resource = Some(crate::config::ResourceBinding {
+            collection: "accounts".to_string(),
+            operation: crate::config::ResourceOperation::Read,
+            id_param: None,
+        });
+        let errors = validation_errors(&config);
+        assert_eq!(errors.iter().filter(|e| e.field == "resources.seed").count(), 2);
+        assert!(errors.iter().any(|e| e.field == "resource.collection"));
+        assert!(errors.iter().any(|e| e.field == "resource.operation"));
+    }
+
+    #[test]
+    fn test_validate_workflows() {
+        let mut config = base_config();
+        config.workflows = vec![crate::config::Workflow {
+            id: "checkout".to_string(),
+            initial_state: String::new(),
+            session: None,
+        }];
+        config.endpoints[0].workflow = Some(crate::config::WorkflowBinding {
+            workflow: "missing".to_string(),
+            variants: vec![crate::config::StateVariant {
+                required_state: Some(" ".to_string()),
+                new_state: None,
+                response: None,
+            }],
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "workflows.initial_state"));
+        assert!(errors.iter().any(|e| e.field == "workflow.workflow"));
+        assert!(errors.iter().any(|e| e.field == "workflow.variants"));
+    }
+
+    #[test]
+    fn test_validate_jobs() {
+        let mut config = base_config();
+        config.jobs = vec![crate::config::Job {
+            id: "export".to_string(),
+            duration: config.endpoints[0].latency.clone(),
+            pending_ms: -1.0,
+            result: config.endpoints[0].response.clone(),
+            error_profile: ErrorProfile::default(),
+            ttl_ms: 0.0,
+            max_jobs: 10,
+        }];
+        config.endpoints[0].job = Some(crate::config::JobBinding {
+            job: "export".to_string(),
+            operation: JobOperation::Status,
+            id_param: None,
+            location: None,
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "jobs.pending_ms"));
+        assert!(errors.iter().any(|e| e.field == "jobs.ttl_ms"));
+        assert!(errors.iter().any(|e| e.field == "job.operation"));
+        assert!(!errors.iter().any(|e| e.field == "job.job"));
+    }
+
+    #[test]
+    fn test_validate_auth() {
+        let mut config = base_config();
+        config.endpoints[0].auth = Some(EndpointAuth::TokenEndpoint);
+        config.endpoints[0].required_scopes = vec!["orders:read".to_string()];
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "auth" && e.location.as_deref() == Some("health")));
+        assert!(errors.iter().any(|e| e.field == "required_scopes"));
+
+        config.auth = Some(AuthConfig {
+            token_format: TokenFormat::Jwt,
+            signing_secret: None,
+            issuer: "web-simulant".to_string(),
+            expires_in_secs: 60,
+            clients: vec![],
+            users: vec![],
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "auth.signing_secret"));
+        assert!(errors.iter().any(|e| e.error.contains("must use POST")));
+    }
+
+    #[test]
+    fn test_validate_idempotency() {
+        let mut config = base_config();
+        config.endpoints[0].idempotency = Some(Idempotency {
+            header: " ".to_string(),
+            ttl_ms: 0.0,
+            max_keys: 10,
+            replay: true,
+            fail_attempts: Some(crate::config::AttemptFailure {
+                attempts: 0,
+                status: 700,
+                headers: HashMap::new(),
+                body: String::new(),
+            }),
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "idempotency.header"));
+        assert!(errors.iter().any(|e| e.field == "idempotency.ttl_ms"));
+        assert!(errors.iter().any(|e| e.field == "idempotency.fail_attempts.attempts"));
+        assert!(errors.iter().any(|e| e.field == "idempotency.fail_attempts.status"));
+        assert!(!errors.iter().any(|e| e.field == "idempotency.max_keys"));
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let mut config = base_config();
+        let mut database = config.endpoints[0].clone();
+        database.id = "database".to_string();
+        database.path = "/db".to_string();
+        database.dependencies = Some(crate::config::Dependencies {
+            mode: crate::config::DependencyMode::Parallel,
+            calls: vec![crate::config::DependencyCall {
+                endpoint_id: "health".to_string(),
+                timeout_ms: Some(0.0),
+                fallback: None,
+            }],
+            error_status: 502,
+            timeout_status: 504,
+            error_body: None,
+        });
+        config.endpoints.push(database);
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "dependencies.calls.timeout_ms"));
+        assert!(!errors.iter().any(|e| e.field == "dependencies"));
+
+        let mut calls_back = config.endpoints[1].dependencies.clone().unwrap();
+        calls_back.calls[0].endpoint_id = "database".to_string();
+        calls_back.calls.push(crate::config::DependencyCall {
+            endpoint_id: "cache".to_string(),
+            timeout_ms: None,
+            fallback: None,
+        });
+        config.endpoints[0].dependencies = Some(calls_back);
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "dependencies" && e.location.as_deref() == Some("health")));
+        assert!(errors.iter().any(|e| e.field == "dependencies" && e.location.as_deref() == Some("database")));
+        assert!(errors.iter().any(|e| e.field == "dependencies.calls.endpoint_id"));
+
+        let mut config = base_config();
+        let mut user = config.endpoints[0].clone();
+        user.id = "user".to_string();
+        user.path = "/users/{id}".to_string();
+        config.endpoints[0].path = "/orders/{order_id}".to_string();
+        config.endpoints[0].dependencies = Some(crate::config::Dependencies {
+            mode: crate::config::DependencyMode::Sequential,
+            calls: vec![crate::config::DependencyCall {
+                endpoint_id: "user".to_string(),
+                timeout_ms: None,
+                fallback: None,
+            }],
+            error_status: 502,
+            timeout_status: 504,
+            error_body: None,
+        });
+        config.endpoints.push(user);
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "dependencies.calls.endpoint_id" && e.error.contains("'id'")));
+        config.endpoints[0].path = "/users/{id}/orders".to_string();
+        assert!(validation_errors(&config).is_empty());
+    }
+
+    #[test]
+    fn test_validate_callbacks() {
+        let mut config = base_config();
+        let callback = crate::config::Callback {
+            url: "localhost:9000/hooks".to_string(),
+            method: HttpMethod::Post,
+            delay: config.endpoints[0].latency.clone(),
+            headers: HashMap::new(),
+            body: String::new(),
+            retry: crate::config::CallbackRetry {
+                max_attempts: 0,
+                multiplier: 0.5,
+                ..Default::default()
+            },
+        };
+        let mut templated = callback.clone();
+        templated.url = "{{request.body.callback_url}}".to_string();
+        templated.retry = Default::default();
+        config.endpoints[0].callbacks = vec![callback, templated];
+
+        let errors = validation_errors(&config);
+        assert_eq!(errors.iter().filter(|e| e.field == "callbacks.url").count(), 1);
+        assert!(errors.iter().any(|e| e.field == "callbacks.retry.max_attempts"));
+        assert!(errors.iter().any(|e| e.field == "callbacks.retry.multiplier"));
+        assert!(!errors.iter().any(|e| e.field == "callbacks.retry.timeout_ms"));
+    }
+
+    #[test]
+    fn test_validate_proxies() {
+        let mut config = base_config();
+        config.proxy = Some(crate::config::FallthroughProxy {
+            upstream: "localhost:9000".to_string(),
+            timeout_ms: 1000.0,
+            latency: config.endpoints[0].latency.clone(),
+            error_profile: ErrorProfile::default(),
+            bandwidth_cap: None,
+            record: false,
+        });
+        config.endpoints[0].proxy = Some(ProxyTarget {
+            upstream: "http://localhost:9000".to_string(),
+            timeout_ms: 0.0,
+            record: false,
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "proxy.upstream" && e.location.as_deref() == Some("proxy")));
+        assert!(errors.iter().any(|e| e.field == "proxy.timeout_ms" && e.location.as_deref() == Some("health")));
+        assert!(!errors.iter().any(|e| e.field == "proxy"));
+
+        config.endpoints[0].proxy = None;
+        config.endpoints[0].auth = Some(EndpointAuth::TokenEndpoint);
+        config.endpoint_groups.push(EndpointGroup {
+            id: "upstream".to_string(),
+            endpoint_ids: vec!["health".to_string()],
+            capacity: None,
+            concurrency_limit: None,
+            rate_limit: None,
+            proxy: Some(ProxyTarget {
+                upstream: "https://api.example.com".to_string(),
+                timeout_ms: 1000.0,
+                record: false,
+            }),
+        });
+        let errors = validation_errors(&config);
+        assert!(errors.iter().any(|e| e.field == "proxy" && e.error.contains("cannot be combined")));
+        assert!(!errors.iter().any(|e| e.field == "endpoint_groups.proxy.upstream"));
+    }
+
+    #[test]
+    fn test_validate_parsed_log_normal_latency() {
+        let mut config = base_config();
+        config.endpoints[0].latency = serde_yaml::from_str(
+            "distribution: log_normal\nparams:\n  mean_ms: 120\n  stddev_ms: 40\n",
+        )
+        .unwrap();
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_proxy_without_endpoints() {
+        let mut config = base_config();
+        config.endpoints.clear();
+        assert!(validation_errors(&config).iter().any(|e| e.field == "endpoints"));
+
+        config.proxy = serde_yaml::from_str("upstream: \"http://localhost:9000\"\nrecord: true\n").unwrap();
+        assert!(validate(&config).is_ok());
+    }
 }
@@ -4,7 +4,16 @@ use crate::config::{
 	parse_auto, parse_json, parse_yaml, validate, Configuration, ConfigError, Endpoint, ValidationError,
 };
 use crate::control_plane::persistence::save_config;
-use crate::engine::EndpointRegistry;
+use crate::export::{export_openapi, DEFAULT_ENGINE_URL};
+use crate::import::{
+	fit_access_log, import_har, import_openapi, import_postman, import_wiremock, merge_config, refit_latencies,
+	AccessLogOptions, HarOptions, ImportMode, ImportOptions, Imported,
+};
+use crate::engine::{
+	capacity_load, endpoint_timeline, upcoming_window_occurrences, ActiveBehavior, CapacityLoad,
+	CapacityPool, EndpointRegistry, LatencyOverride, PoolOwner, ReloadMode, ResolvedEndpoint,
+	ScheduledOccurrence, WorkflowState,
+};
 use axum::body::Bytes;
 use axum::extract::{Multipart, Path, Query, State};
 use axum::http::HeaderMap;
@@ -94,23 +103,22 @@ pub async fn get_endpoint(
 	).into_response()
 }
 
+#[allow(clippy::result_large_err)]
 pub async fn create_endpoint(
 	State(state): State<ControlPlaneState>,
 	Json(endpoint): Json<Endpoint>,
 ) -> Response {
-	let mut config = match current_config(&state) {
-		Ok(config) => config,
-		Err(response) => return response,
-	};
-
-	if config.endpoints.iter().any(|e| e.id == endpoint.id) {
-		return conflict_response("Endpoint id already exists");
-	}
+	apply_config_update(&state, "Endpoint created", Some(&endpoint), |config| {
+		if config.endpoints.iter().any(|e| e.id == endpoint.id) {
+			return Err(conflict_response("Endpoint id already exists"));
+		}
 
-	config.endpoints.push(endpoint.clone());
-	apply_config_update(&state, config, "Endpoint created", Some(&endpoint))
+		config.endpoints.push(endpoint.clone());
+		Ok(())
+	})
 }
 
+#[allow(clippy::result_large_err)]
 pub async fn update_endpoint(
 	State(state): State<ControlPlaneState>,
 	Path(endpoint_id): Path<String>,
@@ -120,35 +128,29 @@ pub async fn update_endpoint(
 		return bad_request_response("Endpoint id in path does not match payload");
 	}
 
-	let mut config = match current_config(&state) {
-		Ok(config) => config,
-		Err(response) => return response,
-	};
+	apply_config_update(&state, "Endpoint updated", Some(&endpoint), |config| {
+		let Some(existing) = config.endpoints.iter().position(|e| e.id == endpoint_id) else {
+			return Err(not_found_response("Endpoint not found"));
+		};
 
-	let Some(existing) = config.endpoints.iter().position(|e| e.id == endpoint_id) else {
-		return not_found_response("Endpoint not found");
-	};
-
-	config.endpoints[existing] = endpoint.clone();
-	apply_config_update(&state, config, "Endpoint updated", Some(&endpoint))
+		config.endpoints[existing] = endpoint.clone();
+		Ok(())
+	})
 }
 
+#[allow(clippy::result_large_err)]
 pub async fn delete_endpoint(
 	State(state): State<ControlPlaneState>,
 	Path(endpoint_id): Path<String>,
 ) -> Response {
-	let mut config = match current_config(&state) {
-		Ok(config) => config,
-		Err(response) => return response,
-	};
-
-	let before = config.endpoints.len();
-	config.endpoints.retain(|e| e.id != endpoint_id);
-	if config.endpoints.len() == before {
-		return not_found_response("Endpoint not found");
-	}
-
-	apply_config_update(&state, config, "Endpoint deleted", None)
+	apply_config_update(&state, "Endpoint deleted", None, |config| {
+		let before = config.endpoints.len();
+		config.endpoints.retain(|e| e.id != endpoint_id);
+		if config.endpoints.len() == before {
+			return Err(not_found_response("Endpoint not found"));
+		}
+		Ok(())
+	})
 }
 
 pub async fn validate_config(headers: HeaderMap, body: Bytes) -> Response {
@@ -171,6 +173,7 @@ pub async fn validate_config(headers: HeaderMap, body: Bytes) -> Response {
 
 pub async fn import_config(
 	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
 	headers: HeaderMap,
 	body: Bytes,
 ) -> Response {
@@ -183,12 +186,12 @@ pub async fn import_config(
 		return validation_error_response(err);
 	}
 
+	let mut guard = state.config.write().expect("config write lock");
 	if let Err(err) = save_config(&state.config_path, &config) {
 		return parse_error_response(err);
 	}
 
-	state.registry.set_config(config.clone());
-	let mut guard = state.config.write().expect("config write lock");
+	state.registry.load_config(config.clone(), reload_mode(&query));
 	*guard = Some(config.clone());
 
 	let endpoints = config.endpoints.iter().map(to_summary).collect::<Vec<_>>();
@@ -207,6 +210,7 @@ pub async fn import_config(
 
 pub async fn import_config_multipart(
 	State(state): State<ControlPlaneState>,
+	query: Query<HashMap<String, String>>,
 	mut multipart: Multipart,
 ) -> Response {
 	let mut content_type = None;
@@ -242,15 +246,242 @@ pub async fn import_config_multipart(
 		})
 		.unwrap_or_default();
 
-	import_config(State(state), headers, content).await
+	import_config(State(state), query, headers, content).await
 }
 
-pub async fn export_config(
+/// Endpoints from an OpenAPI 3 document. `?mode=replace` swaps out the active
+/// configuration; by default the endpoints are merged into it.
+pub async fn import_openapi_spec(
 	State(state): State<ControlPlaneState>,
 	Query(query): Query<HashMap<String, String>>,
+	body: Bytes,
 ) -> Response {
-	let config = state.config.read().expect("config read lock");
-	let Some(config) = config.as_ref() else {
+	import_with(state, query, body, import_openapi)
+}
+
+/// Endpoints from WireMock stub mappings, with a conversion report of the
+/// stub features that were ignored or approximated.
+pub async fn import_wiremock_mappings(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+	body: Bytes,
+) -> Response {
+	import_with(state, query, body, import_wiremock)
+}
+
+/// Endpoints from the saved examples of a Postman collection, with a
+/// conversion report.
+pub async fn import_postman_collection(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+	body: Bytes,
+) -> Response {
+	import_with(state, query, body, import_postman)
+}
+
+/// Run an importer that takes `ImportOptions` from the query.
+fn import_with(
+	state: ControlPlaneState,
+	query: HashMap<String, String>,
+	body: Bytes,
+	importer: fn(&str, &ImportOptions) -> Result<Imported, ConfigError>,
+) -> Response {
+	let mode = match import_mode(&query) {
+		Ok(mode) => mode,
+		Err(response) => return response,
+	};
+	let options = match ImportOptions::from_pairs(query.iter().map(|(key, value)| (key.as_str(), value.as_str()))) {
+		Ok(options) => options,
+		Err(message) => return bad_request_response(&message),
+	};
+
+	match importer(&String::from_utf8_lossy(&body), &options) {
+		Ok(imported) => apply_import(&state, imported, mode, &query),
+		Err(err) => parse_error_response(err),
+	}
+}
+
+/// Endpoints from a HAR capture, with latencies and error rates fitted from
+/// the recorded entries. `?host=` keeps only the given hosts (comma separated)
+/// and `?template_paths=false` keeps id-like path segments literal.
+pub async fn import_har_archive(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+	body: Bytes,
+) -> Response {
+	let mode = match import_mode(&query) {
+		Ok(mode) => mode,
+		Err(response) => return response,
+	};
+	let options = match HarOptions::from_pairs(query.iter().map(|(key, value)| (key.as_str(), value.as_str()))) {
+		Ok(options) => options,
+		Err(message) => return bad_request_response(&message),
+	};
+
+	match import_har(&String::from_utf8_lossy(&body), &options) {
+		Ok(imported) => apply_import(&state, imported, mode, &query),
+		Err(err) => parse_error_response(err),
+	}
+}
+
+/// Endpoints with latencies fitted from an access log. In merge mode the
+/// active endpoints keep everything but their latency, and requests are
+/// grouped by the active routes they match. The per-route quantiles and
+/// candidate fits are returned as `routes`.
+#[allow(clippy::result_large_err)]
+pub async fn import_access_log(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+	body: Bytes,
+) -> Response {
+	let mode = match import_mode(&query) {
+		Ok(mode) => mode,
+		Err(response) => return response,
+	};
+	let mut options = match AccessLogOptions::from_pairs(query.iter().map(|(key, value)| (key.as_str(), value.as_str()))) {
+		Ok(options) => options,
+		Err(message) => return bad_request_response(&message),
+	};
+	let mut routes = Vec::new();
+	let loaded = load_import(&state, mode, &query, |active| {
+		if let Some(active) = active {
+			options.routes = active.endpoints.iter().map(|endpoint| (endpoint.method.clone(), endpoint.path.clone())).collect();
+		}
+		let fitted = fit_access_log(&String::from_utf8_lossy(&body), &options).map_err(parse_error_response)?;
+		routes = fitted.routes;
+		let config = match active {
+			Some(active) => refit_latencies(active, fitted.config),
+			None => fitted.config,
+		};
+		Ok(Imported {
+			config,
+			warnings: fitted.warnings,
+			report: None,
+		})
+	});
+	match loaded {
+		Ok(mut body) => {
+			body["routes"] = json!(routes);
+			axum::Json(body).into_response()
+		}
+		Err(response) => response,
+	}
+}
+
+/// The active configuration as an OpenAPI 3 document, JSON by default or
+/// `?format=yaml`. `?server=` sets the server URL clients should call.
+pub async fn export_openapi_spec(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+) -> Response {
+	let config = match current_config(&state) {
+		Ok(config) => config,
+		Err(response) => return response,
+	};
+	let server = query.get("server").map(String::as_str).unwrap_or(DEFAULT_ENGINE_URL);
+	let document = export_openapi(&config, server);
+
+	let (body, content_type) = if query.get("format").map(String::as_str) == Some("yaml") {
+		(serde_yaml::to_string(&document).unwrap_or_default(), "application/x-yaml")
+	} else {
+		(serde_json::to_string_pretty(&document).unwrap_or_default(), "application/json")
+	};
+	([(axum::http::header::CONTENT_TYPE, content_type)], body).into_response()
+}
+
+/// `?mode=`, merging by default.
+#[allow(clippy::result_large_err)]
+fn import_mode(query: &HashMap<String, String>) -> Result<ImportMode, Response> {
+	match query.get("mode").map(String::as_str) {
+		None => Ok(ImportMode::Merge),
+		Some(mode) => ImportMode::parse(mode).ok_or_else(|| bad_request_response("mode must be 'merge' or 'replace'")),
+	}
+}
+
+/// Validate and load an imported configuration, or with `?dry_run=true` only
+/// return it.
+#[allow(clippy::result_large_err)]
+fn apply_import(
+	state: &ControlPlaneState,
+	imported: Imported,
+	mode: ImportMode,
+	query: &HashMap<String, String>,
+) -> Response {
+	match load_import(state, mode, query, |_| Ok(imported)) {
+		Ok(body) => axum::Json(body).into_response(),
+		Err(response) => response,
+	}
+}
+
+/// `apply_import`'s response body, for callers that add to it. `build` gets
+/// the active configuration when merging, and the config lock is held until
+/// the result is loaded so concurrent updates are not lost.
+#[allow(clippy::result_large_err)]
+fn load_import(
+	state: &ControlPlaneState,
+	mode: ImportMode,
+	query: &HashMap<String, String>,
+	build: impl FnOnce(Option<&Configuration>) -> Result<Imported, Response>,
+) -> Result<serde_json::Value, Response> {
+	let mut guard = state.config.write().expect("config write lock");
+	let active = match mode {
+		ImportMode::Merge => guard.as_ref(),
+		ImportMode::Replace => None,
+	};
+	let imported = build(active)?;
+	let imported_count = imported.config.endpoints.len();
+	let config = match (mode, guard.clone()) {
+		(ImportMode::Merge, Some(active)) => merge_config(active, imported.config),
+		_ => imported.config,
+	};
+	if let Err(err) = validate(&config) {
+		return Err(validation_error_response(err));
+	}
+
+	if matches!(query.get("dry_run").map(String::as_str), Some("true") | Some("1")) {
+		return Ok(json!({
+			"status": "success",
+			"message": "Import preview; nothing was loaded",
+			"mode": mode,
+			"warnings": imported.warnings,
+			"report": imported.report,
+			"config": config
+		}));
+	}
+
+	if let Err(err) = save_config(&state.config_path, &config) {
+		return Err(parse_error_response(err));
+	}
+	state.registry.load_config(config.clone(), reload_mode(query));
+	*guard = Some(config.clone());
+
+	Ok(json!({
+		"status": "success",
+		"message": format!("Imported {} endpoint(s)", imported_count),
+		"mode": mode,
+		"summary": {
+			"endpoints_imported": imported_count,
+			"endpoints_count": config.endpoints.len(),
+			"endpoints": config.endpoints.iter().map(to_summary).collect::<Vec<_>>()
+		},
+		"warnings": imported.warnings,
+		"report": imported.report
+	}))
+}
+
+/// Imports keep runtime state for unchanged endpoints unless `?reset=true`.
+fn reload_mode(query: &HashMap<String, String>) -> ReloadMode {
+	match query.get("reset").map(String::as_str) {
+		Some("true") | Some("1") => ReloadMode::Reset,
+		_ => ReloadMode::Preserve,
+	}
+}
+
+pub async fn reset_runtime(State(state): State<ControlPlaneState>) -> Response {
+	// Held across the reload so a concurrent import is not replaced by the
+	// configuration read here.
+	let guard = state.config.write().expect("config write lock");
+	let Some(config) = guard.clone() else {
 		return (
 			axum::http::StatusCode::NOT_FOUND,
 			axum::Json(json!({"status": "error", "message": "No configuration currently loaded"})),
@@ -258,6 +489,218 @@ pub async fn export_config(
 			.into_response();
 	};
 
+	state.registry.load_config(config, ReloadMode::Reset);
+	drop(guard);
+	axum::Json(json!({
+		"status": "success",
+		"message": "Runtime state reset"
+	}))
+	.into_response()
+}
+
+pub async fn list_resources(State(state): State<ControlPlaneState>) -> Response {
+	let resources = state
+		.registry
+		.resources()
+		.iter()
+		.map(|store| {
+			let store = store.lock().expect("resource lock");
+			json!({
+				"id": store.resource().id,
+				"id_field": store.resource().id_field,
+				"count": store.items().len()
+			})
+		})
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({"status": "success", "resources": resources})).into_response()
+}
+
+pub async fn get_resource(
+	State(state): State<ControlPlaneState>,
+	Path(resource_id): Path<String>,
+) -> Response {
+	let Some(store) = state.registry.resource(&resource_id) else {
+		return not_found_response("Resource not found");
+	};
+	let store = store.lock().expect("resource lock");
+
+	axum::Json(json!({
+		"status": "success",
+		"id": resource_id,
+		"count": store.items().len(),
+		"items": store.items()
+	}))
+	.into_response()
+}
+
+/// Replace a collection's contents with the JSON array in the body.
+pub async fn seed_resource(
+	State(state): State<ControlPlaneState>,
+	Path(resource_id): Path<String>,
+	body: Bytes,
+) -> Response {
+	let Some(store) = state.registry.resource(&resource_id) else {
+		return not_found_response("Resource not found");
+	};
+	let items: Vec<serde_json::Value> = match serde_json::from_slice(&body) {
+		Ok(items) => items,
+		Err(err) => return bad_request_response(&format!("Body must be a JSON array of items: {}", err)),
+	};
+
+	let mut store = store.lock().expect("resource lock");
+	if let Err(err) = store.replace(items) {
+		return bad_request_response(&format!("Invalid item: {}", err));
+	}
+
+	axum::Json(json!({
+		"status": "success",
+		"message": "Resource seeded",
+		"count": store.items().len()
+	}))
+	.into_response()
+}
+
+/// Tracked jobs of each job type, counted by phase.
+pub async fn list_jobs(State(state): State<ControlPlaneState>) -> Response {
+	let jobs = state
+		.registry
+		.jobs()
+		.iter()
+		.map(|queue| {
+			let queue = queue.lock().expect("job lock");
+			json!({
+				"id": queue.job().id,
+				"tracked": queue.len(),
+				"phases": queue.phase_counts()
+			})
+		})
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({"status": "success", "jobs": jobs})).into_response()
+}
+
+pub async fn list_workflows(State(state): State<ControlPlaneState>) -> Response {
+	let workflows = state
+		.registry
+		.workflows()
+		.iter()
+		.map(|workflow| workflow_json(&workflow.lock().expect("workflow lock")))
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({"status": "success", "workflows": workflows})).into_response()
+}
+
+pub async fn get_workflow(
+	State(state): State<ControlPlaneState>,
+	Path(workflow_id): Path<String>,
+) -> Response {
+	let Some(workflow) = state.registry.workflow(&workflow_id) else {
+		return not_found_response("Workflow not found");
+	};
+	let workflow = workflow_json(&workflow.lock().expect("workflow lock"));
+
+	axum::Json(json!({"status": "success", "workflow": workflow})).into_response()
+}
+
+/// Callback deliveries, oldest first; `?endpoint_id=` narrows to one endpoint.
+pub async fn list_callbacks(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+) -> Response {
+	let log = state.registry.callbacks();
+	let log = log.lock().expect("callback lock");
+	let callbacks = log
+		.records()
+		.filter(|record| query.get("endpoint_id").is_none_or(|id| *id == record.endpoint_id))
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({"status": "success", "callbacks": callbacks})).into_response()
+}
+
+/// Back to the initial state; `?session=` limits the reset to one session.
+pub async fn reset_workflow(
+	State(state): State<ControlPlaneState>,
+	Path(workflow_id): Path<String>,
+	Query(query): Query<HashMap<String, String>>,
+) -> Response {
+	let Some(workflow) = state.registry.workflow(&workflow_id) else {
+		return not_found_response("Workflow not found");
+	};
+	let mut workflow = workflow.lock().expect("workflow lock");
+	workflow.reset(query.get("session").map(String::as_str));
+
+	axum::Json(json!({
+		"status": "success",
+		"message": "Workflow reset",
+		"workflow": workflow_json(&workflow)
+	}))
+	.into_response()
+}
+
+fn workflow_json(workflow: &WorkflowState) -> serde_json::Value {
+	json!({
+		"id": workflow.workflow().id,
+		"initial_state": workflow.workflow().initial_state,
+		"state": workflow.global_state(),
+		"sessions": workflow.session_states()
+	})
+}
+
+/// Restore a collection's configured seed items.
+pub async fn reset_resource(
+	State(state): State<ControlPlaneState>,
+	Path(resource_id): Path<String>,
+) -> Response {
+	let Some(store) = state.registry.resource(&resource_id) else {
+		return not_found_response("Resource not found");
+	};
+	let mut store = store.lock().expect("resource lock");
+	store.reset();
+
+	axum::Json(json!({
+		"status": "success",
+		"message": "Resource reset",
+		"count": store.items().len()
+	}))
+	.into_response()
+}
+
+/// Routes captured by the recording proxy, with request counts per status.
+pub async fn recording(State(state): State<ControlPlaneState>) -> Response {
+	let recording = state.registry.recording();
+	let recording = recording.lock().expect("recording lock");
+
+	axum::Json(json!({"status": "success", "routes": recording.summary(), "dropped": recording.dropped()})).into_response()
+}
+
+pub async fn clear_recording(State(state): State<ControlPlaneState>) -> Response {
+	state.registry.recording().lock().expect("recording lock").clear();
+
+	axum::Json(json!({"status": "success", "message": "Recording cleared"})).into_response()
+}
+
+/// The loaded configuration, or with `?source=recording` one built from
+/// recorded traffic.
+pub async fn export_config(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+) -> Response {
+	let config = if query.get("source").map(String::as_str) == Some("recording") {
+		let recording = state.registry.recording();
+		let recording = recording.lock().expect("recording lock");
+		if recording.is_empty() {
+			return not_found_response("No traffic recorded");
+		}
+		recording.configuration()
+	} else {
+		match state.config.read().expect("config read lock").as_ref() {
+			Some(config) => config.clone(),
+			None => return not_found_response("No configuration currently loaded"),
+		}
+	};
+	let config = &config;
+
 	let format = query.get("format").map(String::as_str).unwrap_or("yaml");
 	let (body, content_type, extension) = if format == "json" {
 		let body = serde_json::to_string_pretty(config).unwrap_or_else(|_| "{}".to_string());
@@ -280,6 +723,224 @@ pub async fn export_config(
 	response
 }
 
+pub async fn window_occurrences(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+) -> Response {
+	let count = query
+		.get("count")
+		.and_then(|value| value.parse::<usize>().ok())
+		.unwrap_or(5)
+		.clamp(1, 100);
+
+	if state.config.read().expect("config read lock").is_none() {
+		return not_found_response("No configuration currently loaded");
+	}
+
+	let now = chrono::Utc::now();
+	let windows = state
+		.registry
+		.window_clocks()
+		.into_iter()
+		.enumerate()
+		.flat_map(|(index, (window, clocks))| {
+			clocks.into_iter().map(move |clock| {
+				let elapsed_ms = clock
+					.loaded_at
+					.map(|loaded_at| loaded_at.elapsed().as_millis() as f64)
+					.unwrap_or(0.0);
+				let occurrences = upcoming_window_occurrences(&window, elapsed_ms, now, count)
+					.into_iter()
+					.map(|(start_ms, end_ms)| {
+						json!({
+							"start": offset_to_wall_clock(now, elapsed_ms, start_ms),
+							"end": offset_to_wall_clock(now, elapsed_ms, end_ms)
+						})
+					})
+					.collect::<Vec<_>>();
+				json!({
+					"index": index,
+					"id": window.id,
+					"scope": window.scope,
+					"mode": window.schedule.mode,
+					"endpoints": clock.endpoint_ids,
+					"occurrences": occurrences
+				})
+			})
+		})
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({
+		"status": "success",
+		"now": now.to_rfc3339(),
+		"windows": windows
+	}))
+	.into_response()
+}
+
+pub async fn timeline(
+	State(state): State<ControlPlaneState>,
+	Query(query): Query<HashMap<String, String>>,
+) -> Response {
+	let count = query
+		.get("count")
+		.and_then(|value| value.parse::<usize>().ok())
+		.unwrap_or(3)
+		.clamp(1, 100);
+
+	let now = chrono::Utc::now();
+	let endpoints = state
+		.registry
+		.list()
+		.iter()
+		.map(|resolved| {
+			let timeline = endpoint_timeline(resolved, now, count);
+			let elapsed_ms = timeline.elapsed_ms;
+			let active = |items: &[ActiveBehavior]| {
+				items
+					.iter()
+					.map(|item| {
+						json!({
+							"id": item.id,
+							"priority": item.priority,
+							"ramp_factor": item.ramp_factor,
+							"start": offset_to_wall_clock(now, elapsed_ms, item.start_ms),
+							"end": offset_to_wall_clock(now, elapsed_ms, item.end_ms)
+						})
+					})
+					.collect::<Vec<_>>()
+			};
+			let upcoming = |items: &[ScheduledOccurrence]| {
+				items
+					.iter()
+					.map(|item| {
+						json!({
+							"id": item.id,
+							"start": offset_to_wall_clock(now, elapsed_ms, item.start_ms),
+							"end": offset_to_wall_clock(now, elapsed_ms, item.end_ms)
+						})
+					})
+					.collect::<Vec<_>>()
+			};
+			let latency_override = |item: &Option<LatencyOverride>| {
+				item.as_ref().map(|item| {
+					json!({
+						"id": item.id,
+						"factor": item.factor,
+						"latency": item.latency
+					})
+				})
+			};
+
+			json!({
+				"endpoint_id": timeline.endpoint_id,
+				"method": format!("{:?}", resolved.endpoint.method).to_uppercase(),
+				"path": resolved.endpoint.path,
+				"elapsed_ms": elapsed_ms,
+				"active_windows": active(&timeline.active_windows),
+				"active_bursts": active(&timeline.active_bursts),
+				"profiles": timeline
+					.profiles
+					.iter()
+					.map(|profile| {
+						json!({
+							"id": profile.id,
+							"latency_multiplier": profile.values.latency_multiplier,
+							"error_rate": profile.values.error_rate,
+							"rate_limit_factor": profile.values.rate_limit_factor
+						})
+					})
+					.collect::<Vec<_>>(),
+				"concurrency_limits": timeline
+					.concurrency
+					.iter()
+					.map(|load| pool_json(load, "max_in_flight"))
+					.collect::<Vec<_>>(),
+				"capacity": timeline
+					.capacity
+					.iter()
+					.map(|load| {
+						let mut value = pool_json(load, "workers");
+						value["latency_multiplier"] = json!(load.latency_multiplier);
+						value
+					})
+					.collect::<Vec<_>>(),
+				"latency": {
+					"base": timeline.base_latency,
+					"window_override": latency_override(&timeline.window_latency),
+					"burst_spike": latency_override(&timeline.burst_latency)
+				},
+				"error_profile": timeline.error_profile,
+				"upcoming": {
+					"windows": upcoming(&timeline.upcoming_windows),
+					"bursts": upcoming(&timeline.upcoming_bursts)
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({
+		"status": "success",
+		"now": now.to_rfc3339(),
+		"endpoints": endpoints
+	}))
+	.into_response()
+}
+
+pub async fn concurrency(State(state): State<ControlPlaneState>) -> Response {
+	let endpoints = state.registry.list();
+	let limits = unique_pools(&endpoints, |resolved| &resolved.concurrency)
+		.iter()
+		.map(|pool| pool_json(&capacity_load(pool), "max_in_flight"))
+		.collect::<Vec<_>>();
+	let capacity = unique_pools(&endpoints, |resolved| &resolved.capacity)
+		.iter()
+		.map(|pool| pool_json(&capacity_load(pool), "workers"))
+		.collect::<Vec<_>>();
+
+	axum::Json(json!({
+		"status": "success",
+		"concurrency_limits": limits,
+		"capacity": capacity
+	}))
+	.into_response()
+}
+
+/// Group pools are shared by several endpoints; list each one once.
+fn unique_pools(
+	endpoints: &[ResolvedEndpoint],
+	select: fn(&ResolvedEndpoint) -> &Vec<Arc<CapacityPool>>,
+) -> Vec<Arc<CapacityPool>> {
+	let mut pools: Vec<Arc<CapacityPool>> = Vec::new();
+	for pool in endpoints.iter().flat_map(select) {
+		if !pools.iter().any(|known| Arc::ptr_eq(known, pool)) {
+			pools.push(Arc::clone(pool));
+		}
+	}
+	pools
+}
+
+fn pool_json(load: &CapacityLoad, slots_key: &str) -> serde_json::Value {
+	let (scope, id) = match &load.owner {
+		PoolOwner::Endpoint(id) => ("endpoint", id),
+		PoolOwner::Group(id) => ("group", id),
+	};
+	let mut value = json!({
+		"scope": scope,
+		"id": id,
+		"queue_size": load.queue_size,
+		"in_flight": load.in_flight,
+		"queued": load.queued
+	});
+	value[slots_key] = json!(load.slots);
+	value
+}
+
+fn offset_to_wall_clock(now: chrono::DateTime<chrono::Utc>, elapsed_ms: f64, offset_ms: f64) -> String {
+	let delta = chrono::Duration::milliseconds((offset_ms - elapsed_ms).round() as i64);
+	(now + delta).to_rfc3339()
+}
+
 fn parse_config_from_body(headers: HeaderMap, body: Bytes) -> Result<Configuration, ConfigError> {
 	let content = String::from_utf8_lossy(&body).to_string();
 	let content_type = headers
@@ -328,6 +989,7 @@ fn parse_error_response(err: ConfigError) -> Response {
 		.into_response()
 }
 
+#[allow(clippy::result_large_err)]
 fn current_config(state: &ControlPlaneState) -> Result<Configuration, Response> {
 	let config = state.config.read().expect("config read lock");
 	if let Some(config) = config.as_ref() {
@@ -337,12 +999,22 @@ fn current_config(state: &ControlPlaneState) -> Result<Configuration, Response>
 	}
 }
 
+/// Edit the active configuration under the config lock, so that concurrent
+/// updates and imports are applied one after the other.
 fn apply_config_update(
 	state: &ControlPlaneState,
-	config: Configuration,
 	message: &str,
 	endpoint: Option<&Endpoint>,
+	edit: impl FnOnce(&mut Configuration) -> Result<(), Response>,
 ) -> Response {
+	let mut guard = state.config.write().expect("config write lock");
+	let Some(mut config) = guard.clone() else {
+		return not_found_response("No configuration currently loaded");
+	};
+	if let Err(response) = edit(&mut config) {
+		return response;
+	}
+
 	if let Err(err) = validate(&config) {
 		return validation_error_response(err);
 	}
@@ -352,7 +1024,6 @@ fn apply_config_update(
 	}
 
 		state.registry.set_config(config.clone());
-	let mut guard = state.config.write().expect("config write lock");
 	*guard = Some(config.clone());
 
 	let summary = endpoint.map(to_summary);
@@ -544,4 +1215,757 @@ mod tests {
 
 		assert_eq!(response.status(), StatusCode::NOT_FOUND);
 	}
+
+	#[tokio::test]
+	async fn test_window_occurrences() {
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "health"
+    method: "GET"
+    path: "/health"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+behavior_windows:
+  - id: "nightly"
+    scope: { global: true }
+    schedule: { mode: "cron", cron: "0 2 * * *", timezone: "UTC", duration_ms: 1800000 }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config.clone());
+		*state.config.write().unwrap() = Some(config);
+
+		let router = crate::control_plane::server::build_router(state);
+		let response = router
+			.oneshot(
+				axum::http::Request::builder()
+					.uri("/api/behavior-windows/occurrences?count=3")
+					.body(axum::body::Body::empty())
+					.unwrap(),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(response.status(), StatusCode::OK);
+		let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		assert_eq!(body["windows"][0]["id"], "nightly");
+		assert_eq!(body["windows"][0]["mode"], "cron");
+		assert_eq!(body["windows"][0]["occurrences"].as_array().unwrap().len(), 3);
+	}
+
+	#[tokio::test]
+	async fn test_window_occurrences_follow_each_endpoint_clock() {
+		let state = state();
+		let config = |override_rate: f64| {
+			crate::config::parse_yaml(&format!(
+				r#"
+version: "1.0"
+endpoints:
+  - id: "a"
+    method: "GET"
+    path: "/a"
+    latency: {{ distribution: "fixed", params: {{ delay_ms: 0 }} }}
+    response: {{ status: 200, body: "ok" }}
+  - id: "b"
+    method: "GET"
+    path: "/b"
+    latency: {{ distribution: "fixed", params: {{ delay_ms: 0 }} }}
+    response: {{ status: 200, body: "ok" }}
+behavior_windows:
+  - id: "warmup"
+    scope: {{ global: true }}
+    schedule: {{ mode: "fixed", start_offset_ms: 60000, duration_ms: 10000 }}
+  - id: "a-only"
+    scope: {{ endpoint_id: "a" }}
+    schedule: {{ mode: "fixed", start_offset_ms: 0, duration_ms: 10000 }}
+    error_profile_override: {{ rate: {override_rate} }}
+"#
+			))
+			.expect("config")
+		};
+		state.registry.set_config(config(0.5));
+		tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+		state.registry.set_config(config(1.0));
+		*state.config.write().unwrap() = Some(config(1.0));
+
+		let router = crate::control_plane::server::build_router(state);
+		let response = router
+			.oneshot(
+				axum::http::Request::builder()
+					.uri("/api/behavior-windows/occurrences")
+					.body(axum::body::Body::empty())
+					.unwrap(),
+			)
+			.await
+			.unwrap();
+		let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		let warmup: Vec<_> = body["windows"].as_array().unwrap().iter().filter(|window| window["id"] == "warmup").collect();
+		assert_eq!(warmup.len(), 2);
+		assert_eq!(warmup[0]["endpoints"], json!(["a"]));
+		assert_eq!(warmup[1]["endpoints"], json!(["b"]));
+		let start = |window: &serde_json::Value| {
+			chrono::DateTime::parse_from_rfc3339(window["occurrences"][0]["start"].as_str().unwrap()).unwrap()
+		};
+		// "b" kept the clock of the first load, "a" restarted with the reload.
+		assert!((start(warmup[0]) - start(warmup[1])).num_milliseconds() >= 40);
+	}
+
+	#[tokio::test]
+	async fn test_timeline() {
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "health"
+    method: "GET"
+    path: "/health"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+behavior_windows:
+  - id: "outage"
+    scope: { endpoint_id: "health" }
+    schedule: { mode: "fixed", start_offset_ms: 0, duration_ms: 600000 }
+    error_profile_override: { rate: 1.0, codes: [503] }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config);
+
+		let router = crate::control_plane::server::build_router(state);
+		let response = router
+			.oneshot(
+				axum::http::Request::builder()
+					.uri("/api/timeline")
+					.body(axum::body::Body::empty())
+					.unwrap(),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(response.status(), StatusCode::OK);
+		let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		let endpoint = &body["endpoints"][0];
+		assert_eq!(endpoint["endpoint_id"], "health");
+		assert_eq!(endpoint["active_windows"][0]["id"], "outage");
+		assert_eq!(endpoint["error_profile"]["rate"], 1.0);
+	}
+
+	#[tokio::test]
+	async fn test_concurrency_lists_shared_group_limit_once() {
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "orders"
+    method: "GET"
+    path: "/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+    concurrency_limit: { max_in_flight: 2 }
+  - id: "invoices"
+    method: "GET"
+    path: "/invoices"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+endpoint_groups:
+  - id: "db"
+    endpoint_ids: ["orders", "invoices"]
+    concurrency_limit: { max_in_flight: 20, queue_size: 5, queue_timeout_ms: 100 }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config);
+
+		let router = crate::control_plane::server::build_router(state);
+		let response = router
+			.oneshot(
+				axum::http::Request::builder()
+					.uri("/api/concurrency")
+					.body(axum::body::Body::empty())
+					.unwrap(),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(response.status(), StatusCode::OK);
+		let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		let limits = body["concurrency_limits"].as_array().unwrap();
+		assert_eq!(limits.len(), 2);
+		assert_eq!(limits[0]["scope"], "endpoint");
+		assert_eq!(limits[0]["max_in_flight"], 2);
+		assert_eq!(limits[1]["scope"], "group");
+		assert_eq!(limits[1]["id"], "db");
+		assert_eq!(limits[1]["in_flight"], 0);
+	}
+
+	#[tokio::test]
+	async fn test_reset_runtime() {
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "orders"
+    method: "GET"
+    path: "/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+    rate_limit: { requests_per_second: 1, burst: 1 }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config.clone());
+		*state.config.write().unwrap() = Some(config);
+
+		let limiter = |state: &ControlPlaneState| {
+			let resolved = state.registry.get(&crate::config::HttpMethod::Get, "/orders").unwrap();
+			resolved.endpoint.rate_limiter.unwrap()
+		};
+		let before = limiter(&state);
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let response = router
+			.oneshot(
+				axum::http::Request::builder()
+					.method("POST")
+					.uri("/api/runtime/reset")
+					.body(axum::body::Body::empty())
+					.unwrap(),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(response.status(), StatusCode::OK);
+		assert!(!Arc::ptr_eq(&before, &limiter(&state)));
+	}
+
+	#[tokio::test]
+	async fn test_seed_and_reset_resource() {
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+resources:
+  - id: "users"
+    seed: [{ id: 1, name: "ada" }]
+endpoints:
+  - id: "list-users"
+    method: "GET"
+    path: "/users"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    resource: { collection: "users", operation: "list" }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config);
+		let router = crate::control_plane::server::build_router(state.clone());
+		let send = |method: &str, uri: &str, body: &str| {
+			let request = axum::http::Request::builder()
+				.method(method)
+				.uri(uri)
+				.body(axum::body::Body::from(body.to_string()))
+				.unwrap();
+			router.clone().oneshot(request)
+		};
+
+		let seeded = send("PUT", "/api/resources/users", r#"[{"id": 5}, {"id": 6}]"#).await.unwrap();
+		assert_eq!(seeded.status(), StatusCode::OK);
+		let rejected = send("PUT", "/api/resources/users", r#"[{"id": 5}, {"id": 5}]"#).await.unwrap();
+		assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+
+		let listed = send("GET", "/api/resources/users", "").await.unwrap();
+		let body = axum::body::to_bytes(listed.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		assert_eq!(body["count"], 2);
+
+		let reset = send("POST", "/api/resources/users/reset", "").await.unwrap();
+		assert_eq!(reset.status(), StatusCode::OK);
+		let store = state.registry.resource("users").unwrap();
+		assert_eq!(store.lock().unwrap().items(), &[serde_json::json!({"id": 1, "name": "ada"})]);
+
+		let missing = send("GET", "/api/resources/orders", "").await.unwrap();
+		assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn test_inspect_and_reset_workflow_session() {
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+workflows:
+  - id: "checkout"
+    initial_state: "cart"
+    session: { source: "cookie", name: "sid" }
+endpoints:
+  - id: "pay"
+    method: "POST"
+    path: "/pay"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    workflow:
+      workflow: "checkout"
+      variants: [{ required_state: "cart", new_state: "paid" }]
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config);
+		let workflow = state.registry.workflow("checkout").unwrap();
+		let binding = state.registry.list()[0].endpoint.workflow.clone().unwrap();
+		workflow.lock().unwrap().advance(&binding, Some("s1"));
+		workflow.lock().unwrap().advance(&binding, Some("s2"));
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let send = |method: &str, uri: &str| {
+			let request = axum::http::Request::builder()
+				.method(method)
+				.uri(uri)
+				.body(axum::body::Body::empty())
+				.unwrap();
+			router.clone().oneshot(request)
+		};
+
+		let response = send("GET", "/api/workflows/checkout").await.unwrap();
+		let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		assert_eq!(body["workflow"]["state"], "cart");
+		assert_eq!(body["workflow"]["sessions"]["s1"], "paid");
+
+		let reset = send("POST", "/api/workflows/checkout/reset?session=s1").await.unwrap();
+		assert_eq!(reset.status(), StatusCode::OK);
+		assert_eq!(workflow.lock().unwrap().current(Some("s1")), "cart");
+		assert_eq!(workflow.lock().unwrap().current(Some("s2")), "paid");
+	}
+
+	#[tokio::test]
+	async fn test_callbacks_retry_and_are_logged() {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+		use std::sync::Mutex;
+
+		let received = Arc::new(Mutex::new(Vec::new()));
+		let calls = Arc::new(AtomicUsize::new(0));
+		let listener_app = axum::Router::new().route(
+			"/hooks/:id",
+			axum::routing::post({
+				let received = Arc::clone(&received);
+				let calls = Arc::clone(&calls);
+				move |Path(id): Path<String>, body: String| async move {
+					received.lock().unwrap().push((id, body));
+					if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+						StatusCode::INTERNAL_SERVER_ERROR
+					} else {
+						StatusCode::NO_CONTENT
+					}
+				}
+			}),
+		);
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, listener_app).await });
+
+		let state = state();
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "pay"
+    method: "POST"
+    path: "/payments/{id}"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 202, body: '{"state": "processing"}' }
+    callbacks:
+      - url: "{{request.body.notify}}/{{request.path.id}}"
+        delay: { distribution: "fixed", params: { delay_ms: 10 } }
+        body: '{"payment": "{{request.path.id}}", "was": "{{response.body.state}}"}'
+        retry: { max_attempts: 3, backoff_ms: 10 }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config);
+
+		let engine = crate::engine::build_router(Arc::clone(&state.registry));
+		let request = axum::http::Request::builder()
+			.method("POST")
+			.uri("/payments/p-7")
+			.body(axum::body::Body::from(format!(r#"{{"notify": "http://{}/hooks"}}"#, addr)))
+			.unwrap();
+		assert_eq!(engine.oneshot(request).await.unwrap().status(), StatusCode::ACCEPTED);
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let mut body = serde_json::Value::Null;
+		for _ in 0..100 {
+			let request = axum::http::Request::builder()
+				.uri("/api/callbacks?endpoint_id=pay")
+				.body(axum::body::Body::empty())
+				.unwrap();
+			let response = router.clone().oneshot(request).await.unwrap();
+			let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+			body = serde_json::from_slice(&bytes).unwrap();
+			if body["callbacks"][0]["state"] != "pending" {
+				break;
+			}
+			tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+		}
+
+		let record = &body["callbacks"][0];
+		assert_eq!(record["state"], "delivered");
+		assert_eq!(record["attempts"][0]["status"], 500);
+		assert_eq!(record["attempts"][1]["status"], 204);
+		let received = received.lock().unwrap();
+		assert_eq!(received.len(), 2);
+		assert_eq!(received[0], ("p-7".to_string(), r#"{"payment": "p-7", "was": "processing"}"#.to_string()));
+	}
+
+	#[tokio::test]
+	async fn test_recorded_traffic_exports_as_configuration() {
+		let upstream_app = axum::Router::new().route(
+			"/orders/:id",
+			axum::routing::get(|Path(id): Path<String>| async move {
+				if id == "0" {
+					(StatusCode::NOT_FOUND, r#"{"error": "missing"}"#.to_string())
+				} else {
+					(StatusCode::OK, format!(r#"{{"id": {}}}"#, id))
+				}
+			}),
+		)
+		.route(
+			"/customers/:name",
+			axum::routing::get(|Path(name): Path<String>| async move { format!(r#"{{"name": "{}"}}"#, name) }),
+		);
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, upstream_app).await });
+
+		let state = state();
+		let config = crate::config::parse_yaml(&format!(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "customer"
+    method: "GET"
+    path: "/customers/{{name}}"
+    latency: {{ distribution: "fixed", params: {{ delay_ms: 0 }} }}
+    response: {{ status: 200 }}
+    proxy: {{ upstream: "http://{addr}", record: true }}
+proxy:
+  upstream: "http://{addr}"
+  record: true
+"#
+		))
+		.expect("config");
+		state.registry.set_config(config);
+
+		let engine = crate::engine::build_router(Arc::clone(&state.registry));
+		for path in ["/orders/0", "/orders/1", "/orders/2", "/orders/3", "/customers/ada", "/customers/grace"] {
+			let request = axum::http::Request::builder().uri(path).body(axum::body::Body::empty()).unwrap();
+			engine.clone().oneshot(request).await.unwrap();
+		}
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let request = axum::http::Request::builder()
+			.uri("/api/config/export?source=recording&format=json")
+			.body(axum::body::Body::empty())
+			.unwrap();
+		let response = router.clone().oneshot(request).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let exported = parse_json(&String::from_utf8_lossy(&bytes)).expect("exported config");
+		assert!(validate(&exported).is_ok());
+		let paths: Vec<&str> = exported.endpoints.iter().map(|endpoint| endpoint.path.as_str()).collect();
+		assert_eq!(paths, vec!["/orders/{id}", "/customers/{name}"]);
+		let endpoint = &exported.endpoints[0];
+		assert_eq!(endpoint.response.status, 200);
+		assert_eq!(endpoint.error_profile.codes, vec![404]);
+		assert_eq!(endpoint.error_profile.rate, 0.25);
+
+		let request = axum::http::Request::builder()
+			.method("DELETE")
+			.uri("/api/recording")
+			.body(axum::body::Body::empty())
+			.unwrap();
+		router.clone().oneshot(request).await.unwrap();
+		let request = axum::http::Request::builder()
+			.uri("/api/config/export?source=recording")
+			.body(axum::body::Body::empty())
+			.unwrap();
+		assert_eq!(router.oneshot(request).await.unwrap().status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn test_import_openapi_merges_into_active_config() {
+		let dir = tempfile::tempdir().unwrap();
+		let state = ControlPlaneState {
+			config_path: dir.path().join("config.yaml"),
+			..state()
+		};
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "health"
+    method: "GET"
+    path: "/health"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config.clone());
+		*state.config.write().unwrap() = Some(config);
+
+		let spec = r#"{
+			"openapi": "3.1.0",
+			"info": {"title": "Orders"},
+			"paths": {"/orders/{id}": {"get": {"operationId": "getOrder", "responses": {
+				"200": {"content": {"application/json": {"schema": {"type": "object", "properties": {"id": {"type": "string", "format": "uuid"}}}}}}
+			}}}}
+		}"#;
+		let router = crate::control_plane::server::build_router(state.clone());
+		let import = |uri: &str| {
+			axum::http::Request::builder()
+				.method("POST")
+				.uri(uri)
+				.body(axum::body::Body::from(spec))
+				.unwrap()
+		};
+
+		let response = router.clone().oneshot(import("/api/import/openapi?mode=sideways")).await.unwrap();
+		assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+		let response = router
+			.clone()
+			.oneshot(import("/api/import/openapi?dry_run=true&latency_ms=25"))
+			.await
+			.unwrap();
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+		assert_eq!(body["config"]["endpoints"][1]["latency"]["params"]["delay_ms"], 25.0);
+		assert!(state.registry.get(&crate::config::HttpMethod::Get, "/orders/{id}").is_none());
+
+		let response = router.oneshot(import("/api/import/openapi")).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+		let ids: Vec<_> = state.registry.list().into_iter().map(|resolved| resolved.endpoint.id).collect();
+		assert_eq!(ids, vec!["health", "getorder"]);
+		let engine = crate::engine::build_router(Arc::clone(&state.registry));
+		let request = axum::http::Request::builder()
+			.uri("/orders/42")
+			.body(axum::body::Body::empty())
+			.unwrap();
+		let response = engine.oneshot(request).await.unwrap();
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		assert_eq!(&bytes[..], br#"{"id":"00000000-0000-4000-8000-000000000000"}"#);
+		assert!(dir.path().join("config.yaml").exists());
+	}
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+	async fn test_concurrent_imports_and_updates_keep_every_change() {
+		let dir = tempfile::tempdir().unwrap();
+		let state = ControlPlaneState {
+			config_path: dir.path().join("config.yaml"),
+			..state()
+		};
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "health"
+    method: "GET"
+    path: "/health"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "ok" }
+"#,
+		)
+		.expect("config");
+		state.registry.set_config(config.clone());
+		*state.config.write().unwrap() = Some(config);
+		let router = crate::control_plane::server::build_router(state.clone());
+
+		let mut tasks = Vec::new();
+		for n in 0..8 {
+			let spec = serde_json::json!({
+				"openapi": "3.1.0",
+				"info": {"title": "Imported"},
+				"paths": {format!("/imported/{n}"): {"get": {"operationId": format!("imported-{n}"), "responses": {"200": {}}}}}
+			});
+			let endpoint = serde_json::json!({
+				"id": format!("created-{n}"),
+				"method": "GET",
+				"path": format!("/created/{n}"),
+				"latency": {"distribution": "fixed", "params": {"delay_ms": 0}},
+				"response": {"status": 200, "body": "{}"}
+			});
+			for (uri, body) in [("/api/import/openapi", spec), ("/api/endpoints", endpoint)] {
+				let request = axum::http::Request::builder()
+					.method("POST")
+					.uri(uri)
+					.header("Content-Type", "application/json")
+					.body(axum::body::Body::from(body.to_string()))
+					.unwrap();
+				tasks.push(tokio::spawn(router.clone().oneshot(request)));
+			}
+		}
+		for task in tasks {
+			let response = task.await.unwrap().unwrap();
+			assert!(response.status().is_success(), "{}", response.status());
+		}
+
+		let config = state.config.read().unwrap().clone().unwrap();
+		assert_eq!(config.endpoints.len(), 17);
+		assert_eq!(state.registry.list().len(), 17);
+		let saved = crate::control_plane::load_config(&dir.path().join("config.yaml")).unwrap().expect("saved config");
+		assert_eq!(saved.endpoints.len(), 17);
+	}
+
+	#[tokio::test]
+	async fn test_import_har_replaces_active_config() {
+		let dir = tempfile::tempdir().unwrap();
+		let state = ControlPlaneState {
+			config_path: dir.path().join("config.yaml"),
+			..state()
+		};
+		let entry = |url: &str, status: u16, wait: f64| {
+			json!({
+				"request": {"method": "GET", "url": url},
+				"response": {"status": status, "headers": [], "content": {"text": "{}"}},
+				"timings": {"wait": wait}
+			})
+		};
+		let har = json!({"log": {"entries": [
+			entry("https://shop.example.com/carts/1", 200, 30.0),
+			entry("https://shop.example.com/carts/2", 500, 90.0),
+			entry("https://ads.example.com/pixel", 200, 5.0),
+		]}})
+		.to_string();
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let request = axum::http::Request::builder()
+			.method("POST")
+			.uri("/api/import/har?mode=replace&host=shop.example.com")
+			.body(axum::body::Body::from(har))
+			.unwrap();
+		let response = router.oneshot(request).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+		assert_eq!(body["summary"]["endpoints_count"], 1);
+		assert_eq!(body["warnings"][0], "1 entries for host 'ads.example.com' skipped by the host filter");
+
+		let resolved = state.registry.get(&crate::config::HttpMethod::Get, "/carts/{id}").expect("endpoint");
+		assert_eq!(resolved.endpoint.error_profile.rate, 0.5);
+		assert_eq!(resolved.endpoint.error_profile.codes, vec![500]);
+	}
+
+	#[tokio::test]
+	async fn test_import_access_log_refits_active_latencies() {
+		let orders = crate::config::Endpoint::new(
+			"order".to_string(),
+			crate::config::HttpMethod::Get,
+			"/orders/{orderId}".to_string(),
+			crate::config::LatencyConfig {
+				distribution: crate::config::DistributionType::Fixed,
+				params: crate::config::DistributionParams::Fixed { delay_ms: 10.0 },
+			},
+			crate::config::Response {
+				status: 200,
+				headers: HashMap::new(),
+				body: r#"{"id": 1}"#.to_string(),
+			},
+		);
+		let state = state();
+		*state.config.write().unwrap() =
+			Some(crate::config::Configuration::from_endpoints(crate::config::Metadata::default(), vec![orders]));
+		let log: Vec<String> = [40, 55, 61, 48, 70, 52, 66, 45]
+			.iter()
+			.enumerate()
+			.map(|(index, ms)| format!(r#"{{"method": "GET", "path": "/orders/A-{}", "status": 200, "duration": {}}}"#, index, ms))
+			.collect();
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let request = axum::http::Request::builder()
+			.method("POST")
+			.uri("/api/import/access-log?log_format=json&quantiles=0.5,0.99&dry_run=true")
+			.body(axum::body::Body::from(log.join("\n")))
+			.unwrap();
+		let response = router.oneshot(request).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+		let route = &body["routes"][0];
+		assert_eq!((route["path"].as_str(), route["count"].as_u64()), (Some("/orders/{orderId}"), Some(8)));
+		assert_eq!(route["quantiles"]["p50"], 53.5);
+		assert_eq!(route["candidates"].as_array().map(Vec::len), Some(4));
+		let endpoints = body["config"]["endpoints"].as_array().unwrap();
+		assert_eq!(endpoints.len(), 1);
+		assert_eq!(endpoints[0]["id"], "order");
+		assert_eq!(endpoints[0]["response"]["body"], r#"{"id": 1}"#);
+		assert_ne!(endpoints[0]["latency"]["distribution"], "fixed");
+	}
+
+	#[tokio::test]
+	async fn test_import_wiremock_reports_unsupported_features() {
+		let dir = tempfile::tempdir().unwrap();
+		let state = ControlPlaneState {
+			config_path: dir.path().join("config.yaml"),
+			..state()
+		};
+		let mappings = json!({"mappings": [
+			{
+				"request": {"method": "GET", "urlPath": "/ping", "queryParameters": {"v": {"equalTo": "1"}}},
+				"response": {"status": 200, "body": "pong", "fixedDelayMilliseconds": 15}
+			},
+			{
+				"request": {"method": "GET", "urlPattern": "/(a|b)"},
+				"response": {"status": 200}
+			}
+		]})
+		.to_string();
+
+		let router = crate::control_plane::server::build_router(state.clone());
+		let request = axum::http::Request::builder()
+			.method("POST")
+			.uri("/api/import/wiremock?dry_run=true")
+			.body(axum::body::Body::from(mappings))
+			.unwrap();
+		let response = router.oneshot(request).await.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+		assert_eq!(body["config"]["endpoints"][0]["latency"]["params"]["delay_ms"], 15.0);
+		assert_eq!(body["report"]["converted"], 1);
+		assert_eq!(body["report"]["skipped"], 1);
+		assert_eq!(body["report"]["unsupported"]["request.queryParameters (ignored)"][0], "GET /ping");
+		assert!(state.registry.get(&crate::config::HttpMethod::Get, "/ping").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_export_openapi() {
+		let state = state();
+		let router = crate::control_plane::server::build_router(state.clone());
+		let request = |uri: &str| axum::http::Request::builder().uri(uri).body(axum::body::Body::empty()).unwrap();
+		let response = router.clone().oneshot(request("/api/export/openapi")).await.unwrap();
+		assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+		let config = crate::config::parse_yaml(include_str!("../../examples/02-user-api-basic.yaml")).expect("config");
+		*state.config.write().unwrap() = Some(config);
+		let response = router
+			.oneshot(request("/api/export/openapi?format=yaml&server=http://sim.test:8080"))
+			.await
+			.unwrap();
+		assert_eq!(response.headers()[axum::http::header::CONTENT_TYPE], "application/x-yaml");
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		let document: serde_json::Value = serde_yaml::from_slice(&bytes).unwrap();
+		assert_eq!(document["servers"][0]["url"], "http://sim.test:8080");
+		assert!(document["paths"]["/api/users/me"]["get"]["x-simulant-latency"].is_object());
+	}
 }
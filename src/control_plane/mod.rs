@@ -9,11 +9,8 @@ pub use persistence::*;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_placeholder() {
         // TODO: Phase 1.7 - Add control plane tests
-        assert!(true);
     }
 }
@@ -59,12 +59,29 @@ mod tests {
 				error_profile: ErrorProfile::default(),
 				rate_limit: None,
 				bandwidth_cap: None,
+				capacity: None,
+				concurrency_limit: None,
+				resource: None,
+				workflow: None,
+				job: None,
+				auth: None,
+				required_scopes: vec![],
+				idempotency: None,
+				dependencies: None,
+				callbacks: vec![],
+				proxy: None,
 				loaded_at: None,
 				rate_limiter: None,
 			}],
 			endpoint_groups: vec![],
+			rate_limit: None,
+			auth: None,
+			proxy: None,
 			behavior_windows: vec![],
 			burst_events: vec![],
+			behavior_profiles: vec![],
+			resources: vec![],
+			jobs: vec![],
 			workflows: vec![],
 		}
 	}
@@ -1,9 +1,11 @@
 // Control plane HTTP server (port 8081)
 
 use crate::control_plane::handlers::{
-	create_endpoint, delete_endpoint, export_config, get_endpoint, health, import_config,
-	import_config_multipart, list_endpoints, status, update_endpoint, validate_config,
-	ControlPlaneState,
+	concurrency, create_endpoint, delete_endpoint, export_config, export_openapi_spec, get_endpoint, get_resource,
+	get_workflow, health, import_config, import_access_log, import_config_multipart, import_har_archive, import_openapi_spec, import_postman_collection, import_wiremock_mappings, list_callbacks, list_endpoints, list_jobs, list_resources,
+	list_workflows, recording, clear_recording, reset_resource, reset_runtime, reset_workflow, seed_resource, status,
+	update_endpoint, validate_config,
+	timeline, window_occurrences, ControlPlaneState,
 };
 use crate::engine::EndpointRegistry;
 use axum::routing::{get, get_service, post};
@@ -26,10 +28,29 @@ pub fn build_router(state: ControlPlaneState) -> Router {
 			"/api/endpoints/:id",
 			get(get_endpoint).put(update_endpoint).delete(delete_endpoint),
 		)
+		.route("/api/behavior-windows/occurrences", get(window_occurrences))
+		.route("/api/timeline", get(timeline))
+		.route("/api/concurrency", get(concurrency))
+		.route("/api/runtime/reset", post(reset_runtime))
+		.route("/api/resources", get(list_resources))
+		.route("/api/resources/:id", get(get_resource).put(seed_resource))
+		.route("/api/resources/:id/reset", post(reset_resource))
+		.route("/api/workflows", get(list_workflows))
+		.route("/api/jobs", get(list_jobs))
+		.route("/api/callbacks", get(list_callbacks))
+		.route("/api/recording", get(recording).delete(clear_recording))
+		.route("/api/workflows/:id", get(get_workflow))
+		.route("/api/workflows/:id/reset", post(reset_workflow))
 		.route("/api/config/export", get(export_config))
 		.route("/api/config/validate", post(validate_config))
 		.route("/api/config/import", post(import_config))
 		.route("/api/config/import/multipart", post(import_config_multipart))
+		.route("/api/import/openapi", post(import_openapi_spec))
+		.route("/api/import/har", post(import_har_archive))
+		.route("/api/import/wiremock", post(import_wiremock_mappings))
+		.route("/api/import/postman", post(import_postman_collection))
+		.route("/api/import/access-log", post(import_access_log))
+		.route("/api/export/openapi", get(export_openapi_spec))
 		.nest_service("/static", static_service)
 		.with_state(state)
 }
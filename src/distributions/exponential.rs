@@ -1,6 +1,7 @@
 // Exponential distribution
 
 use super::Distribution;
+use rand::RngCore;
 use rand_distr::Distribution as RandDistribution;
 use rand_distr::Exp;
 use std::time::Duration;
@@ -16,12 +17,19 @@ impl ExponentialDistribution {
 }
 
 impl Distribution for ExponentialDistribution {
-    fn sample(&self) -> Duration {
+    fn sample_with(&self, rng: &mut dyn RngCore) -> Duration {
         let exp = Exp::new(self.rate).expect("rate should be validated before use");
-        let value = exp.sample(&mut rand::thread_rng());
+        let value = exp.sample(rng);
         let clamped = if value.is_finite() && value >= 0.0 { value } else { 0.0 };
         Duration::from_secs_f64(clamped / 1000.0)
     }
+
+    fn cdf(&self, ms: f64) -> f64 {
+        if ms <= 0.0 {
+            return 0.0;
+        }
+        1.0 - (-self.rate * ms).exp()
+    }
 }
 
 #[cfg(test)]
@@ -37,7 +45,7 @@ mod tests {
         let samples = 5000;
 
         for _ in 0..samples {
-            total += dist.sample().as_secs_f64() * 1000.0;
+            total += dist.sample_with(&mut rand::thread_rng()).as_secs_f64() * 1000.0;
         }
 
         let mean = total / samples as f64;
@@ -1,6 +1,7 @@
 // Fixed latency distribution
 
 use super::Distribution;
+use rand::RngCore;
 use std::time::Duration;
 
 pub struct FixedDistribution {
@@ -16,9 +17,13 @@ impl FixedDistribution {
 }
 
 impl Distribution for FixedDistribution {
-    fn sample(&self) -> Duration {
+    fn sample_with(&self, _rng: &mut dyn RngCore) -> Duration {
         self.delay
     }
+
+    fn cdf(&self, ms: f64) -> f64 {
+        if ms >= self.delay.as_secs_f64() * 1000.0 { 1.0 } else { 0.0 }
+    }
 }
 
 #[cfg(test)]
@@ -28,7 +33,7 @@ mod tests {
     #[test]
     fn test_fixed_distribution() {
         let dist = FixedDistribution::new(25.0);
-        let sampled = dist.sample();
+        let sampled = dist.sample_with(&mut rand::thread_rng());
         assert_eq!(sampled, Duration::from_millis(25));
     }
 }
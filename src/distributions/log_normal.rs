@@ -1,6 +1,7 @@
 // Log-normal distribution
 
-use super::Distribution;
+use super::{standard_normal_cdf, Distribution};
+use rand::RngCore;
 use rand_distr::Distribution as RandDistribution;
 use rand_distr::LogNormal;
 use std::time::Duration;
@@ -33,7 +34,7 @@ impl LogNormalDistribution {
 }
 
 impl Distribution for LogNormalDistribution {
-    fn sample(&self) -> Duration {
+    fn sample_with(&self, rng: &mut dyn RngCore) -> Duration {
         let Some((mu, sigma)) = self.mu_sigma() else {
             return Duration::from_secs_f64(0.0);
         };
@@ -43,10 +44,23 @@ impl Distribution for LogNormalDistribution {
         }
 
         let dist = LogNormal::new(mu, sigma).expect("mu and sigma should be valid");
-        let value = dist.sample(&mut rand::thread_rng());
+        let value = dist.sample(rng);
         let clamped = if value.is_finite() && value >= 0.0 { value } else { 0.0 };
         Duration::from_secs_f64(clamped / 1000.0)
     }
+
+    fn cdf(&self, ms: f64) -> f64 {
+        let Some((mu, sigma)) = self.mu_sigma() else {
+            return 1.0;
+        };
+        if ms <= 0.0 {
+            return 0.0;
+        }
+        if sigma == 0.0 {
+            return if ms >= self.mean_ms { 1.0 } else { 0.0 };
+        }
+        standard_normal_cdf((ms.ln() - mu) / sigma)
+    }
 }
 
 #[cfg(test)]
@@ -60,10 +74,10 @@ mod tests {
         let mut total = 0.0;
 
         for _ in 0..samples {
-            total += dist.sample().as_secs_f64() * 1000.0;
+            total += dist.sample_with(&mut rand::thread_rng()).as_secs_f64() * 1000.0;
         }
 
         let mean = total / samples as f64;
-        assert!(mean >= 80.0 && mean <= 120.0, "mean out of range: {}", mean);
+        assert!((80.0..=120.0).contains(&mean), "mean out of range: {}", mean);
     }
 }
@@ -12,12 +12,30 @@ pub use exponential::ExponentialDistribution;
 pub use uniform::UniformDistribution;
 pub use log_normal::LogNormalDistribution;
 
+use rand::RngCore;
 use std::time::Duration;
 
 /// Trait for latency distributions
 pub trait Distribution: Send + Sync {
-    /// Generate a latency duration
-    fn sample(&self) -> Duration;
+    /// Generate a latency duration from the given random source
+    fn sample_with(&self, rng: &mut dyn RngCore) -> Duration;
+
+    /// Probability that a sample is at most `ms` milliseconds
+    fn cdf(&self, ms: f64) -> f64;
+}
+
+/// Standard normal CDF, via the Abramowitz-Stegun approximation of erf
+/// (absolute error below 1.5e-7)
+pub(crate) fn standard_normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
 }
 
 #[cfg(test)]
@@ -27,7 +45,37 @@ mod tests {
     #[test]
     fn test_placeholder() {
         let dist = FixedDistribution::new(1.0);
-        let sampled = dist.sample();
+        let sampled = dist.sample_with(&mut rand::thread_rng());
         assert_eq!(sampled, Duration::from_millis(1));
     }
+
+    #[test]
+    fn test_cdf() {
+        assert!((standard_normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((standard_normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert_eq!(FixedDistribution::new(10.0).cdf(9.9), 0.0);
+        assert_eq!(FixedDistribution::new(10.0).cdf(10.0), 1.0);
+        assert_eq!(UniformDistribution::new(10.0, 30.0).cdf(15.0), 0.25);
+        assert!((ExponentialDistribution::new(0.02).cdf(50.0) - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+        assert!((LogNormalDistribution::new(100.0, 0.0).cdf(100.0) - 1.0).abs() < 1e-12);
+        // The median of a log-normal is below its mean
+        assert!(LogNormalDistribution::new(100.0, 50.0).cdf(100.0) > 0.5);
+        // Negative draws are redrawn, so no mass is left below zero
+        let normal = NormalDistribution::new(10.0, 10.0);
+        assert_eq!(normal.cdf(-1.0), 0.0);
+        // ... which moves the median above the mean: (0.5 - 0.1587) / 0.8413
+        assert!((normal.cdf(10.0) - 0.4057).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_sample_with_seeded_rng_is_reproducible() {
+        use rand::SeedableRng;
+
+        let dist = NormalDistribution::new(100.0, 25.0);
+        let mut first = rand::rngs::StdRng::seed_from_u64(42);
+        let mut second = rand::rngs::StdRng::seed_from_u64(42);
+        for _ in 0..10 {
+            assert_eq!(dist.sample_with(&mut first), dist.sample_with(&mut second));
+        }
+    }
 }
@@ -1,6 +1,7 @@
 // Normal (Gaussian) distribution
 
-use super::Distribution;
+use super::{standard_normal_cdf, Distribution};
+use rand::RngCore;
 use rand_distr::Distribution as RandDistribution;
 use rand_distr::Normal;
 use std::time::Duration;
@@ -17,12 +18,12 @@ impl NormalDistribution {
 }
 
 impl Distribution for NormalDistribution {
-    fn sample(&self) -> Duration {
+    fn sample_with(&self, rng: &mut dyn RngCore) -> Duration {
         let normal = Normal::new(self.mean_ms, self.stddev_ms)
             .expect("mean and stddev should be validated before use");
 
         for _ in 0..10 {
-            let value = normal.sample(&mut rand::thread_rng());
+            let value = normal.sample(rng);
             if value.is_finite() && value >= 0.0 {
                 return Duration::from_secs_f64(value / 1000.0);
             }
@@ -30,6 +31,19 @@ impl Distribution for NormalDistribution {
 
         Duration::from_secs_f64(0.0)
     }
+
+    /// Truncated at zero, matching the sampler's redraws of negative values
+    fn cdf(&self, ms: f64) -> f64 {
+        if ms < 0.0 {
+            return 0.0;
+        }
+        if self.stddev_ms <= 0.0 {
+            return if ms >= self.mean_ms { 1.0 } else { 0.0 };
+        }
+        let below_zero = standard_normal_cdf(-self.mean_ms / self.stddev_ms);
+        let value = standard_normal_cdf((ms - self.mean_ms) / self.stddev_ms);
+        ((value - below_zero) / (1.0 - below_zero)).clamp(0.0, 1.0)
+    }
 }
 
 #[cfg(test)]
@@ -42,7 +56,7 @@ mod tests {
         let mut samples = Vec::with_capacity(5000);
 
         for _ in 0..5000 {
-            let ms = dist.sample().as_secs_f64() * 1000.0;
+            let ms = dist.sample_with(&mut rand::thread_rng()).as_secs_f64() * 1000.0;
             samples.push(ms);
         }
 
@@ -54,7 +68,7 @@ mod tests {
             / samples.len() as f64;
         let stddev = variance.sqrt();
 
-        assert!(mean >= 42.5 && mean <= 57.5, "mean out of range: {}", mean);
-        assert!(stddev >= 7.0 && stddev <= 13.5, "stddev out of range: {}", stddev);
+        assert!((42.5..=57.5).contains(&mean), "mean out of range: {}", mean);
+        assert!((7.0..=13.5).contains(&stddev), "stddev out of range: {}", stddev);
     }
 }
@@ -1,7 +1,7 @@
 // Uniform distribution
 
 use super::Distribution;
-use rand::Rng;
+use rand::{Rng, RngCore};
 use std::time::Duration;
 
 pub struct UniformDistribution {
@@ -16,10 +16,17 @@ impl UniformDistribution {
 }
 
 impl Distribution for UniformDistribution {
-    fn sample(&self) -> Duration {
-        let value = rand::thread_rng().gen_range(self.min_ms..self.max_ms);
+    fn sample_with(&self, rng: &mut dyn RngCore) -> Duration {
+        let value = rng.gen_range(self.min_ms..self.max_ms);
         Duration::from_secs_f64(value / 1000.0)
     }
+
+    fn cdf(&self, ms: f64) -> f64 {
+        if self.max_ms <= self.min_ms {
+            return if ms >= self.min_ms { 1.0 } else { 0.0 };
+        }
+        ((ms - self.min_ms) / (self.max_ms - self.min_ms)).clamp(0.0, 1.0)
+    }
 }
 
 #[cfg(test)]
@@ -35,7 +42,7 @@ mod tests {
         let mut max = f64::MIN;
 
         for _ in 0..samples {
-            let value = dist.sample().as_secs_f64() * 1000.0;
+            let value = dist.sample_with(&mut rand::thread_rng()).as_secs_f64() * 1000.0;
             total += value;
             if value < min {
                 min = value;
@@ -48,6 +55,6 @@ mod tests {
         let mean = total / samples as f64;
         assert!(min >= 10.0, "min below range: {}", min);
         assert!(max < 30.0, "max above range: {}", max);
-        assert!(mean >= 18.0 && mean <= 22.0, "mean out of range: {}", mean);
+        assert!((18.0..=22.0).contains(&mean), "mean out of range: {}", mean);
     }
 }
@@ -0,0 +1,390 @@
+// Simulated OAuth2
+//
+// Token issuance for the client credentials and password grants, and bearer
+// token checks for protected endpoints
+
+use crate::config::{AuthConfig, TokenFormat};
+use crate::engine::RequestContext;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Issues and checks tokens for one auth configuration.
+#[derive(Debug)]
+pub struct Authenticator {
+	config: AuthConfig,
+	/// Opaque tokens by value.
+	issued: HashMap<String, Grant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Grant {
+	subject: String,
+	scopes: Vec<String>,
+	expires_at: u64,
+}
+
+/// Why a protected request was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailure {
+	Missing,
+	Invalid,
+	Expired,
+	InsufficientScope,
+}
+
+impl AuthFailure {
+	pub fn status(self) -> u16 {
+		match self {
+			Self::InsufficientScope => 403,
+			_ => 401,
+		}
+	}
+
+	/// `WWW-Authenticate` challenge per RFC 6750.
+	pub fn challenge(self) -> String {
+		match self {
+			Self::Missing => "Bearer".to_string(),
+			Self::Invalid => r#"Bearer error="invalid_token", error_description="Token is not valid""#.to_string(),
+			Self::Expired => r#"Bearer error="invalid_token", error_description="Token has expired""#.to_string(),
+			Self::InsufficientScope => r#"Bearer error="insufficient_scope""#.to_string(),
+		}
+	}
+}
+
+/// Token endpoint reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenResponse {
+	pub status: u16,
+	pub headers: HashMap<String, String>,
+	pub body: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TokenRequest {
+	grant_type: Option<String>,
+	client_id: Option<String>,
+	client_secret: Option<String>,
+	username: Option<String>,
+	password: Option<String>,
+	scope: Option<String>,
+}
+
+impl Authenticator {
+	pub fn new(config: AuthConfig) -> Self {
+		Self {
+			config,
+			issued: HashMap::new(),
+		}
+	}
+
+	pub fn config(&self) -> &AuthConfig {
+		&self.config
+	}
+
+	/// Handle a token request body (form or JSON) and its client credentials.
+	pub fn issue(&mut self, request: &RequestContext) -> TokenResponse {
+		self.issue_at(request, unix_now())
+	}
+
+	fn issue_at(&mut self, request: &RequestContext, now: u64) -> TokenResponse {
+		let is_json = request
+			.header("content-type")
+			.is_some_and(|content_type| content_type.contains("json"));
+		let parsed = if is_json {
+			serde_json::from_str::<TokenRequest>(&request.body).ok()
+		} else {
+			serde_urlencoded::from_str::<TokenRequest>(&request.body).ok()
+		};
+		let Some(mut token_request) = parsed else {
+			return token_error(400, "invalid_request", "Malformed token request");
+		};
+		if let Some((client_id, client_secret)) = basic_credentials(request) {
+			token_request.client_id = Some(client_id);
+			token_request.client_secret = Some(client_secret);
+		}
+
+		let client = match (&token_request.client_id, &token_request.client_secret) {
+			(Some(id), secret) => {
+				let client = self.config.clients.iter().find(|client| {
+					client.client_id == *id && secret.as_deref() == Some(client.client_secret.as_str())
+				});
+				match client {
+					Some(client) => Some(client),
+					None => return token_error(401, "invalid_client", "Unknown client or wrong secret"),
+				}
+			}
+			(None, _) => None,
+		};
+
+		let (subject, allowed) = match token_request.grant_type.as_deref() {
+			Some("client_credentials") => match client {
+				Some(client) => (client.client_id.clone(), &client.scopes),
+				None => return token_error(401, "invalid_client", "Client authentication required"),
+			},
+			Some("password") => {
+				let user = self.config.users.iter().find(|user| {
+					Some(user.username.as_str()) == token_request.username.as_deref()
+						&& Some(user.password.as_str()) == token_request.password.as_deref()
+				});
+				match user {
+					Some(user) => (user.username.clone(), &user.scopes),
+					None => return token_error(400, "invalid_grant", "Wrong username or password"),
+				}
+			}
+			Some(_) => return token_error(400, "unsupported_grant_type", "Only client_credentials and password are supported"),
+			None => return token_error(400, "invalid_request", "Missing grant_type"),
+		};
+
+		let scopes = match token_request.scope.as_deref().map(str::split_whitespace) {
+			Some(requested) => {
+				let requested: Vec<String> = requested.map(str::to_string).collect();
+				if requested.iter().any(|scope| !allowed.contains(scope)) {
+					return token_error(400, "invalid_scope", "Requested scope is not allowed");
+				}
+				requested
+			}
+			None => allowed.clone(),
+		};
+
+		let grant = Grant {
+			subject,
+			scopes,
+			expires_at: now + self.config.expires_in_secs,
+		};
+		let access_token = match self.config.token_format {
+			TokenFormat::Opaque => {
+				self.issued.retain(|_, issued| issued.expires_at > now);
+				let token: String = rand::thread_rng()
+					.sample_iter(&Alphanumeric)
+					.take(40)
+					.map(char::from)
+					.collect();
+				self.issued.insert(token.clone(), grant.clone());
+				token
+			}
+			TokenFormat::Jwt => self.sign(&grant, now),
+		};
+
+		TokenResponse {
+			status: 200,
+			headers: HashMap::from([
+				("Content-Type".to_string(), "application/json".to_string()),
+				("Cache-Control".to_string(), "no-store".to_string()),
+			]),
+			body: json!({
+				"access_token": access_token,
+				"token_type": "Bearer",
+				"expires_in": self.config.expires_in_secs,
+				"scope": grant.scopes.join(" ")
+			})
+			.to_string(),
+		}
+	}
+
+	/// Check the request's bearer token against the required scopes.
+	pub fn authorize(&self, request: &RequestContext, required_scopes: &[String]) -> Result<(), AuthFailure> {
+		self.authorize_at(request, required_scopes, unix_now())
+	}
+
+	fn authorize_at(&self, request: &RequestContext, required_scopes: &[String], now: u64) -> Result<(), AuthFailure> {
+		let token = request
+			.header("authorization")
+			.and_then(|value| value.strip_prefix("Bearer "))
+			.map(str::trim)
+			.ok_or(AuthFailure::Missing)?;
+
+		let grant = match self.config.token_format {
+			TokenFormat::Opaque => self.issued.get(token).cloned().ok_or(AuthFailure::Invalid)?,
+			TokenFormat::Jwt => self.verify(token)?,
+		};
+		if grant.expires_at <= now {
+			return Err(AuthFailure::Expired);
+		}
+		if required_scopes.iter().any(|scope| !grant.scopes.contains(scope)) {
+			return Err(AuthFailure::InsufficientScope);
+		}
+		Ok(())
+	}
+
+	fn mac(&self) -> HmacSha256 {
+		let secret = self.config.signing_secret.as_deref().unwrap_or_default();
+		HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length")
+	}
+
+	fn sign(&self, grant: &Grant, now: u64) -> String {
+		let header = URL_SAFE_NO_PAD.encode(json!({ "alg": "HS256", "typ": "JWT" }).to_string());
+		let claims = URL_SAFE_NO_PAD.encode(
+			json!({
+				"iss": self.config.issuer,
+				"sub": grant.subject,
+				"iat": now,
+				"exp": grant.expires_at,
+				"scope": grant.scopes.join(" ")
+			})
+			.to_string(),
+		);
+		let mut mac = self.mac();
+		mac.update(format!("{}.{}", header, claims).as_bytes());
+		let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+		format!("{}.{}.{}", header, claims, signature)
+	}
+
+	fn verify(&self, token: &str) -> Result<Grant, AuthFailure> {
+		let mut parts = token.split('.');
+		let (Some(header), Some(claims), Some(signature), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+		else {
+			return Err(AuthFailure::Invalid);
+		};
+
+		let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| AuthFailure::Invalid)?;
+		let mut mac = self.mac();
+		mac.update(format!("{}.{}", header, claims).as_bytes());
+		mac.verify_slice(&signature).map_err(|_| AuthFailure::Invalid)?;
+
+		let claims: Value = URL_SAFE_NO_PAD
+			.decode(claims)
+			.ok()
+			.and_then(|bytes| serde_json::from_slice(&bytes).ok())
+			.ok_or(AuthFailure::Invalid)?;
+		if claims["iss"].as_str() != Some(self.config.issuer.as_str()) {
+			return Err(AuthFailure::Invalid);
+		}
+		Ok(Grant {
+			subject: claims["sub"].as_str().unwrap_or_default().to_string(),
+			scopes: claims["scope"]
+				.as_str()
+				.unwrap_or_default()
+				.split_whitespace()
+				.map(str::to_string)
+				.collect(),
+			expires_at: claims["exp"].as_u64().ok_or(AuthFailure::Invalid)?,
+		})
+	}
+}
+
+fn basic_credentials(request: &RequestContext) -> Option<(String, String)> {
+	let encoded = request.header("authorization")?.strip_prefix("Basic ")?;
+	let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+	let (id, secret) = decoded.split_once(':')?;
+	Some((id.to_string(), secret.to_string()))
+}
+
+fn token_error(status: u16, error: &str, description: &str) -> TokenResponse {
+	let mut headers = HashMap::from([("Content-Type".to_string(), "application/json".to_string())]);
+	if status == 401 {
+		headers.insert("WWW-Authenticate".to_string(), "Basic".to_string());
+	}
+	TokenResponse {
+		status,
+		headers,
+		body: json!({ "error": error, "error_description": description }).to_string(),
+	}
+}
+
+fn unix_now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|elapsed| elapsed.as_secs())
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{OAuthClient, OAuthUser};
+
+	fn authenticator(token_format: TokenFormat) -> Authenticator {
+		Authenticator::new(AuthConfig {
+			token_format,
+			signing_secret: Some("secret".to_string()),
+			issuer: "web-simulant".to_string(),
+			expires_in_secs: 60,
+			clients: vec![OAuthClient {
+				client_id: "billing".to_string(),
+				client_secret: "s3cret".to_string(),
+				scopes: vec!["orders:read".to_string(), "orders:write".to_string()],
+			}],
+			users: vec![OAuthUser {
+				username: "ada".to_string(),
+				password: "pw".to_string(),
+				scopes: vec!["profile".to_string()],
+			}],
+		})
+	}
+
+	fn token_request(body: &str) -> RequestContext {
+		RequestContext::from_body(body)
+	}
+
+	fn bearer(token: &str) -> RequestContext {
+		let mut request = RequestContext::from_body("");
+		request
+			.headers
+			.insert("Authorization", format!("Bearer {}", token).parse().unwrap());
+		request
+	}
+
+	fn access_token(response: &TokenResponse) -> String {
+		let body: Value = serde_json::from_str(&response.body).unwrap();
+		body["access_token"].as_str().unwrap().to_string()
+	}
+
+	#[test]
+	fn test_client_credentials_grant_and_scopes() {
+		for format in [TokenFormat::Opaque, TokenFormat::Jwt] {
+			let mut auth = authenticator(format);
+			let response = auth.issue_at(
+				&token_request("grant_type=client_credentials&client_id=billing&client_secret=s3cret&scope=orders%3Aread"),
+				1_000,
+			);
+			assert_eq!(response.status, 200);
+			let token = access_token(&response);
+
+			let read = vec!["orders:read".to_string()];
+			let write = vec!["orders:write".to_string()];
+			assert_eq!(auth.authorize_at(&bearer(&token), &read, 1_030), Ok(()));
+			assert_eq!(auth.authorize_at(&bearer(&token), &write, 1_030), Err(AuthFailure::InsufficientScope));
+			assert_eq!(auth.authorize_at(&bearer(&token), &read, 1_060), Err(AuthFailure::Expired));
+			assert_eq!(auth.authorize_at(&bearer("forged"), &read, 1_030), Err(AuthFailure::Invalid));
+			assert_eq!(auth.authorize_at(&token_request(""), &read, 1_030), Err(AuthFailure::Missing));
+		}
+	}
+
+	#[test]
+	fn test_password_grant_with_basic_client_auth() {
+		let mut auth = authenticator(TokenFormat::Jwt);
+		let mut request = token_request(r#"{"grant_type": "password", "username": "ada", "password": "pw"}"#);
+		request.headers.insert("Content-Type", "application/json".parse().unwrap());
+		request
+			.headers
+			.insert("Authorization", format!("Basic {}", STANDARD.encode("billing:s3cret")).parse().unwrap());
+		assert_eq!(auth.issue_at(&request, 0).status, 200);
+
+		let wrong = token_request("grant_type=password&username=ada&password=nope");
+		assert_eq!(auth.issue_at(&wrong, 0).status, 400);
+		let bad_client = token_request("grant_type=client_credentials&client_id=billing&client_secret=x");
+		assert_eq!(auth.issue_at(&bad_client, 0).status, 401);
+		let unsupported = token_request("grant_type=authorization_code");
+		assert!(auth.issue_at(&unsupported, 0).body.contains("unsupported_grant_type"));
+	}
+
+	#[test]
+	fn test_jwt_rejects_tampered_claims() {
+		let mut auth = authenticator(TokenFormat::Jwt);
+		let token = access_token(&auth.issue_at(&token_request("grant_type=password&username=ada&password=pw"), 0));
+		let mut parts: Vec<&str> = token.split('.').collect();
+		let forged = URL_SAFE_NO_PAD.encode(r#"{"iss":"web-simulant","sub":"ada","exp":99999999999,"scope":"admin"}"#);
+		parts[1] = &forged;
+		assert_eq!(auth.authorize_at(&bearer(&parts.join(".")), &[], 0), Err(AuthFailure::Invalid));
+	}
+}
@@ -0,0 +1,279 @@
+// Outbound callbacks
+//
+// Webhooks scheduled by endpoints after they respond, delivered with retries,
+// and the log of every attempt shown by the control plane
+
+use crate::config::{Callback, CallbackRetry, Endpoint};
+use crate::engine::resources::random_uuid;
+use crate::engine::{method_to_string, sample_latency, RequestContext};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Deliveries kept in the log; the oldest are dropped beyond this.
+const MAX_RECORDS: usize = 1000;
+/// Longest wait between attempts, and longest time one attempt may take.
+const MAX_WAIT_MS: f64 = 3_600_000.0;
+
+/// History of scheduled deliveries.
+#[derive(Debug, Default)]
+pub struct CallbackLog {
+	records: VecDeque<CallbackRecord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CallbackRecord {
+	pub id: String,
+	pub endpoint_id: String,
+	pub method: String,
+	pub url: String,
+	pub state: DeliveryState,
+	/// When the first attempt is due.
+	pub scheduled_for: DateTime<Utc>,
+	pub attempts: Vec<DeliveryAttempt>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryState {
+	Pending,
+	Delivered,
+	Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveryAttempt {
+	pub at: DateTime<Utc>,
+	/// Response status; absent when no response arrived.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<u16>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	pub duration_ms: f64,
+}
+
+impl CallbackLog {
+	/// Deliveries, oldest first.
+	pub fn records(&self) -> impl Iterator<Item = &CallbackRecord> {
+		self.records.iter()
+	}
+
+	fn insert(&mut self, record: CallbackRecord) {
+		while self.records.len() >= MAX_RECORDS {
+			self.records.pop_front();
+		}
+		self.records.push_back(record);
+	}
+
+	fn push_attempt(&mut self, id: &str, attempt: DeliveryAttempt, state: DeliveryState) {
+		if let Some(record) = self.records.iter_mut().find(|record| record.id == id) {
+			record.attempts.push(attempt);
+			record.state = state;
+		}
+	}
+}
+
+/// A rendered callback, ready to send.
+struct Delivery {
+	id: String,
+	method: reqwest::Method,
+	url: String,
+	headers: HashMap<String, String>,
+	body: String,
+	retry: CallbackRetry,
+}
+
+/// Render and schedule the endpoint's callbacks for one served request.
+pub fn schedule_callbacks(
+	log: &Arc<Mutex<CallbackLog>>,
+	endpoint: &Endpoint,
+	request: &RequestContext,
+	response_body: &str,
+) {
+	let request_body = serde_json::from_str(&request.body).ok();
+	let response_body = serde_json::from_str(response_body).ok();
+
+	for callback in &endpoint.callbacks {
+		let id = random_uuid();
+		let context = TemplateContext {
+			request,
+			request_body: request_body.as_ref(),
+			response_body: response_body.as_ref(),
+			callback_id: &id,
+		};
+		let delivery = render_callback(callback, &context, id.clone());
+		let delay = sample_latency(&callback.delay);
+
+		log.lock().expect("callback lock").insert(CallbackRecord {
+			id,
+			endpoint_id: endpoint.id.clone(),
+			method: delivery.method.to_string(),
+			url: delivery.url.clone(),
+			state: DeliveryState::Pending,
+			scheduled_for: Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default(),
+			attempts: Vec::new(),
+		});
+		tokio::spawn(deliver(Arc::clone(log), delivery, delay));
+	}
+}
+
+fn render_callback(callback: &Callback, context: &TemplateContext, id: String) -> Delivery {
+	Delivery {
+		id,
+		method: reqwest::Method::from_bytes(method_to_string(&callback.method).as_bytes())
+			.unwrap_or(reqwest::Method::POST),
+		url: render(&callback.url, context),
+		headers: callback
+			.headers
+			.iter()
+			.map(|(name, value)| (name.clone(), render(value, context)))
+			.collect(),
+		body: render(&callback.body, context),
+		retry: callback.retry.clone(),
+	}
+}
+
+/// One client for all deliveries, built on first use.
+fn client() -> &'static reqwest::Client {
+	static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+	CLIENT.get_or_init(reqwest::Client::new)
+}
+
+async fn deliver(log: Arc<Mutex<CallbackLog>>, delivery: Delivery, delay: Duration) {
+	tokio::time::sleep(delay).await;
+
+	let max_attempts = delivery.retry.max_attempts.max(1);
+	let mut backoff_ms = delivery.retry.backoff_ms.clamp(0.0, MAX_WAIT_MS);
+	for attempt in 1..=max_attempts {
+		let at = Utc::now();
+		let started = Instant::now();
+		let mut builder = client()
+			.request(delivery.method.clone(), &delivery.url)
+			.timeout(Duration::from_secs_f64(delivery.retry.timeout_ms.clamp(1.0, MAX_WAIT_MS) / 1000.0))
+			.body(delivery.body.clone());
+		for (name, value) in &delivery.headers {
+			builder = builder.header(name, value);
+		}
+		let (status, error) = match builder.send().await {
+			Ok(response) => (Some(response.status().as_u16()), None),
+			Err(err) => (None, Some(err.to_string())),
+		};
+
+		let delivered = status.is_some_and(|status| (200..300).contains(&status));
+		let state = match (delivered, attempt == max_attempts) {
+			(true, _) => DeliveryState::Delivered,
+			(false, true) => DeliveryState::Failed,
+			(false, false) => DeliveryState::Pending,
+		};
+		let outcome = DeliveryAttempt {
+			at,
+			status,
+			error,
+			duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+		};
+		log.lock().expect("callback lock").push_attempt(&delivery.id, outcome, state);
+		if state != DeliveryState::Pending {
+			return;
+		}
+
+		tokio::time::sleep(Duration::from_secs_f64(backoff_ms / 1000.0)).await;
+		backoff_ms = (backoff_ms * delivery.retry.multiplier.max(1.0)).min(MAX_WAIT_MS);
+	}
+}
+
+/// Values available to `{{...}}` placeholders.
+struct TemplateContext<'a> {
+	request: &'a RequestContext,
+	request_body: Option<&'a Value>,
+	response_body: Option<&'a Value>,
+	callback_id: &'a str,
+}
+
+impl TemplateContext<'_> {
+	/// Value of one placeholder; empty when it names nothing.
+	fn resolve(&self, name: &str) -> String {
+		match name {
+			"callback.id" => return self.callback_id.to_string(),
+			"now" => return Utc::now().to_rfc3339(),
+			"request.body" => return self.request.body.clone(),
+			_ => {}
+		}
+		let (scope, key) = name.split_once('.').and_then(|(scope, rest)| {
+			let (kind, key) = rest.split_once('.')?;
+			Some((format!("{}.{}", scope, kind), key))
+		}).unwrap_or_default();
+
+		match scope.as_str() {
+			"request.body" => lookup(self.request_body, key),
+			"response.body" => lookup(self.response_body, key),
+			"request.path" => self.request.path_params.get(key).cloned().unwrap_or_default(),
+			"request.query" => self.request.query.get(key).cloned().unwrap_or_default(),
+			"request.header" => self.request.header(key).unwrap_or_default().to_string(),
+			_ => String::new(),
+		}
+	}
+}
+
+/// Replace every `{{name}}` in `template`.
+fn render(template: &str, context: &TemplateContext) -> String {
+	let mut rendered = String::with_capacity(template.len());
+	let mut rest = template;
+	while let Some(start) = rest.find("{{") {
+		let Some(end) = rest[start + 2..].find("}}") else {
+			break;
+		};
+		rendered.push_str(&rest[..start]);
+		rendered.push_str(&context.resolve(rest[start + 2..start + 2 + end].trim()));
+		rest = &rest[start + 2 + end + 2..];
+	}
+	rendered.push_str(rest);
+	rendered
+}
+
+/// Follow a dotted path through objects and arrays. Strings are inserted
+/// as-is, anything else as JSON.
+fn lookup(value: Option<&Value>, path: &str) -> String {
+	let found = value.and_then(|value| {
+		path.split('.').try_fold(value, |value, segment| match value {
+			Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+			_ => value.get(segment),
+		})
+	});
+	match found {
+		Some(Value::String(text)) => text.clone(),
+		Some(value) => value.to_string(),
+		None => String::new(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_render_fills_placeholders() {
+		let mut request = RequestContext::from_body(r#"{"callback_url": "http://localhost:9000/hooks", "items": [{"sku": "a-1"}]}"#);
+		request.path_params.insert("id".to_string(), "42".to_string());
+		request.headers.insert("X-Tenant", "acme".parse().unwrap());
+		let request_body = serde_json::from_str(&request.body).ok();
+		let response_body = json!({"id": 7, "amount": {"value": 12.5}});
+		let context = TemplateContext {
+			request: &request,
+			request_body: request_body.as_ref(),
+			response_body: Some(&response_body),
+			callback_id: "cb-1",
+		};
+
+		assert_eq!(render("{{ request.body.callback_url }}", &context), "http://localhost:9000/hooks");
+		assert_eq!(
+			render(r#"{"event": "{{callback.id}}", "order": {{request.path.id}}, "sku": "{{request.body.items.0.sku}}"}"#, &context),
+			r#"{"event": "cb-1", "order": 42, "sku": "a-1"}"#
+		);
+		assert_eq!(render("{{response.body.amount}} {{request.header.x-tenant}}", &context), r#"{"value":12.5} acme"#);
+		assert_eq!(render("[{{response.body.missing}}] {{unclosed", &context), "[] {{unclosed");
+	}
+}
@@ -0,0 +1,298 @@
+// Capacity pools
+//
+// Worker slots with a bounded wait queue, backing both capacity models and
+// concurrency limits; latency can grow with in-flight load
+
+use crate::config::{CapacityModel, ConcurrencyLimit, LoadPoint};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Runtime state for a capacity model or concurrency limit, shared by every
+/// endpoint it covers.
+#[derive(Debug)]
+pub struct CapacityPool {
+	owner: PoolOwner,
+	slots: u32,
+	queue_size: u32,
+	queue_timeout: Option<Duration>,
+	load_curve: Vec<LoadPoint>,
+	rejection_status: u16,
+	rejection_body: String,
+	workers: Arc<Semaphore>,
+	in_flight: AtomicUsize,
+	queued: AtomicUsize,
+	serving: AtomicUsize,
+}
+
+/// Endpoint or group a pool was configured on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolOwner {
+	Endpoint(String),
+	Group(String),
+}
+
+/// Held while a request occupies a worker slot.
+#[derive(Debug)]
+pub struct CapacityPermit {
+	_worker: OwnedSemaphorePermit,
+	_in_flight: Counted,
+	_serving: Counted,
+}
+
+/// Decrements its counter on drop, so cancelled requests are released too.
+#[derive(Debug)]
+struct Counted(Arc<CapacityPool>, fn(&CapacityPool) -> &AtomicUsize);
+
+impl Drop for Counted {
+	fn drop(&mut self) {
+		(self.1)(&self.0).fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+impl Counted {
+	fn enter(pool: &Arc<CapacityPool>, counter: fn(&CapacityPool) -> &AtomicUsize) -> (Self, usize) {
+		let previous = counter(pool).fetch_add(1, Ordering::SeqCst);
+		(Self(Arc::clone(pool), counter), previous)
+	}
+}
+
+impl CapacityPool {
+	pub fn new(owner: PoolOwner, model: CapacityModel) -> Self {
+		Self::with_slots(owner, model.workers, model.queue_size, None, model.load_curve)
+			.rejecting(model.overflow_status, model.overflow_body)
+	}
+
+	pub fn from_limit(owner: PoolOwner, limit: ConcurrencyLimit) -> Self {
+		let queue_timeout = limit
+			.queue_timeout_ms
+			.map(|timeout_ms| Duration::from_secs_f64(timeout_ms.max(0.0) / 1000.0));
+		Self::with_slots(owner, limit.max_in_flight, limit.queue_size, queue_timeout, Vec::new())
+			.rejecting(limit.rejection_status, limit.rejection_body)
+	}
+
+	fn with_slots(
+		owner: PoolOwner,
+		slots: u32,
+		queue_size: u32,
+		queue_timeout: Option<Duration>,
+		load_curve: Vec<LoadPoint>,
+	) -> Self {
+		let slots = slots.max(1);
+		Self {
+			owner,
+			slots,
+			queue_size,
+			queue_timeout,
+			load_curve,
+			rejection_status: 503,
+			rejection_body: String::new(),
+			workers: Arc::new(Semaphore::new(slots as usize)),
+			in_flight: AtomicUsize::new(0),
+			queued: AtomicUsize::new(0),
+			serving: AtomicUsize::new(0),
+		}
+	}
+
+	fn rejecting(mut self, status: u16, body: String) -> Self {
+		self.rejection_status = status;
+		self.rejection_body = body;
+		self
+	}
+
+	pub fn owner(&self) -> &PoolOwner {
+		&self.owner
+	}
+
+	/// Requests served concurrently.
+	pub fn slots(&self) -> u32 {
+		self.slots
+	}
+
+	pub fn queue_size(&self) -> u32 {
+		self.queue_size
+	}
+
+	/// Status and body returned when a request cannot get a slot.
+	pub fn rejection(&self) -> (u16, &str) {
+		(self.rejection_status, &self.rejection_body)
+	}
+
+	/// Requests holding a worker slot or waiting in the queue.
+	pub fn in_flight(&self) -> usize {
+		self.in_flight.load(Ordering::SeqCst)
+	}
+
+	pub fn queued(&self) -> usize {
+		self.queued.load(Ordering::SeqCst)
+	}
+
+	/// Wait for a worker slot; `None` when every worker is busy and the queue
+	/// is full, or the queue timeout expires first.
+	pub async fn acquire(self: &Arc<Self>) -> Option<CapacityPermit> {
+		let (in_flight, _) = Counted::enter(self, |pool| &pool.in_flight);
+
+		if let Ok(worker) = Arc::clone(&self.workers).try_acquire_owned() {
+			return Some(self.permit(worker, in_flight));
+		}
+
+		let (queued, ahead) = Counted::enter(self, |pool| &pool.queued);
+		if ahead >= self.queue_size as usize {
+			// A slot may have been freed since the first attempt.
+			drop(queued);
+			let worker = Arc::clone(&self.workers).try_acquire_owned().ok()?;
+			return Some(self.permit(worker, in_flight));
+		}
+
+		let acquire = Arc::clone(&self.workers).acquire_owned();
+		let worker = match self.queue_timeout {
+			Some(timeout) => tokio::time::timeout(timeout, acquire).await.ok()?.ok()?,
+			None => acquire.await.ok()?,
+		};
+		drop(queued);
+		Some(self.permit(worker, in_flight))
+	}
+
+	fn permit(self: &Arc<Self>, worker: OwnedSemaphorePermit, in_flight: Counted) -> CapacityPermit {
+		let (serving, _) = Counted::enter(self, |pool| &pool.serving);
+		CapacityPermit {
+			_worker: worker,
+			_in_flight: in_flight,
+			_serving: serving,
+		}
+	}
+
+	/// Latency multiplier for the requests holding a worker slot; queued
+	/// requests wait but add no work.
+	pub fn latency_multiplier(&self) -> f64 {
+		load_multiplier(&self.load_curve, self.serving.load(Ordering::SeqCst))
+	}
+}
+
+/// Interpolate the load curve linearly; the end points hold outside its range.
+pub(crate) fn load_multiplier(curve: &[LoadPoint], in_flight: usize) -> f64 {
+	let (Some(first), Some(last)) = (curve.first(), curve.last()) else {
+		return 1.0;
+	};
+
+	let at = in_flight as f64;
+	if at <= first.in_flight as f64 {
+		return first.latency_multiplier;
+	}
+	if at >= last.in_flight as f64 {
+		return last.latency_multiplier;
+	}
+
+	curve
+		.windows(2)
+		.find(|pair| at < pair[1].in_flight as f64)
+		.map(|pair| {
+			let (from, to) = (&pair[0], &pair[1]);
+			let progress = (at - from.in_flight as f64) / (to.in_flight - from.in_flight) as f64;
+			from.latency_multiplier + (to.latency_multiplier - from.latency_multiplier) * progress
+		})
+		.unwrap_or(last.latency_multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn owner() -> PoolOwner {
+		PoolOwner::Endpoint("orders".to_string())
+	}
+
+	fn model(workers: u32, queue_size: u32) -> CapacityModel {
+		CapacityModel {
+			workers,
+			queue_size,
+			load_curve: vec![
+				LoadPoint {
+					in_flight: 1,
+					latency_multiplier: 1.0,
+				},
+				LoadPoint {
+					in_flight: 5,
+					latency_multiplier: 3.0,
+				},
+			],
+			overflow_status: 503,
+			overflow_body: String::new(),
+		}
+	}
+
+	#[test]
+	fn test_load_multiplier_interpolates() {
+		let curve = model(1, 0).load_curve;
+		assert_eq!(load_multiplier(&curve, 0), 1.0);
+		assert_eq!(load_multiplier(&curve, 3), 2.0);
+		assert_eq!(load_multiplier(&curve, 50), 3.0);
+	}
+
+	#[tokio::test]
+	async fn test_acquire_rejects_when_queue_full() {
+		let pool = Arc::new(CapacityPool::new(owner(), model(1, 0)));
+		let held = pool.acquire().await.unwrap();
+		assert_eq!(pool.in_flight(), 1);
+		assert!(pool.acquire().await.is_none());
+		assert_eq!(pool.in_flight(), 1);
+		assert_eq!(pool.queued(), 0);
+		drop(held);
+		assert_eq!(pool.in_flight(), 0);
+	}
+
+	#[tokio::test]
+	async fn test_acquire_waits_in_queue() {
+		let pool = Arc::new(CapacityPool::new(owner(), model(1, 1)));
+		let held = pool.acquire().await.unwrap();
+
+		let waiter = {
+			let pool = Arc::clone(&pool);
+			tokio::spawn(async move { pool.acquire().await.is_some() })
+		};
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		assert_eq!(pool.queued(), 1);
+		assert!(pool.acquire().await.is_none());
+
+		drop(held);
+		assert!(waiter.await.unwrap());
+		assert_eq!(pool.queued(), 0);
+		assert_eq!(pool.in_flight(), 0);
+	}
+
+	#[tokio::test]
+	async fn test_latency_multiplier_ignores_queued_requests() {
+		let pool = Arc::new(CapacityPool::new(owner(), model(1, 4)));
+		let held = pool.acquire().await.unwrap();
+		let waiters: Vec<_> = (0..4)
+			.map(|_| {
+				let pool = Arc::clone(&pool);
+				tokio::spawn(async move { pool.acquire().await.is_some() })
+			})
+			.collect();
+		tokio::time::sleep(Duration::from_millis(20)).await;
+
+		assert_eq!(pool.in_flight(), 5);
+		assert_eq!(pool.latency_multiplier(), 1.0);
+		drop(held);
+		for waiter in waiters {
+			assert!(waiter.await.unwrap());
+		}
+	}
+
+	#[tokio::test]
+	async fn test_acquire_queue_timeout_rejects() {
+		let pool = Arc::new(CapacityPool::from_limit(owner(), ConcurrencyLimit {
+			max_in_flight: 1,
+			queue_size: 5,
+			queue_timeout_ms: Some(20.0),
+			rejection_status: 429,
+			rejection_body: "pool exhausted".to_string(),
+		}));
+		let _held = pool.acquire().await.unwrap();
+		assert!(pool.acquire().await.is_none());
+		assert_eq!(pool.queued(), 0);
+		assert_eq!(pool.rejection(), (429, "pool exhausted"));
+	}
+}
@@ -1,65 +1,366 @@
 // Endpoint request handler
 
 use crate::config::{
-	BandwidthCap, BehaviorSchedule, BehaviorWindow, BodyMatchType, BurstEvent, DistributionParams,
-	DistributionType, Endpoint, ErrorMix, ErrorProfile, LatencyConfig, MixtureComponent,
-	RampConfig, RampCurve, RequestMatch, ScheduleMode,
+	BandwidthCap, BehaviorProfile, BehaviorSchedule, BehaviorWindow, BodyMatchType, BurstArrival,
+	BurstEvent, Dependencies, DependencyMode, DistributionParams, DistributionType, Endpoint, EndpointAuth, ErrorMix, ErrorProfile, Keyframe,
+	LatencyConfig, MixtureComponent, RampConfig, RampCurve, RateDecision, RateLimiter, RequestMatch, ScheduleMode,
+	FALLTHROUGH_ENDPOINT_ID,
 };
 use crate::distributions::{
 	Distribution, ExponentialDistribution, FixedDistribution, LogNormalDistribution, NormalDistribution,
 	UniformDistribution,
 };
 use crate::engine::response::{build_plain_text, build_response};
-use crate::engine::{EndpointBehaviors, ResolvedEndpoint};
+use crate::engine::{
+	apply_job, apply_operation, forward, schedule_callbacks, Attempt, AuthFailure, EndpointBehaviors, RequestContext, ResolvedEndpoint,
+	KeyInFlight, StoredResponse,
+};
+use axum::body::Bytes;
 use axum::response::Response;
-use rand::Rng;
+use chrono::{DateTime, Timelike, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_distr::{Distribution as RandDistribution, Poisson};
+use std::borrow::Cow;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::future::Future;
 use std::hash::{Hash, Hasher};
-use std::time::Duration;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
 
-pub async fn handle_request(resolved: &ResolvedEndpoint, request_body: &str) -> Response {
+pub async fn handle_request(resolved: &ResolvedEndpoint, request: &RequestContext) -> Response {
 	let endpoint = &resolved.endpoint;
 	let behaviors = &resolved.behaviors;
-	if !request_matches(request_body, endpoint.request.as_ref()) {
+	if !request_matches(&request.body, endpoint.request.as_ref()) {
 		return build_plain_text(400, "Request body did not match");
 	}
-
-	if !check_rate_limit(endpoint) {
-		return build_plain_text(429, "Rate limit exceeded");
+	if let (Some(EndpointAuth::Required), Some(auth)) = (endpoint.auth, &resolved.auth) {
+		let authorized = auth.lock().expect("auth lock").authorize(request, &endpoint.required_scopes);
+		if let Err(failure) = authorized {
+			return auth_failure_response(failure);
+		}
 	}
 
 	let elapsed_ms = elapsed_ms(endpoint);
-	let delay = sample_latency_with_behaviors(endpoint, behaviors, elapsed_ms);
+	let rate_limit_factor = active_profiles(behaviors, elapsed_ms)
+		.iter()
+		.map(|(_, values)| values.rate_limit_factor)
+		.product();
+	if let Some(response) = check_rate_limit(resolved, request, rate_limit_factor) {
+		return response;
+	}
+
+	let mut permits = Vec::with_capacity(resolved.concurrency.len() + resolved.capacity.len());
+	for pool in resolved.concurrency.iter().chain(&resolved.capacity) {
+		match pool.acquire().await {
+			Some(permit) => permits.push(permit),
+			None => {
+				let (status, body) = pool.rejection();
+				return build_plain_text(status, body);
+			}
+		}
+	}
+	let load_multiplier: f64 = resolved
+		.capacity
+		.iter()
+		.map(|pool| pool.latency_multiplier())
+		.product();
+
+	let idempotency = match (&endpoint.idempotency, &resolved.idempotency) {
+		(Some(config), Some(store)) => request.header(&config.header).map(|key| (config, store, key)),
+		_ => None,
+	};
+	// The key is claimed before the simulated work, so a concurrent retry
+	// with the same key finds it in flight.
+	let attempt = idempotency.map(|(_, store, key)| store.lock().expect("idempotency lock").begin(key, &request.body));
+	let _in_flight = idempotency
+		.filter(|_| attempt == Some(Attempt::Proceed))
+		.map(|(_, store, key)| KeyInFlight::new(store, key));
+
+	if let (Some((config, _, _)), Some(attempt)) = (idempotency, attempt) {
+		let json_error = |status: u16, message: String| {
+			let headers = HashMap::from([("Content-Type".to_string(), "application/json".to_string())]);
+			build_response(status, &headers, serde_json::json!({ "error": message }).to_string())
+		};
+		let answered = match attempt {
+			Attempt::Proceed => None,
+			Attempt::Fail(_) => config
+				.fail_attempts
+				.as_ref()
+				.map(|failure| build_response(failure.status, &failure.headers, &failure.body)),
+			Attempt::Replay(stored) => {
+				let mut headers = stored.headers;
+				headers.insert("Idempotent-Replayed".to_string(), "true".to_string());
+				Some(build_response(stored.status, &headers, &stored.body))
+			}
+			Attempt::Mismatch => Some(json_error(
+				422,
+				format!("{} was reused with a different request body", config.header),
+			)),
+			Attempt::InFlight => Some(json_error(
+				409,
+				format!("a request with this {} is still being processed", config.header),
+			)),
+		};
+		// Answered from the key alone: the slots are given back before the
+		// latency, which the load does not stretch.
+		if let Some(response) = answered {
+			drop(permits);
+			let delay = sample_latency_with_behaviors(endpoint, behaviors, elapsed_ms);
+			if delay > Duration::from_millis(0) {
+				tokio::time::sleep(delay).await;
+			}
+			return response;
+		}
+	}
+
+	let delay = sample_latency_with_behaviors(endpoint, behaviors, elapsed_ms).mul_f64(load_multiplier);
 	if delay > Duration::from_millis(0) {
 		tokio::time::sleep(delay).await;
 	}
 
+	if let Some(dependencies) = &endpoint.dependencies {
+		if let Some(response) = call_dependencies(resolved, dependencies, request).await {
+			return response;
+		}
+	}
+
 	let mut status = endpoint.response.status;
-	let mut body = endpoint.response.body.as_str();
+	let mut body = Bytes::from(endpoint.response.body.clone());
+	let mut headers = Cow::Borrowed(&endpoint.response.headers);
 
 	let error_profile = effective_error_profile(endpoint, behaviors, elapsed_ms);
-	if should_error(&error_profile) {
-		if error_profile.error_in_payload {
-			if !error_profile.body.is_empty() {
-				body = error_profile.body.as_str();
+	let injected_error = should_error(&error_profile);
+	if injected_error && !error_profile.error_in_payload {
+		status = pick_error_status(&error_profile);
+		body = error_profile.body.clone().into();
+	} else {
+		// Injected status errors skip state changes, so failed calls leave no trace.
+		if let Some(target) = &resolved.proxy {
+			let started = Instant::now();
+			let upstream = forward(target, &endpoint.method, request).await;
+			if let Some(recording) = &resolved.recording {
+				let method = request.method.as_ref().unwrap_or(&endpoint.method);
+				// Endpoints record under their own route; the catch-all under the request path.
+				let path = if endpoint.id == FALLTHROUGH_ENDPOINT_ID { &request.path } else { &endpoint.path };
+				let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+				recording
+					.lock()
+					.expect("recording lock")
+					.record(&target.upstream, method, path, &upstream, latency_ms);
 			}
-		} else {
-			status = pick_error_status(&error_profile);
-			body = error_profile.body.as_str();
+			status = upstream.status;
+			body = upstream.body;
+			headers = Cow::Owned(upstream.headers);
+		}
+		if let (Some(binding), Some(state)) = (&endpoint.workflow, &resolved.workflow) {
+			let mut state = state.lock().expect("workflow lock");
+			let session = state.workflow().session.as_ref().and_then(|session| request.session_key(session));
+			let response = state
+				.advance(binding, session.as_deref())
+				.and_then(|variant| variant.response.as_ref());
+			if let Some(response) = response {
+				status = response.status;
+				body = response.body.clone().into();
+				headers = Cow::Borrowed(&response.headers);
+			}
+		}
+		if let (Some(EndpointAuth::TokenEndpoint), Some(auth)) = (endpoint.auth, &resolved.auth) {
+			let issued = auth.lock().expect("auth lock").issue(request);
+			status = issued.status;
+			body = issued.body.into();
+			headers.to_mut().extend(issued.headers);
+		}
+		if let (Some(binding), Some(queue)) = (&endpoint.job, &resolved.job) {
+			let outcome = {
+				let mut queue = queue.lock().expect("job lock");
+				apply_job(&mut queue, endpoint, binding, request)
+			};
+			status = outcome.status;
+			body = outcome.body.into();
+			headers.to_mut().extend(outcome.headers);
+		}
+		if let (Some(binding), Some(store)) = (&endpoint.resource, &resolved.resource) {
+			let outcome = {
+				let mut store = store.lock().expect("resource lock");
+				apply_operation(&mut store, endpoint, binding, request)
+			};
+			status = outcome.status;
+			body = outcome.body.into();
+			if let Some(location) = outcome.location {
+				headers.to_mut().insert("Location".to_string(), location);
+			}
+		}
+		if injected_error && !error_profile.body.is_empty() {
+			body = error_profile.body.clone().into();
 		}
 	}
 
-	let final_body = apply_payload_corruption(body, &error_profile);
+	let final_body = apply_payload_corruption(&body, &error_profile);
 
 	let bandwidth_delay = compute_bandwidth_delay(&final_body, endpoint.bandwidth_cap.as_ref());
 	if bandwidth_delay > Duration::from_millis(0) {
 		tokio::time::sleep(bandwidth_delay).await;
 	}
 
-	build_response(status, &endpoint.response.headers, &final_body)
+	if let Some(log) = resolved.callbacks.as_ref().filter(|_| (200..300).contains(&status)) {
+		schedule_callbacks(log, endpoint, request, &String::from_utf8_lossy(&final_body));
+	}
+	if let Some((_, store, key)) = idempotency {
+		store.lock().expect("idempotency lock").record(key, StoredResponse {
+			status,
+			headers: headers.as_ref().clone(),
+			body: final_body.clone(),
+		});
+	}
+
+	build_response(status, &headers, &final_body)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DependencyResult {
+	Status(u16),
+	TimedOut,
+}
+
+impl DependencyResult {
+	fn failed(self) -> bool {
+		!matches!(self, Self::Status(status) if status < 400)
+	}
+}
+
+/// Call the endpoint's dependencies; `Some` is the response to send instead
+/// of the endpoint's own: a propagated failure, or the first fallback.
+async fn call_dependencies(
+	resolved: &ResolvedEndpoint,
+	dependencies: &Dependencies,
+	request: &RequestContext,
+) -> Option<Response> {
+	let calls: Vec<_> = dependencies
+		.calls
+		.iter()
+		.filter_map(|call| {
+			let dependency = resolved
+				.dependencies
+				.iter()
+				.find(|dependency| dependency.endpoint.id == call.endpoint_id)?;
+			// Validation ensures the caller's path has every parameter the
+			// dependency's path names.
+			let mut forwarded = request.clone();
+			forwarded.method = Some(dependency.endpoint.method.clone());
+			forwarded.path_params = dependency
+				.endpoint
+				.path_params()
+				.into_iter()
+				.filter_map(|name| Some((name.to_string(), request.path_params.get(name)?.clone())))
+				.collect();
+			forwarded.path = dependency
+				.endpoint
+				.path
+				.split('/')
+				.map(|segment| {
+					segment
+						.strip_prefix('{')
+						.and_then(|name| name.strip_suffix('}'))
+						.and_then(|name| forwarded.path_params.get(name))
+						.map_or(segment, String::as_str)
+				})
+				.collect::<Vec<_>>()
+				.join("/");
+			Some((call, call_dependency(dependency.clone(), forwarded, call.timeout_ms)))
+		})
+		.collect();
+
+	let mut results = Vec::with_capacity(calls.len());
+	match dependencies.mode {
+		DependencyMode::Sequential => {
+			for (call, pending) in calls {
+				let result = pending.await;
+				results.push((call, result));
+				if result.failed() && call.fallback.is_none() {
+					break;
+				}
+			}
+		}
+		DependencyMode::Parallel => {
+			let (called, pending): (Vec<_>, Vec<_>) = calls.into_iter().unzip();
+			let mut tasks = JoinSet::new();
+			for (index, pending) in pending.into_iter().enumerate() {
+				tasks.spawn(async move { (index, pending.await) });
+			}
+			let mut finished = vec![DependencyResult::TimedOut; called.len()];
+			while let Some(joined) = tasks.join_next().await {
+				if let Ok((index, result)) = joined {
+					finished[index] = result;
+				}
+			}
+			results.extend(called.into_iter().zip(finished));
+		}
+	}
+
+	let mut fallback = None;
+	for (call, result) in results {
+		if !result.failed() {
+			continue;
+		}
+		match &call.fallback {
+			Some(response) => {
+				fallback.get_or_insert(response);
+			}
+			None => {
+				let (status, reason) = match result {
+					DependencyResult::TimedOut => (dependencies.timeout_status, "timed out".to_string()),
+					DependencyResult::Status(status) => (dependencies.error_status, format!("failed with {}", status)),
+				};
+				let body = dependencies.error_body.clone().unwrap_or_else(|| {
+					serde_json::json!({ "error": format!("dependency '{}' {}", call.endpoint_id, reason) }).to_string()
+				});
+				let headers = HashMap::from([("Content-Type".to_string(), "application/json".to_string())]);
+				return Some(build_response(status, &headers, &body));
+			}
+		}
+	}
+	fallback.map(|response| build_response(response.status, &response.headers, &response.body))
 }
 
-fn elapsed_ms(endpoint: &Endpoint) -> f64 {
+/// Boxed so endpoints can call endpoints that have dependencies of their own.
+fn call_dependency(
+	dependency: ResolvedEndpoint,
+	request: RequestContext,
+	timeout_ms: Option<f64>,
+) -> Pin<Box<dyn Future<Output = DependencyResult> + Send>> {
+	Box::pin(async move {
+		let call = handle_request(&dependency, &request);
+		let response = match timeout_ms {
+			Some(timeout_ms) => {
+				let limit = Duration::from_secs_f64(timeout_ms.max(0.0) / 1000.0);
+				match tokio::time::timeout(limit, call).await {
+					Ok(response) => response,
+					Err(_) => return DependencyResult::TimedOut,
+				}
+			}
+			None => call.await,
+		};
+		DependencyResult::Status(response.status().as_u16())
+	})
+}
+
+fn auth_failure_response(failure: AuthFailure) -> Response {
+	let error = match failure {
+		AuthFailure::InsufficientScope => "insufficient_scope",
+		AuthFailure::Missing => "unauthorized",
+		AuthFailure::Invalid | AuthFailure::Expired => "invalid_token",
+	};
+	let headers = HashMap::from([
+		("Content-Type".to_string(), "application/json".to_string()),
+		("WWW-Authenticate".to_string(), failure.challenge()),
+	]);
+	build_response(failure.status(), &headers, serde_json::json!({ "error": error }).to_string())
+}
+
+pub(crate) fn elapsed_ms(endpoint: &Endpoint) -> f64 {
 	endpoint
 		.loaded_at
 		.map(|loaded_at| loaded_at.elapsed().as_millis() as f64)
@@ -70,21 +371,39 @@ fn sample_latency_with_behaviors(
 	endpoint: &Endpoint,
 	behaviors: &EndpointBehaviors,
 	elapsed_ms: f64,
+) -> Duration {
+	let latency_multiplier: f64 = active_profiles(behaviors, elapsed_ms)
+		.iter()
+		.map(|(_, values)| values.latency_multiplier)
+		.product();
+	sample_unscaled_latency(endpoint, behaviors, elapsed_ms).mul_f64(latency_multiplier.max(0.0))
+}
+
+fn sample_unscaled_latency(
+	endpoint: &Endpoint,
+	behaviors: &EndpointBehaviors,
+	elapsed_ms: f64,
 ) -> Duration {
 	let mut base_sample = sample_latency(&endpoint.latency);
 
-	if let Some((window, factor)) = active_window(behaviors, elapsed_ms) {
-		if let Some(latency_override) = window.latency_override.as_ref() {
-			base_sample = blend_latency(&endpoint.latency, latency_override, factor);
-		}
+	let windows = active_windows(behaviors, elapsed_ms);
+	let window_override = windows
+		.iter()
+		.rev()
+		.find_map(|(window, factor)| window.latency_override.as_ref().map(|latency| (latency, *factor)));
+	if let Some((latency_override, factor)) = window_override {
+		base_sample = blend_latency(&endpoint.latency, latency_override, factor);
 	}
 
-	if let Some((burst, factor)) = active_burst(behaviors, elapsed_ms) {
-		if let Some(latency_override) = burst.latency_spike.as_ref() {
-			let roll: f64 = rand::thread_rng().gen();
-			if factor >= 1.0 || roll < factor {
-				return sample_latency(latency_override);
-			}
+	let bursts = active_bursts(behaviors, elapsed_ms);
+	let burst_spike = bursts
+		.iter()
+		.rev()
+		.find_map(|(burst, factor)| burst.latency_spike.as_ref().map(|latency| (latency, *factor)));
+	if let Some((latency_override, factor)) = burst_spike {
+		let roll: f64 = rand::thread_rng().gen();
+		if factor >= 1.0 || roll < factor {
+			return sample_latency(latency_override);
 		}
 	}
 
@@ -106,20 +425,32 @@ fn blend_latency(base: &LatencyConfig, override_latency: &LatencyConfig, factor:
 	}
 }
 
-fn effective_error_profile(
+pub(crate) fn effective_error_profile(
 	endpoint: &Endpoint,
 	behaviors: &EndpointBehaviors,
 	elapsed_ms: f64,
 ) -> ErrorProfile {
 	let mut profile = endpoint.error_profile.clone();
 
-	if let Some((window, factor)) = active_window(behaviors, elapsed_ms) {
+	for (behavior_profile, values) in active_profiles(behaviors, elapsed_ms) {
+		if let Some(rate) = values.error_rate {
+			let override_profile = ErrorProfile {
+				rate,
+				codes: behavior_profile.error_codes.clone(),
+				body: behavior_profile.error_body.clone(),
+				..ErrorProfile::default()
+			};
+			profile = merge_error_profiles(&profile, &override_profile, behavior_profile.error_mix.clone(), 1.0);
+		}
+	}
+
+	for (window, factor) in active_windows(behaviors, elapsed_ms) {
 		if let Some(override_profile) = window.error_profile_override.as_ref() {
 			profile = merge_error_profiles(&profile, override_profile, window.error_mix.clone(), factor);
 		}
 	}
 
-	if let Some((burst, factor)) = active_burst(behaviors, elapsed_ms) {
+	for (burst, factor) in active_bursts(behaviors, elapsed_ms) {
 		if let Some(error_spike) = burst.error_spike.as_ref() {
 			profile = merge_error_profiles(
 				&profile,
@@ -184,40 +515,135 @@ fn merge_error_profiles(
 	}
 }
 
-fn active_window(
+/// Interpolated keyframe values of a behavior profile at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileValues {
+	pub latency_multiplier: f64,
+	pub error_rate: Option<f64>,
+	pub rate_limit_factor: f64,
+}
+
+pub(crate) fn active_profiles(
 	behaviors: &EndpointBehaviors,
 	elapsed_ms: f64,
-) -> Option<(&BehaviorWindow, f64)> {
+) -> Vec<(&BehaviorProfile, ProfileValues)> {
+	behaviors
+		.profiles
+		.iter()
+		.filter_map(|profile| profile_values(profile, elapsed_ms).map(|values| (profile, values)))
+		.collect()
+}
+
+/// Evaluate a profile; values hold at the first and last keyframes outside their range.
+pub(crate) fn profile_values(profile: &BehaviorProfile, elapsed_ms: f64) -> Option<ProfileValues> {
+	let first = profile.keyframes.first()?;
+	let last = profile.keyframes.last()?;
+
+	let period_ms = last.at_ms - first.at_ms;
+	let at_ms = if profile.repeat && period_ms > 0.0 && elapsed_ms > last.at_ms {
+		first.at_ms + (elapsed_ms - first.at_ms) % period_ms
+	} else {
+		elapsed_ms
+	};
+
+	if at_ms <= first.at_ms {
+		return Some(keyframe_values(first));
+	}
+	if at_ms >= last.at_ms {
+		return Some(keyframe_values(last));
+	}
+
+	let segment = profile
+		.keyframes
+		.windows(2)
+		.find(|pair| at_ms >= pair[0].at_ms && at_ms < pair[1].at_ms)?;
+	let (from, to) = (&segment[0], &segment[1]);
+	let span_ms = to.at_ms - from.at_ms;
+	if span_ms <= 0.0 {
+		return Some(keyframe_values(to));
+	}
+	let progress = (at_ms - from.at_ms) / span_ms;
+	let progress = match profile.interpolation {
+		Some(RampCurve::SCurve) => smoothstep(progress),
+		_ => progress,
+	};
+	let lerp = |a: f64, b: f64| a + (b - a) * progress;
+
+	Some(ProfileValues {
+		latency_multiplier: lerp(from.latency_multiplier, to.latency_multiplier),
+		error_rate: match (from.error_rate, to.error_rate) {
+			(Some(a), Some(b)) => Some(lerp(a, b)),
+			_ => None,
+		},
+		rate_limit_factor: lerp(from.rate_limit_factor, to.rate_limit_factor),
+	})
+}
+
+fn keyframe_values(keyframe: &Keyframe) -> ProfileValues {
+	ProfileValues {
+		latency_multiplier: keyframe.latency_multiplier,
+		error_rate: keyframe.error_rate,
+		rate_limit_factor: keyframe.rate_limit_factor,
+	}
+}
+
+/// Active windows in application order (lowest priority first).
+///
+/// The highest priority active window always applies; lower priority windows
+/// are only kept while every window above them is marked `stack`. Ties keep
+/// declaration order, so the first declared window wins.
+pub(crate) fn active_windows(
+	behaviors: &EndpointBehaviors,
+	elapsed_ms: f64,
+) -> Vec<(&BehaviorWindow, f64)> {
+	let mut active = Vec::new();
 	for window in &behaviors.windows {
 		if let Some((start_ms, end_ms)) = schedule_range(&window.schedule, elapsed_ms, window) {
 			if elapsed_ms >= start_ms && elapsed_ms < end_ms {
 				let factor = ramp_factor(elapsed_ms, start_ms, end_ms, window.ramp.as_ref());
-				return Some((window, factor));
+				active.push((window, factor, window.priority, window.stack));
 			}
 		}
 	}
 
-	None
+	compose_by_priority(active)
 }
 
-fn active_burst(
+/// Active bursts in application order, composed like `active_windows`.
+pub(crate) fn active_bursts(
 	behaviors: &EndpointBehaviors,
 	elapsed_ms: f64,
-) -> Option<(&BurstEvent, f64)> {
+) -> Vec<(&BurstEvent, f64)> {
+	let mut active = Vec::new();
 	for burst in &behaviors.bursts {
 		let frequency = &burst.frequency;
 		if let Some((start_ms, end_ms)) = burst_range(frequency, burst.duration_ms, elapsed_ms, burst) {
 			if elapsed_ms >= start_ms && elapsed_ms < end_ms {
 				let factor = ramp_factor(elapsed_ms, start_ms, end_ms, burst.ramp.as_ref());
-				return Some((burst, factor));
+				active.push((burst, factor, burst.priority, burst.stack));
 			}
 		}
 	}
 
-	None
+	compose_by_priority(active)
 }
 
-fn schedule_range(
+fn compose_by_priority<T>(mut active: Vec<(T, f64, i32, bool)>) -> Vec<(T, f64)> {
+	active.sort_by_key(|(_, _, priority, _)| std::cmp::Reverse(*priority));
+
+	let mut composed = Vec::new();
+	for (item, factor, _, stack) in active {
+		composed.push((item, factor));
+		if !stack {
+			break;
+		}
+	}
+
+	composed.reverse();
+	composed
+}
+
+pub(crate) fn schedule_range(
 	schedule: &BehaviorSchedule,
 	elapsed_ms: f64,
 	window: &BehaviorWindow,
@@ -244,17 +670,84 @@ fn schedule_range(
 					return None;
 				}
 			}
-			let base_start = min_delay_ms + (occurrence as f64 * every_ms);
-			let jitter_ms = schedule.jitter_ms.unwrap_or(0.0);
-			let jitter = jitter_for_window(window, occurrence, jitter_ms);
-			let start_ms = (base_start + jitter).max(min_delay_ms).max(0.0);
+			Some(recurring_range(schedule, window, occurrence))
+		}
+		ScheduleMode::Cron => cron_ranges(schedule, elapsed_ms, Utc::now()).next(),
+	}
+}
+
+fn recurring_range(schedule: &BehaviorSchedule, window: &BehaviorWindow, occurrence: u32) -> (f64, f64) {
+	let every_ms = schedule.every_ms.unwrap_or(0.0);
+	let min_delay_ms = schedule.min_delay_ms.unwrap_or(0.0);
+	let base_start = min_delay_ms + (occurrence as f64 * every_ms);
+	let jitter_ms = schedule.jitter_ms.unwrap_or(0.0);
+	let jitter = jitter_for_window(window, occurrence, jitter_ms);
+	let start_ms = (base_start + jitter).max(min_delay_ms).max(0.0);
+	(start_ms, start_ms + schedule.duration_ms)
+}
+
+/// Cron occurrences that have not ended yet, mapped onto the elapsed-time axis.
+/// The schedule is parsed when the registry loads the window; unparsed ones have none.
+fn cron_ranges(
+	schedule: &BehaviorSchedule,
+	elapsed_ms: f64,
+	now: DateTime<Utc>,
+) -> impl Iterator<Item = (f64, f64)> {
+	let duration_ms = schedule.duration_ms;
+	let occurrences = schedule.parsed_cron.as_ref().map(|cron| {
+		let search_from = now - chrono::Duration::milliseconds(duration_ms.ceil() as i64);
+		cron.starts_from(search_from.with_nanosecond(0).unwrap_or(search_from))
+	});
+
+	occurrences
+		.into_iter()
+		.flatten()
+		.map(move |start| {
+			let offset_ms = (start - now).num_milliseconds() as f64;
+			let start_ms = elapsed_ms + offset_ms;
+			(start_ms, start_ms + duration_ms)
+		})
+		.skip_while(move |(_, end_ms)| *end_ms <= elapsed_ms)
+}
+
+/// Upcoming (or currently active) occurrences of a behavior window, expressed
+/// as `(start_ms, end_ms)` offsets from config load time.
+pub fn upcoming_window_occurrences(
+	window: &BehaviorWindow,
+	elapsed_ms: f64,
+	now: DateTime<Utc>,
+	count: usize,
+) -> Vec<(f64, f64)> {
+	let schedule = &window.schedule;
+	match schedule.mode {
+		ScheduleMode::Fixed => {
+			let start_ms = schedule.start_offset_ms.unwrap_or(0.0);
 			let end_ms = start_ms + schedule.duration_ms;
-			Some((start_ms, end_ms))
+			if end_ms > elapsed_ms && count > 0 {
+				vec![(start_ms, end_ms)]
+			} else {
+				vec![]
+			}
+		}
+		ScheduleMode::Recurring => {
+			let every_ms = schedule.every_ms.unwrap_or(0.0);
+			if every_ms <= 0.0 {
+				return vec![];
+			}
+			let min_delay_ms = schedule.min_delay_ms.unwrap_or(0.0);
+			let first = ((elapsed_ms - min_delay_ms) / every_ms).floor().max(0.0) as u32;
+			let last = schedule.max_occurrences.unwrap_or(u32::MAX);
+			(first..last)
+				.map(|occurrence| recurring_range(schedule, window, occurrence))
+				.filter(|(_, end_ms)| *end_ms > elapsed_ms)
+				.take(count)
+				.collect()
 		}
+		ScheduleMode::Cron => cron_ranges(schedule, elapsed_ms, now).take(count).collect(),
 	}
 }
 
-fn burst_range(
+pub(crate) fn burst_range(
 	frequency: &crate::config::BurstFrequency,
 	duration_ms: f64,
 	elapsed_ms: f64,
@@ -264,13 +757,103 @@ fn burst_range(
 	if every_ms <= 0.0 {
 		return None;
 	}
-	let occurrence = (elapsed_ms / every_ms).floor() as u32;
-	let base_start = occurrence as f64 * every_ms;
+	match frequency.arrival {
+		BurstArrival::Periodic => {
+			let occurrence = (elapsed_ms / every_ms).floor() as u32;
+			Some(burst_occurrence_range(frequency, duration_ms, burst, occurrence))
+		}
+		BurstArrival::Poisson => {
+			// Sampled durations never exceed duration_ms, so older buckets cannot be active.
+			let first = ((elapsed_ms - duration_ms) / every_ms).floor().max(0.0) as u64;
+			let last = (elapsed_ms / every_ms).floor().max(0.0) as u64;
+			(first..=last)
+				.flat_map(|bucket| poisson_arrivals(burst, bucket))
+				.rfind(|(start_ms, end_ms)| *start_ms <= elapsed_ms && elapsed_ms < *end_ms)
+		}
+	}
+}
+
+fn burst_occurrence_range(
+	frequency: &crate::config::BurstFrequency,
+	duration_ms: f64,
+	burst: &BurstEvent,
+	occurrence: u32,
+) -> (f64, f64) {
+	let base_start = occurrence as f64 * frequency.every_ms;
 	let jitter_ms = frequency.jitter_ms.unwrap_or(0.0);
 	let jitter = jitter_for_burst(burst, occurrence, jitter_ms);
 	let start_ms = (base_start + jitter).max(0.0);
-	let end_ms = start_ms + duration_ms;
-	Some((start_ms, end_ms))
+	let duration_ms = match burst.duration_distribution {
+		Some(_) => burst_duration(burst, &mut burst_rng(burst, occurrence as u64)),
+		None => duration_ms,
+	};
+	(start_ms, start_ms + duration_ms)
+}
+
+/// Poisson arrivals within one `every_ms` wide bucket of the timeline.
+///
+/// Each bucket draws its own arrival count and offsets from a generator seeded
+/// by the burst key, the configured seed and the bucket index, so the process
+/// is reproducible and can be evaluated at any point without replaying it.
+fn poisson_arrivals(burst: &BurstEvent, bucket: u64) -> Vec<(f64, f64)> {
+	let every_ms = burst.frequency.every_ms;
+	let mut rng = burst_rng(burst, bucket);
+	let count = Poisson::new(1.0).map(|poisson| poisson.sample(&mut rng) as u64).unwrap_or(0);
+	let bucket_start = bucket as f64 * every_ms;
+
+	let mut starts = (0..count)
+		.map(|_| bucket_start + rng.gen::<f64>() * every_ms)
+		.collect::<Vec<_>>();
+	starts.sort_by(|a, b| a.total_cmp(b));
+	starts
+		.into_iter()
+		.map(|start_ms| (start_ms, start_ms + burst_duration(burst, &mut rng)))
+		.collect()
+}
+
+fn burst_duration(burst: &BurstEvent, rng: &mut StdRng) -> f64 {
+	match &burst.duration_distribution {
+		Some(distribution) => {
+			let sampled = sample_latency_from(distribution, rng).as_secs_f64() * 1000.0;
+			sampled.clamp(0.0, burst.duration_ms)
+		}
+		None => burst.duration_ms,
+	}
+}
+
+fn burst_rng(burst: &BurstEvent, stream: u64) -> StdRng {
+	let mut hasher = DefaultHasher::new();
+	burst_key(burst).hash(&mut hasher);
+	burst.frequency.seed.unwrap_or(0).hash(&mut hasher);
+	stream.hash(&mut hasher);
+	StdRng::seed_from_u64(hasher.finish())
+}
+
+/// Upcoming (or currently active) occurrences of a burst event, expressed as
+/// `(start_ms, end_ms)` offsets from config load time.
+pub fn upcoming_burst_occurrences(burst: &BurstEvent, elapsed_ms: f64, count: usize) -> Vec<(f64, f64)> {
+	let frequency = &burst.frequency;
+	if frequency.every_ms <= 0.0 {
+		return vec![];
+	}
+	match frequency.arrival {
+		BurstArrival::Periodic => {
+			let first = (elapsed_ms / frequency.every_ms).floor().max(0.0) as u32;
+			(first..u32::MAX)
+				.map(|occurrence| burst_occurrence_range(frequency, burst.duration_ms, burst, occurrence))
+				.filter(|(_, end_ms)| *end_ms > elapsed_ms)
+				.take(count)
+				.collect()
+		}
+		BurstArrival::Poisson => {
+			let first = ((elapsed_ms - burst.duration_ms) / frequency.every_ms).floor().max(0.0) as u64;
+			(first..u64::MAX)
+				.flat_map(|bucket| poisson_arrivals(burst, bucket))
+				.filter(|(_, end_ms)| *end_ms > elapsed_ms)
+				.take(count)
+				.collect()
+		}
+	}
 }
 
 fn jitter_for_window(window: &BehaviorWindow, occurrence: u32, jitter_ms: f64) -> f64 {
@@ -358,13 +941,59 @@ fn smoothstep(value: f64) -> f64 {
 	value * value * (3.0 - 2.0 * value)
 }
 
-fn check_rate_limit(endpoint: &Endpoint) -> bool {
-	let Some(limiter) = endpoint.rate_limiter.as_ref() else {
-		return true;
-	};
+/// Take a token from the endpoint bucket and every shared bucket, or from none
+/// of them when any is empty, returning the rejection of the first empty one.
+/// Profiles only scale the endpoint's own bucket.
+fn check_rate_limit(
+	resolved: &ResolvedEndpoint,
+	request: &RequestContext,
+	refill_factor: f64,
+) -> Option<Response> {
+	let own = resolved.endpoint.rate_limiter.iter().map(|limiter| (limiter, refill_factor));
+	let shared = resolved.shared_rate_limiters.iter().map(|limiter| (limiter, 1.0));
+
+	let mut admitted = Vec::new();
+	for (limiter, factor) in own.chain(shared) {
+		let mut limiter = limiter.lock().expect("rate limiter lock");
+		let client = limiter.key().and_then(|key| request.client_key(key));
+		match limiter.check(client.as_deref(), factor) {
+			RateDecision::Limited => {
+				return Some(rate_limited_response(&limiter, client.as_deref(), factor));
+			}
+			RateDecision::Exempt => {}
+			RateDecision::Allowed => admitted.push((limiter, client)),
+		}
+	}
 
-	let mut limiter = limiter.lock().expect("rate limiter lock");
-	limiter.try_take()
+	for (limiter, client) in &mut admitted {
+		limiter.take(client.as_deref());
+	}
+	None
+}
+
+/// Rejection carrying `RateLimit-*` and `Retry-After` headers from the bucket
+/// state; configured headers override the computed ones.
+fn rate_limited_response(limiter: &RateLimiter, client: Option<&str>, refill_factor: f64) -> Response {
+	let whole_seconds = |secs: f64| secs.ceil().min(u32::MAX as f64).max(0.0) as u64;
+	let mut headers = HashMap::new();
+	headers.insert("Content-Type".to_string(), "text/plain".to_string());
+	if let Some(status) = limiter.status(client, refill_factor) {
+		headers.insert("RateLimit-Limit".to_string(), (status.limit.floor() as u64).to_string());
+		headers.insert("RateLimit-Remaining".to_string(), (status.remaining.floor() as u64).to_string());
+		headers.insert("RateLimit-Reset".to_string(), whole_seconds(status.reset_secs).to_string());
+		headers.insert(
+			"Retry-After".to_string(),
+			whole_seconds(status.retry_after_secs).max(1).to_string(),
+		);
+	}
+
+	match limiter.response() {
+		Some(response) => {
+			headers.extend(response.headers.clone());
+			build_response(response.status, &headers, &response.body)
+		}
+		None => build_response(429, &headers, "Rate limit exceeded"),
+	}
 }
 
 fn request_matches(body: &str, request: Option<&RequestMatch>) -> bool {
@@ -374,40 +1003,51 @@ fn request_matches(body: &str, request: Option<&RequestMatch>) -> bool {
 
 	match request.body_match {
 		BodyMatchType::Any | BodyMatchType::Ignore => true,
-		BodyMatchType::Exact => request.body.as_deref().map_or(false, |expected| expected == body),
-		BodyMatchType::Contains => request.body.as_deref().map_or(false, |expected| body.contains(expected)),
+		BodyMatchType::Exact => request.body.as_deref() == Some(body),
+		BodyMatchType::Contains => request.body.as_deref().is_some_and(|expected| body.contains(expected)),
 	}
 }
 
-fn sample_latency(latency: &LatencyConfig) -> Duration {
-	sample_latency_with(&latency.distribution, &latency.params)
+pub(crate) fn sample_latency(latency: &LatencyConfig) -> Duration {
+	sample_latency_from(latency, &mut rand::thread_rng())
+}
+
+fn sample_latency_from(latency: &LatencyConfig, rng: &mut dyn RngCore) -> Duration {
+	sample_latency_with(&latency.distribution, &latency.params, rng)
 }
 
-fn sample_latency_with(distribution: &DistributionType, params: &DistributionParams) -> Duration {
+fn sample_latency_with(
+	distribution: &DistributionType,
+	params: &DistributionParams,
+	rng: &mut dyn RngCore,
+) -> Duration {
 	match (distribution, params) {
 		(DistributionType::Fixed, DistributionParams::Fixed { delay_ms }) => {
-			FixedDistribution::new(*delay_ms).sample()
+			FixedDistribution::new(*delay_ms).sample_with(rng)
 		}
 		(DistributionType::Normal, DistributionParams::Normal { mean_ms, stddev_ms }) => {
-			NormalDistribution::new(*mean_ms, *stddev_ms).sample()
+			NormalDistribution::new(*mean_ms, *stddev_ms).sample_with(rng)
 		}
 		(DistributionType::Exponential, DistributionParams::Exponential { rate }) => {
-			ExponentialDistribution::new(*rate).sample()
+			ExponentialDistribution::new(*rate).sample_with(rng)
 		}
 		(DistributionType::Uniform, DistributionParams::Uniform { min_ms, max_ms }) => {
-			UniformDistribution::new(*min_ms, *max_ms).sample()
+			UniformDistribution::new(*min_ms, *max_ms).sample_with(rng)
 		}
-		(DistributionType::LogNormal, DistributionParams::LogNormal { mean_ms, stddev_ms }) => {
-			LogNormalDistribution::new(*mean_ms, *stddev_ms).sample()
+		(
+			DistributionType::LogNormal,
+			DistributionParams::LogNormal { mean_ms, stddev_ms } | DistributionParams::Normal { mean_ms, stddev_ms },
+		) => {
+			LogNormalDistribution::new(*mean_ms, *stddev_ms).sample_with(rng)
 		}
 		(DistributionType::Mixture, DistributionParams::Mixture { components }) => {
-			sample_mixture(components)
+			sample_mixture(components, rng)
 		}
 		_ => Duration::from_millis(0),
 	}
 }
 
-fn sample_mixture(components: &[MixtureComponent]) -> Duration {
+fn sample_mixture(components: &[MixtureComponent], rng: &mut dyn RngCore) -> Duration {
 	let mut total_weight = 0.0;
 	for component in components {
 		if component.weight.is_finite() && component.weight > 0.0 {
@@ -419,7 +1059,7 @@ fn sample_mixture(components: &[MixtureComponent]) -> Duration {
 		return Duration::from_millis(0);
 	}
 
-	let mut roll: f64 = rand::thread_rng().gen::<f64>() * total_weight;
+	let mut roll: f64 = rng.gen::<f64>() * total_weight;
 	for component in components {
 		if !component.weight.is_finite() || component.weight <= 0.0 {
 			continue;
@@ -428,7 +1068,7 @@ fn sample_mixture(components: &[MixtureComponent]) -> Duration {
 			continue;
 		}
 		if roll < component.weight {
-			return sample_latency_with(&component.distribution, component.params.as_ref());
+			return sample_latency_with(&component.distribution, component.params.as_ref(), rng);
 		}
 		roll -= component.weight;
 	}
@@ -436,7 +1076,7 @@ fn sample_mixture(components: &[MixtureComponent]) -> Duration {
 	Duration::from_millis(0)
 }
 
-fn should_error(profile: &ErrorProfile) -> bool {
+pub(crate) fn should_error(profile: &ErrorProfile) -> bool {
 	if profile.rate <= 0.0 {
 		return false;
 	}
@@ -448,7 +1088,7 @@ fn should_error(profile: &ErrorProfile) -> bool {
 	roll < profile.rate
 }
 
-fn pick_error_status(profile: &ErrorProfile) -> u16 {
+pub(crate) fn pick_error_status(profile: &ErrorProfile) -> u16 {
 	if profile.codes.is_empty() {
 		return 500;
 	}
@@ -457,18 +1097,18 @@ fn pick_error_status(profile: &ErrorProfile) -> u16 {
 	profile.codes[idx]
 }
 
-fn apply_payload_corruption(body: &str, profile: &ErrorProfile) -> String {
+fn apply_payload_corruption(body: &Bytes, profile: &ErrorProfile) -> Bytes {
 	let Some(corruption) = &profile.payload_corruption else {
-		return body.to_string();
+		return body.clone();
 	};
 
 	if corruption.rate <= 0.0 {
-		return body.to_string();
+		return body.clone();
 	}
 
 	let roll: f64 = rand::thread_rng().gen();
 	if roll >= corruption.rate {
-		return body.to_string();
+		return body.clone();
 	}
 
 	match corruption.mode {
@@ -476,19 +1116,21 @@ fn apply_payload_corruption(body: &str, profile: &ErrorProfile) -> String {
 			let ratio = corruption.truncate_ratio.unwrap_or(0.5);
 			let ratio = ratio.clamp(0.0, 1.0);
 			let len = (body.len() as f64 * ratio).floor() as usize;
-			body[..len.min(body.len())].to_string()
+			body.slice(..len.min(body.len()))
 		}
 		crate::config::CorruptionMode::Replace => {
-			corruption
-				.replacement
-				.as_deref()
-				.unwrap_or("")
-				.to_string()
+			Bytes::from(
+				corruption
+					.replacement
+					.as_deref()
+					.unwrap_or("")
+					.to_string(),
+			)
 		}
 	}
 }
 
-fn compute_bandwidth_delay(body: &str, cap: Option<&BandwidthCap>) -> Duration {
+fn compute_bandwidth_delay(body: &[u8], cap: Option<&BandwidthCap>) -> Duration {
 	let Some(cap) = cap else {
 		return Duration::from_millis(0);
 	};
@@ -497,7 +1139,7 @@ fn compute_bandwidth_delay(body: &str, cap: Option<&BandwidthCap>) -> Duration {
 		return Duration::from_millis(0);
 	}
 
-	let bytes = body.as_bytes().len() as f64;
+	let bytes = body.len() as f64;
 	let seconds = bytes / cap.bytes_per_second;
 	Duration::from_secs_f64(seconds)
 }
@@ -506,7 +1148,7 @@ fn compute_bandwidth_delay(body: &str, cap: Option<&BandwidthCap>) -> Duration {
 mod tests {
 	use super::*;
 	use crate::config::{DistributionParams, DistributionType, Response as ConfigResponse};
-	use std::collections::HashMap;
+	use std::sync::Arc;
 
 	fn base_endpoint() -> Endpoint {
 		Endpoint {
@@ -526,22 +1168,49 @@ mod tests {
 			error_profile: ErrorProfile::default(),
 			rate_limit: None,
 			bandwidth_cap: None,
+			capacity: None,
+			concurrency_limit: None,
+			resource: None,
+			workflow: None,
+			job: None,
+			auth: None,
+			required_scopes: vec![],
+			idempotency: None,
+			dependencies: None,
+			callbacks: vec![],
+			proxy: None,
 			loaded_at: None,
 			rate_limiter: None,
 		}
 	}
 
-	#[tokio::test]
-	async fn test_handle_request_success() {
-		let endpoint = base_endpoint();
-		let resolved = ResolvedEndpoint {
+	fn resolved(endpoint: Endpoint) -> ResolvedEndpoint {
+		ResolvedEndpoint {
 			endpoint,
 			behaviors: EndpointBehaviors {
 				windows: vec![],
 				bursts: vec![],
+				profiles: vec![],
 			},
-		};
-		let response = handle_request(&resolved, "").await;
+			shared_rate_limiters: vec![],
+			concurrency: vec![],
+			capacity: vec![],
+			resource: None,
+			workflow: None,
+			job: None,
+			auth: None,
+			idempotency: None,
+			dependencies: vec![],
+			callbacks: None,
+			proxy: None,
+			recording: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_handle_request_success() {
+		let endpoint = base_endpoint();
+		let response = handle_request(&resolved(endpoint), &RequestContext::default()).await;
 		assert_eq!(response.status(), axum::http::StatusCode::OK);
 	}
 
@@ -552,7 +1221,7 @@ mod tests {
 		endpoint.error_profile.codes = vec![503];
 		endpoint.error_profile.body = "error".to_string();
 
-		let response = handle_request(&endpoint, "").await;
+		let response = handle_request(&resolved(endpoint), &RequestContext::default()).await;
 		assert_eq!(response.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
 	}
 
@@ -564,7 +1233,447 @@ mod tests {
 			body: Some("ping".to_string()),
 		});
 
-		let response = handle_request(&endpoint, "pong").await;
+		let response = handle_request(&resolved(endpoint), &RequestContext::from_body("pong")).await;
 		assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
 	}
+
+	fn cron_window(cron: &str, timezone: Option<&str>) -> BehaviorWindow {
+		BehaviorWindow {
+			id: Some("nightly".to_string()),
+			scope: crate::config::BehaviorScope {
+				endpoint_id: None,
+				group_id: None,
+				global: true,
+			},
+			schedule: BehaviorSchedule {
+				mode: ScheduleMode::Cron,
+				start_offset_ms: None,
+				duration_ms: 1_800_000.0,
+				every_ms: None,
+				jitter_ms: None,
+				max_occurrences: None,
+				min_delay_ms: None,
+				cron: Some(cron.to_string()),
+				timezone: timezone.map(str::to_string),
+				parsed_cron: crate::config::CronSchedule::parse(cron, timezone).ok().map(Arc::new),
+			},
+			priority: 0,
+			stack: false,
+			ramp: None,
+			error_mix: ErrorMix::Override,
+			latency_override: None,
+			error_profile_override: None,
+		}
+	}
+
+	fn error_window(id: &str, priority: i32, stack: bool, rate: f64) -> BehaviorWindow {
+		let mut window = cron_window("0 2 * * *", None);
+		window.id = Some(id.to_string());
+		window.schedule.mode = ScheduleMode::Fixed;
+		window.schedule.start_offset_ms = Some(0.0);
+		window.priority = priority;
+		window.stack = stack;
+		window.error_mix = ErrorMix::Additive;
+		window.error_profile_override = Some(ErrorProfile {
+			rate,
+			codes: vec![503],
+			..ErrorProfile::default()
+		});
+		window
+	}
+
+	#[test]
+	fn test_higher_priority_window_shadows_lower() {
+		let behaviors = EndpointBehaviors {
+			windows: vec![error_window("low", 0, false, 0.2), error_window("high", 5, false, 0.5)],
+			bursts: vec![],
+			profiles: vec![],
+		};
+		let active = active_windows(&behaviors, 10.0);
+		assert_eq!(active.len(), 1);
+		assert_eq!(active[0].0.id.as_deref(), Some("high"));
+
+		let profile = effective_error_profile(&base_endpoint(), &behaviors, 10.0);
+		assert!((profile.rate - 0.5).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_stacked_windows_compose_in_priority_order() {
+		let behaviors = EndpointBehaviors {
+			windows: vec![error_window("high", 5, true, 0.5), error_window("low", 0, false, 0.2)],
+			bursts: vec![],
+			profiles: vec![],
+		};
+		let active = active_windows(&behaviors, 10.0);
+		let ids: Vec<_> = active.iter().map(|(window, _)| window.id.as_deref().unwrap()).collect();
+		assert_eq!(ids, vec!["low", "high"]);
+
+		let profile = effective_error_profile(&base_endpoint(), &behaviors, 10.0);
+		assert!((profile.rate - 0.7).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_equal_priority_windows_first_declared_wins() {
+		let behaviors = EndpointBehaviors {
+			windows: vec![error_window("first", 0, false, 0.3), error_window("second", 0, false, 0.6)],
+			bursts: vec![],
+			profiles: vec![],
+		};
+		let active = active_windows(&behaviors, 10.0);
+		assert_eq!(active.len(), 1);
+		assert_eq!(active[0].0.id.as_deref(), Some("first"));
+	}
+
+	fn poisson_burst(seed: Option<u64>) -> BurstEvent {
+		BurstEvent {
+			id: Some("incident".to_string()),
+			scope: crate::config::BehaviorScope {
+				endpoint_id: None,
+				group_id: None,
+				global: true,
+			},
+			frequency: crate::config::BurstFrequency {
+				arrival: BurstArrival::Poisson,
+				every_ms: 1_000.0,
+				jitter_ms: None,
+				seed,
+			},
+			duration_ms: 200.0,
+			duration_distribution: None,
+			priority: 0,
+			stack: false,
+			ramp: None,
+			latency_spike: None,
+			error_spike: None,
+		}
+	}
+
+	#[test]
+	fn test_poisson_bursts_reproducible_under_seed() {
+		let first = upcoming_burst_occurrences(&poisson_burst(Some(7)), 0.0, 20);
+		let again = upcoming_burst_occurrences(&poisson_burst(Some(7)), 0.0, 20);
+		let other = upcoming_burst_occurrences(&poisson_burst(Some(8)), 0.0, 20);
+		assert_eq!(first, again);
+		assert_ne!(first, other);
+	}
+
+	#[test]
+	fn test_poisson_bursts_mean_interval_and_clustering() {
+		let burst = poisson_burst(Some(1));
+		let arrivals = (0..5_000).flat_map(|bucket| poisson_arrivals(&burst, bucket)).collect::<Vec<_>>();
+
+		let rate = arrivals.len() as f64 / 5_000.0;
+		assert!((0.9..=1.1).contains(&rate), "arrival rate out of range: {}", rate);
+
+		let back_to_back = arrivals.windows(2).any(|pair| pair[1].0 < pair[0].1);
+		assert!(back_to_back, "expected some bursts to start before the previous one ended");
+	}
+
+	#[test]
+	fn test_poisson_burst_range_matches_arrivals() {
+		let burst = poisson_burst(None);
+		let (start_ms, end_ms) = upcoming_burst_occurrences(&burst, 0.0, 1)[0];
+		assert_eq!(
+			burst_range(&burst.frequency, burst.duration_ms, start_ms, &burst),
+			Some((start_ms, end_ms))
+		);
+	}
+
+	#[test]
+	fn test_burst_duration_distribution_is_capped() {
+		let mut burst = poisson_burst(Some(3));
+		burst.duration_distribution = Some(LatencyConfig {
+			distribution: DistributionType::Uniform,
+			params: DistributionParams::Uniform { min_ms: 50.0, max_ms: 400.0 },
+		});
+		let arrivals = (0..500).flat_map(|bucket| poisson_arrivals(&burst, bucket)).collect::<Vec<_>>();
+		let durations = arrivals.iter().map(|(start, end)| end - start).collect::<Vec<_>>();
+		assert!(durations.iter().all(|duration| (49.999..=200.001).contains(duration)));
+		assert!(durations.iter().any(|duration| *duration < 199.0));
+	}
+
+	fn utc(value: &str) -> DateTime<Utc> {
+		DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+	}
+
+	#[test]
+	fn test_cron_range_active_inside_window() {
+		let window = cron_window("0 2 * * *", None);
+		let now = utc("2026-03-10T02:10:00Z");
+		let (start_ms, end_ms) = cron_ranges(&window.schedule, 100_000.0, now).next().unwrap();
+		assert_eq!(start_ms, 100_000.0 - 600_000.0);
+		assert_eq!(end_ms, start_ms + 1_800_000.0);
+	}
+
+	#[test]
+	fn test_cron_range_next_when_outside_window() {
+		let window = cron_window("0 2 * * *", None);
+		let now = utc("2026-03-10T02:30:00Z");
+		let (start_ms, _) = cron_ranges(&window.schedule, 0.0, now).next().unwrap();
+		assert_eq!(start_ms, 23.5 * 3_600_000.0);
+	}
+
+	#[test]
+	fn test_cron_range_respects_timezone() {
+		let window = cron_window("0 2 * * *", Some("America/New_York"));
+		let now = utc("2026-03-10T06:10:00Z");
+		let (start_ms, _) = cron_ranges(&window.schedule, 0.0, now).next().unwrap();
+		assert_eq!(start_ms, -600_000.0);
+	}
+
+	#[test]
+	fn test_upcoming_occurrences_cron_weekdays() {
+		let window = cron_window("0 2 * * 1-5", None);
+		let now = utc("2026-03-13T12:00:00Z");
+		let occurrences = upcoming_window_occurrences(&window, 0.0, now, 2);
+		assert_eq!(occurrences.len(), 2);
+		let day_ms = 24.0 * 3_600_000.0;
+		assert_eq!(occurrences[0].0, 2.5 * day_ms + 2.0 * 3_600_000.0);
+		assert_eq!(occurrences[1].0, occurrences[0].0 + day_ms);
+	}
+
+	#[test]
+	fn test_upcoming_occurrences_recurring_max() {
+		let mut window = cron_window("0 2 * * *", None);
+		window.schedule.mode = ScheduleMode::Recurring;
+		window.schedule.every_ms = Some(1_000.0);
+		window.schedule.duration_ms = 100.0;
+		window.schedule.max_occurrences = Some(3);
+		let occurrences = upcoming_window_occurrences(&window, 1_050.0, Utc::now(), 10);
+		assert_eq!(occurrences, vec![(1_000.0, 1_100.0), (2_000.0, 2_100.0)]);
+	}
+
+	fn degrading_profile(interpolation: Option<RampCurve>, repeat: bool) -> BehaviorProfile {
+		let keyframe = |at_ms: f64, latency_multiplier: f64, error_rate: f64| Keyframe {
+			at_ms,
+			latency_multiplier,
+			error_rate: Some(error_rate),
+			rate_limit_factor: 1.0 / latency_multiplier,
+		};
+		BehaviorProfile {
+			id: Some("leak".to_string()),
+			scope: crate::config::BehaviorScope {
+				endpoint_id: Some("health".to_string()),
+				group_id: None,
+				global: false,
+			},
+			keyframes: vec![keyframe(1_000.0, 1.0, 0.0), keyframe(2_000.0, 4.0, 0.4)],
+			interpolation,
+			repeat,
+			error_mix: ErrorMix::Override,
+			error_codes: vec![503],
+			error_body: String::new(),
+		}
+	}
+
+	#[test]
+	fn test_profile_values_linear_interpolation() {
+		let profile = degrading_profile(None, false);
+		let values = profile_values(&profile, 1_500.0).unwrap();
+		assert!((values.latency_multiplier - 2.5).abs() < 1e-9);
+		assert!((values.error_rate.unwrap() - 0.2).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_profile_values_hold_outside_keyframes() {
+		let profile = degrading_profile(None, false);
+		assert_eq!(profile_values(&profile, 0.0).unwrap().latency_multiplier, 1.0);
+		let late = profile_values(&profile, 10_000.0).unwrap();
+		assert_eq!(late.latency_multiplier, 4.0);
+		assert_eq!(late.rate_limit_factor, 0.25);
+	}
+
+	#[test]
+	fn test_profile_values_s_curve() {
+		let profile = degrading_profile(Some(RampCurve::SCurve), false);
+		let early = profile_values(&profile, 1_250.0).unwrap();
+		assert!(early.latency_multiplier < 1.75);
+		let mid = profile_values(&profile, 1_500.0).unwrap();
+		assert!((mid.latency_multiplier - 2.5).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_profile_values_repeat_sawtooth() {
+		let profile = degrading_profile(None, true);
+		let values = profile_values(&profile, 3_500.0).unwrap();
+		assert!((values.latency_multiplier - 2.5).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_profile_error_rate_applies() {
+		let mut resolved = resolved(base_endpoint());
+		resolved.behaviors.profiles.push(degrading_profile(None, false));
+		let profile = effective_error_profile(&resolved.endpoint, &resolved.behaviors, 5_000.0);
+		assert!((profile.rate - 0.4).abs() < 1e-9);
+		assert_eq!(profile.codes, vec![503]);
+	}
+
+	#[tokio::test]
+	async fn test_handle_request_rejects_on_capacity_overflow() {
+		let mut endpoint = base_endpoint();
+		endpoint.latency.params = DistributionParams::Fixed { delay_ms: 100.0 };
+		let mut resolved = resolved(endpoint);
+		let owner = crate::engine::PoolOwner::Endpoint("health".to_string());
+		resolved.capacity.push(Arc::new(crate::engine::CapacityPool::new(owner, crate::config::CapacityModel {
+			workers: 1,
+			queue_size: 0,
+			load_curve: vec![],
+			overflow_status: 503,
+			overflow_body: "busy".to_string(),
+		})));
+
+		let request = RequestContext::default();
+		let (first, second) = tokio::join!(handle_request(&resolved, &request), async {
+			tokio::time::sleep(Duration::from_millis(20)).await;
+			handle_request(&resolved, &request).await
+		});
+		assert_eq!(first.status(), axum::http::StatusCode::OK);
+		assert_eq!(second.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+	}
+
+	fn limiter(per_second: f64, burst: f64) -> Arc<std::sync::Mutex<crate::config::RateLimiter>> {
+		Arc::new(std::sync::Mutex::new(crate::config::RateLimiter::new(&crate::config::RateLimit {
+			requests_per_second: Some(per_second),
+			requests_per_hour: None,
+			burst: Some(burst),
+			key: None,
+			response: None,
+		})))
+	}
+
+	#[tokio::test]
+	async fn test_shared_rate_limit_spans_endpoints() {
+		let shared = limiter(0.001, 1.0);
+		let mut first = resolved(base_endpoint());
+		first.shared_rate_limiters.push(Arc::clone(&shared));
+		let mut second = resolved(base_endpoint());
+		second.shared_rate_limiters.push(shared);
+
+		let response = handle_request(&first, &RequestContext::default()).await;
+		assert_eq!(response.status(), axum::http::StatusCode::OK);
+		let response = handle_request(&second, &RequestContext::default()).await;
+		assert_eq!(response.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+	}
+
+	#[test]
+	fn test_rate_limit_rejection_keeps_endpoint_tokens() {
+		let mut endpoint = base_endpoint();
+		endpoint.rate_limiter = Some(limiter(0.001, 1.0));
+		let mut resolved = resolved(endpoint);
+		resolved.shared_rate_limiters.push(limiter(0.001, 0.0));
+
+		let request = RequestContext::default();
+		assert!(check_rate_limit(&resolved, &request, 1.0).is_some());
+		resolved.shared_rate_limiters.clear();
+		assert!(check_rate_limit(&resolved, &request, 1.0).is_none());
+	}
+
+	fn keyed_limiter(max_keys: usize, missing: crate::config::MissingKeyPolicy) -> Arc<std::sync::Mutex<crate::config::RateLimiter>> {
+		Arc::new(std::sync::Mutex::new(crate::config::RateLimiter::new(&crate::config::RateLimit {
+			requests_per_second: Some(0.001),
+			requests_per_hour: None,
+			burst: Some(1.0),
+			key: Some(crate::config::RateLimitKey {
+				source: crate::config::KeySource::Header,
+				name: Some("x-tenant".to_string()),
+				max_keys,
+				idle_timeout_ms: 60_000.0,
+				missing,
+			}),
+			response: None,
+		})))
+	}
+
+	fn tenant(name: &str) -> RequestContext {
+		let mut request = RequestContext::default();
+		request.headers.insert("x-tenant", name.parse().unwrap());
+		request
+	}
+
+	#[test]
+	fn test_per_client_rate_limit_buckets() {
+		let mut endpoint = base_endpoint();
+		endpoint.rate_limiter = Some(keyed_limiter(10, crate::config::MissingKeyPolicy::Shared));
+		let resolved = resolved(endpoint);
+
+		assert!(check_rate_limit(&resolved, &tenant("acme"), 1.0).is_none());
+		assert!(check_rate_limit(&resolved, &tenant("acme"), 1.0).is_some());
+		assert!(check_rate_limit(&resolved, &tenant("globex"), 1.0).is_none());
+		assert!(check_rate_limit(&resolved, &RequestContext::default(), 1.0).is_none());
+		assert!(check_rate_limit(&resolved, &RequestContext::default(), 1.0).is_some());
+	}
+
+	#[test]
+	fn test_per_client_rate_limit_evicts_beyond_max_keys() {
+		let mut endpoint = base_endpoint();
+		endpoint.rate_limiter = Some(keyed_limiter(1, crate::config::MissingKeyPolicy::Shared));
+		let resolved = resolved(endpoint);
+
+		assert!(check_rate_limit(&resolved, &tenant("acme"), 1.0).is_none());
+		assert!(check_rate_limit(&resolved, &tenant("globex"), 1.0).is_none());
+		assert!(check_rate_limit(&resolved, &tenant("acme"), 1.0).is_none());
+	}
+
+	#[test]
+	fn test_per_client_rate_limit_missing_key_policy() {
+		let mut endpoint = base_endpoint();
+		endpoint.rate_limiter = Some(keyed_limiter(10, crate::config::MissingKeyPolicy::Reject));
+		let rejecting = resolved(endpoint);
+		assert!(check_rate_limit(&rejecting, &RequestContext::default(), 1.0).is_some());
+
+		let mut endpoint = base_endpoint();
+		endpoint.rate_limiter = Some(keyed_limiter(10, crate::config::MissingKeyPolicy::Allow));
+		let allowing = resolved(endpoint);
+		assert!(check_rate_limit(&allowing, &RequestContext::default(), 1.0).is_none());
+		assert!(check_rate_limit(&allowing, &RequestContext::default(), 1.0).is_none());
+	}
+
+	#[tokio::test]
+	async fn test_rate_limited_response_headers() {
+		let mut endpoint = base_endpoint();
+		endpoint.rate_limiter = Some(limiter(0.5, 2.0));
+		let resolved = resolved(endpoint);
+		let request = RequestContext::default();
+
+		for _ in 0..2 {
+			assert!(check_rate_limit(&resolved, &request, 1.0).is_none());
+		}
+		let response = check_rate_limit(&resolved, &request, 1.0).unwrap();
+		assert_eq!(response.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+		let headers = response.headers();
+		assert_eq!(headers.get("ratelimit-limit").unwrap(), "2");
+		assert_eq!(headers.get("ratelimit-remaining").unwrap(), "0");
+		assert_eq!(headers.get("ratelimit-reset").unwrap(), "4");
+		assert_eq!(headers.get("retry-after").unwrap(), "2");
+	}
+
+	#[tokio::test]
+	async fn test_rate_limited_response_custom() {
+		let mut endpoint = base_endpoint();
+		let mut custom = HashMap::new();
+		custom.insert("Content-Type".to_string(), "application/json".to_string());
+		custom.insert("Retry-After".to_string(), "30".to_string());
+		endpoint.rate_limiter = Some(Arc::new(std::sync::Mutex::new(crate::config::RateLimiter::new(
+			&crate::config::RateLimit {
+				requests_per_second: Some(1.0),
+				requests_per_hour: None,
+				burst: Some(1.0),
+				key: None,
+				response: Some(crate::config::RateLimitResponse {
+					status: 503,
+					headers: custom,
+					body: r#"{"error":"slow down"}"#.to_string(),
+				}),
+			},
+		))));
+		let resolved = resolved(endpoint);
+		let request = RequestContext::default();
+
+		assert!(check_rate_limit(&resolved, &request, 1.0).is_none());
+		let response = check_rate_limit(&resolved, &request, 1.0).unwrap();
+		assert_eq!(response.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+		assert_eq!(response.headers().get("retry-after").unwrap(), "30");
+		assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+		assert_eq!(response.headers().get("ratelimit-limit").unwrap(), "1");
+	}
 }
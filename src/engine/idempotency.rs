@@ -0,0 +1,232 @@
+// Idempotency keys
+//
+// Per-key attempt counts and stored first responses, so repeated requests
+// replay the original outcome and retries can be failed deterministically
+
+use crate::config::Idempotency;
+use axum::body::Bytes;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Keys seen by one endpoint.
+#[derive(Debug)]
+pub struct IdempotencyStore {
+	config: Idempotency,
+	keys: HashMap<String, KeyRecord>,
+}
+
+#[derive(Debug)]
+struct KeyRecord {
+	first_seen: Instant,
+	attempts: u32,
+	fingerprint: u64,
+	response: Option<StoredResponse>,
+	/// An attempt is running the endpoint.
+	in_flight: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+	pub status: u16,
+	pub headers: HashMap<String, String>,
+	pub body: Bytes,
+}
+
+/// What to do with a keyed request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attempt {
+	/// Run the endpoint; `record` the outcome afterwards.
+	Proceed,
+	/// Scripted failure for attempt number `n` of the key.
+	Fail(u32),
+	/// Same key, same body: return the first response again.
+	Replay(StoredResponse),
+	/// Same key sent with a different body.
+	Mismatch,
+	/// An earlier attempt with the key has not finished yet.
+	InFlight,
+}
+
+/// Marks a key as in flight until dropped, so cancelled requests release it too.
+#[derive(Debug)]
+pub struct KeyInFlight(Arc<Mutex<IdempotencyStore>>, String);
+
+impl KeyInFlight {
+	pub fn new(store: &Arc<Mutex<IdempotencyStore>>, key: &str) -> Self {
+		Self(Arc::clone(store), key.to_string())
+	}
+}
+
+impl Drop for KeyInFlight {
+	fn drop(&mut self) {
+		if let Ok(mut store) = self.0.lock() {
+			if let Some(record) = store.keys.get_mut(&self.1) {
+				record.in_flight = false;
+			}
+		}
+	}
+}
+
+impl IdempotencyStore {
+	pub fn new(config: Idempotency) -> Self {
+		Self {
+			config,
+			keys: HashMap::new(),
+		}
+	}
+
+	/// Count an attempt for `key` and decide how to answer it. A `Proceed`
+	/// leaves the key in flight until the caller's `KeyInFlight` is dropped.
+	pub fn begin(&mut self, key: &str, body: &str) -> Attempt {
+		self.begin_at(key, body, Instant::now())
+	}
+
+	fn begin_at(&mut self, key: &str, body: &str, now: Instant) -> Attempt {
+		self.sweep(key, now);
+
+		let fingerprint = fingerprint(body);
+		let record = self.keys.entry(key.to_string()).or_insert(KeyRecord {
+			first_seen: now,
+			attempts: 0,
+			fingerprint,
+			response: None,
+			in_flight: false,
+		});
+		if record.fingerprint != fingerprint {
+			return Attempt::Mismatch;
+		}
+		if record.in_flight {
+			return Attempt::InFlight;
+		}
+		record.attempts = record.attempts.saturating_add(1);
+
+		if let Some(response) = record.response.as_ref().filter(|_| self.config.replay) {
+			return Attempt::Replay(response.clone());
+		}
+		match &self.config.fail_attempts {
+			Some(failure) if record.attempts <= failure.attempts => Attempt::Fail(record.attempts),
+			_ => {
+				record.in_flight = true;
+				Attempt::Proceed
+			}
+		}
+	}
+
+	/// Store the response to a key's first successful attempt. Server errors
+	/// and 429s are transient, so the next retry runs the endpoint again.
+	/// Either way the attempt is over and the key no longer in flight.
+	pub fn record(&mut self, key: &str, response: StoredResponse) {
+		let replay = self.config.replay && response.status < 500 && response.status != 429;
+		if let Some(record) = self.keys.get_mut(key) {
+			record.in_flight = false;
+			if replay {
+				record.response.get_or_insert(response);
+			}
+		}
+	}
+
+	/// Drop keys past their TTL, then make room for `incoming` within `max_keys`.
+	fn sweep(&mut self, incoming: &str, now: Instant) {
+		let ttl = Duration::from_secs_f64(self.config.ttl_ms.max(0.0) / 1000.0);
+		self.keys
+			.retain(|_, record| now.saturating_duration_since(record.first_seen) < ttl);
+		if self.keys.contains_key(incoming) {
+			return;
+		}
+
+		while self.keys.len() >= self.config.max_keys.max(1) {
+			let oldest = self
+				.keys
+				.iter()
+				.min_by_key(|(_, record)| record.first_seen)
+				.map(|(key, _)| key.clone());
+			match oldest {
+				Some(key) => self.keys.remove(&key),
+				None => break,
+			};
+		}
+	}
+}
+
+fn fingerprint(body: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	body.hash(&mut hasher);
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::AttemptFailure;
+
+	fn store(fail_attempts: Option<u32>, max_keys: usize) -> IdempotencyStore {
+		IdempotencyStore::new(Idempotency {
+			header: "Idempotency-Key".to_string(),
+			ttl_ms: 1000.0,
+			max_keys,
+			replay: true,
+			fail_attempts: fail_attempts.map(|attempts| AttemptFailure {
+				attempts,
+				status: 503,
+				headers: HashMap::new(),
+				body: String::new(),
+			}),
+		})
+	}
+
+	fn response(status: u16, body: &str) -> StoredResponse {
+		StoredResponse {
+			status,
+			headers: HashMap::new(),
+			body: body.to_string().into(),
+		}
+	}
+
+	#[test]
+	fn test_fails_first_attempts_then_replays() {
+		let mut keys = store(Some(2), 10);
+		let now = Instant::now();
+		assert_eq!(keys.begin_at("a", "{}", now), Attempt::Fail(1));
+		assert_eq!(keys.begin_at("a", "{}", now), Attempt::Fail(2));
+		assert_eq!(keys.begin_at("b", "{}", now), Attempt::Fail(1));
+		assert_eq!(keys.begin_at("a", "{}", now), Attempt::Proceed);
+
+		keys.record("a", response(500, "oops"));
+		assert_eq!(keys.begin_at("a", "{}", now), Attempt::Proceed);
+		keys.record("a", response(201, "first"));
+		keys.record("a", response(201, "second"));
+		assert_eq!(keys.begin_at("a", "{}", now), Attempt::Replay(response(201, "first")));
+		assert_eq!(keys.begin_at("a", "{\"other\": 1}", now), Attempt::Mismatch);
+	}
+
+	#[test]
+	fn test_key_is_in_flight_until_released() {
+		let keys = Arc::new(Mutex::new(store(None, 10)));
+		let now = Instant::now();
+		assert_eq!(keys.lock().unwrap().begin_at("a", "{}", now), Attempt::Proceed);
+		let in_flight = KeyInFlight::new(&keys, "a");
+		assert_eq!(keys.lock().unwrap().begin_at("a", "{}", now), Attempt::InFlight);
+		assert_eq!(keys.lock().unwrap().begin_at("a", "{\"other\": 1}", now), Attempt::Mismatch);
+
+		drop(in_flight);
+		assert_eq!(keys.lock().unwrap().begin_at("a", "{}", now), Attempt::Proceed);
+	}
+
+	#[test]
+	fn test_keys_expire_and_are_bounded() {
+		let mut keys = store(None, 2);
+		let start = Instant::now();
+		keys.begin_at("a", "", start);
+		keys.record("a", response(200, "a"));
+		keys.begin_at("b", "", start + Duration::from_millis(10));
+		keys.begin_at("c", "", start + Duration::from_millis(20));
+		assert_eq!(keys.keys.len(), 2);
+		assert_eq!(keys.begin_at("a", "", start + Duration::from_millis(30)), Attempt::Proceed);
+
+		keys.begin_at("d", "", start + Duration::from_secs(5));
+		assert_eq!(keys.keys.len(), 1);
+	}
+}
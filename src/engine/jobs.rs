@@ -0,0 +1,275 @@
+// Asynchronous jobs
+//
+// Submitted jobs complete after a sampled duration; status endpoints report
+// their progress and, once done, the sampled outcome
+
+use crate::config::{Endpoint, Job, JobBinding, JobOperation};
+use crate::engine::resources::random_uuid;
+use crate::engine::{pick_error_status, sample_latency, should_error, RequestContext};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Jobs of one type, by id.
+#[derive(Debug)]
+pub struct JobQueue {
+	job: Job,
+	records: HashMap<String, JobRecord>,
+}
+
+#[derive(Debug)]
+struct JobRecord {
+	submitted_at: Instant,
+	pending: Duration,
+	duration: Duration,
+	outcome: JobOutcome,
+}
+
+/// Decided at submission so polls agree with each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JobOutcome {
+	Succeeded,
+	Failed { status: u16, body: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobPhase {
+	Pending,
+	Running,
+	Succeeded,
+	Failed,
+}
+
+impl JobPhase {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Pending => "pending",
+			Self::Running => "running",
+			Self::Succeeded => "succeeded",
+			Self::Failed => "failed",
+		}
+	}
+}
+
+/// Response for a job operation; `headers` add to the endpoint's.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobResponse {
+	pub status: u16,
+	pub headers: HashMap<String, String>,
+	pub body: String,
+}
+
+impl JobQueue {
+	pub fn new(job: Job) -> Self {
+		Self {
+			job,
+			records: HashMap::new(),
+		}
+	}
+
+	pub fn job(&self) -> &Job {
+		&self.job
+	}
+
+	pub fn len(&self) -> usize {
+		self.records.len()
+	}
+
+	/// Number of tracked jobs in each phase.
+	pub fn phase_counts(&self) -> HashMap<&'static str, usize> {
+		let now = Instant::now();
+		let mut counts = HashMap::new();
+		for id in self.records.keys() {
+			if let Some(phase) = self.phase_at(id, now) {
+				*counts.entry(phase.as_str()).or_insert(0) += 1;
+			}
+		}
+		counts
+	}
+
+	/// Start a job and return its id.
+	pub fn submit(&mut self) -> String {
+		self.submit_at(Instant::now())
+	}
+
+	fn submit_at(&mut self, now: Instant) -> String {
+		self.sweep(now);
+
+		let profile = &self.job.error_profile;
+		let outcome = if should_error(profile) {
+			JobOutcome::Failed {
+				status: if profile.error_in_payload { 200 } else { pick_error_status(profile) },
+				body: profile.body.clone(),
+			}
+		} else {
+			JobOutcome::Succeeded
+		};
+		let id = random_uuid();
+		self.records.insert(id.clone(), JobRecord {
+			submitted_at: now,
+			pending: Duration::from_secs_f64(self.job.pending_ms.max(0.0) / 1000.0),
+			duration: sample_latency(&self.job.duration),
+			outcome,
+		});
+		id
+	}
+
+	pub fn phase(&self, id: &str) -> Option<JobPhase> {
+		self.phase_at(id, Instant::now())
+	}
+
+	fn phase_at(&self, id: &str, now: Instant) -> Option<JobPhase> {
+		let record = self.records.get(id)?;
+		let elapsed = now.saturating_duration_since(record.submitted_at);
+		Some(if elapsed >= record.duration {
+			match record.outcome {
+				JobOutcome::Succeeded => JobPhase::Succeeded,
+				JobOutcome::Failed { .. } => JobPhase::Failed,
+			}
+		} else if elapsed < record.pending {
+			JobPhase::Pending
+		} else {
+			JobPhase::Running
+		})
+	}
+
+	/// Drop finished jobs past their TTL, then the oldest beyond `max_jobs`.
+	fn sweep(&mut self, now: Instant) {
+		let ttl = Duration::from_secs_f64(self.job.ttl_ms.max(0.0) / 1000.0);
+		self.records
+			.retain(|_, record| now.saturating_duration_since(record.submitted_at) < record.duration + ttl);
+
+		while self.records.len() >= self.job.max_jobs.max(1) {
+			let oldest = self
+				.records
+				.iter()
+				.min_by_key(|(_, record)| record.submitted_at)
+				.map(|(id, _)| id.clone());
+			match oldest {
+				Some(id) => self.records.remove(&id),
+				None => break,
+			};
+		}
+	}
+}
+
+/// Run the endpoint's job operation.
+pub fn apply_job(
+	queue: &mut JobQueue,
+	endpoint: &Endpoint,
+	binding: &JobBinding,
+	request: &RequestContext,
+) -> JobResponse {
+	match binding.operation {
+		JobOperation::Submit => {
+			let id = queue.submit();
+			let location = match &binding.location {
+				Some(template) => template.replace("{id}", &id),
+				None => format!("{}/{}", request.path.trim_end_matches('/'), id),
+			};
+			JobResponse {
+				status: 202,
+				headers: HashMap::from([("Location".to_string(), location)]),
+				body: json!({ "job_id": id, "status": JobPhase::Pending.as_str() }).to_string(),
+			}
+		}
+		JobOperation::Status => {
+			let id = binding
+				.id_param
+				.as_deref()
+				.or_else(|| endpoint.path_params().last().copied())
+				.and_then(|name| request.path_params.get(name))
+				.map(String::as_str)
+				.unwrap_or_default();
+			job_status(queue, id)
+		}
+	}
+}
+
+fn job_status(queue: &JobQueue, id: &str) -> JobResponse {
+	let phase = queue.phase(id);
+	let outcome = queue.records.get(id).map(|record| &record.outcome);
+	match (phase, outcome) {
+		(Some(JobPhase::Succeeded), _) => JobResponse {
+			status: queue.job.result.status,
+			headers: queue.job.result.headers.clone(),
+			body: queue.job.result.body.clone(),
+		},
+		(Some(JobPhase::Failed), Some(JobOutcome::Failed { status, body })) => JobResponse {
+			status: *status,
+			headers: HashMap::new(),
+			body: body.clone(),
+		},
+		(Some(phase), _) => JobResponse {
+			status: 200,
+			headers: HashMap::new(),
+			body: json!({ "job_id": id, "status": phase.as_str() }).to_string(),
+		},
+		(None, _) => JobResponse {
+			status: 404,
+			headers: HashMap::new(),
+			body: json!({ "error": format!("Job '{}' not found", id) }).to_string(),
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{DistributionParams, DistributionType, ErrorProfile, LatencyConfig, Response};
+
+	fn queue(error_rate: f64, max_jobs: usize) -> JobQueue {
+		JobQueue::new(Job {
+			id: "export".to_string(),
+			duration: LatencyConfig {
+				distribution: DistributionType::Fixed,
+				params: DistributionParams::Fixed { delay_ms: 1000.0 },
+			},
+			pending_ms: 200.0,
+			result: Response {
+				status: 200,
+				headers: HashMap::new(),
+				body: "done".to_string(),
+			},
+			error_profile: ErrorProfile {
+				rate: error_rate,
+				codes: vec![502],
+				..ErrorProfile::default()
+			},
+			ttl_ms: 5000.0,
+			max_jobs,
+		})
+	}
+
+	#[test]
+	fn test_phases_follow_sampled_duration() {
+		let mut jobs = queue(0.0, 10);
+		let start = Instant::now();
+		let id = jobs.submit_at(start);
+
+		let at = |ms| start + Duration::from_millis(ms);
+		assert_eq!(jobs.phase_at(&id, at(100)), Some(JobPhase::Pending));
+		assert_eq!(jobs.phase_at(&id, at(500)), Some(JobPhase::Running));
+		assert_eq!(jobs.phase_at(&id, at(1000)), Some(JobPhase::Succeeded));
+		assert_eq!(jobs.phase_at("missing", at(0)), None);
+
+		let mut failing = queue(1.0, 10);
+		let id = failing.submit_at(start);
+		assert_eq!(failing.phase_at(&id, at(1500)), Some(JobPhase::Failed));
+	}
+
+	#[test]
+	fn test_sweep_drops_expired_and_oldest_jobs() {
+		let mut jobs = queue(0.0, 2);
+		let start = Instant::now();
+		let first = jobs.submit_at(start);
+		let second = jobs.submit_at(start + Duration::from_millis(10));
+		let third = jobs.submit_at(start + Duration::from_millis(20));
+		assert_eq!(jobs.phase_at(&first, start), None);
+		assert!(jobs.phase_at(&second, start).is_some());
+
+		jobs.submit_at(start + Duration::from_secs(10));
+		assert_eq!(jobs.len(), 1);
+		assert_eq!(jobs.phase_at(&third, start), None);
+	}
+}
@@ -1,22 +1,42 @@
 // Engine module - serves simulated endpoints on port 8080
 
 mod router;
+mod auth;
+mod callbacks;
+mod capacity;
 mod handler;
+mod idempotency;
+mod jobs;
+mod proxy;
+mod recording;
 mod server;
 mod registry;
+mod request;
+mod resources;
 mod response;
+mod timeline;
+mod workflow;
 
+pub(crate) use router::match_path;
+pub use auth::*;
+pub use callbacks::*;
+pub use capacity::*;
 pub use handler::*;
+pub use idempotency::*;
+pub use jobs::*;
+pub use proxy::*;
+pub use recording::*;
 pub use server::*;
 pub use registry::*;
+pub use request::*;
+pub use resources::*;
+pub use timeline::*;
+pub use workflow::*;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_placeholder() {
         // TODO: Phase 1.4-1.6 - Add engine tests
-        assert!(true);
     }
 }
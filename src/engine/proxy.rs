@@ -0,0 +1,133 @@
+// Reverse proxy
+//
+// Forwards requests to a real upstream so the simulator's latency and faults
+// can be layered on its responses
+
+use crate::config::{HttpMethod, ProxyTarget};
+use crate::engine::{method_to_string, RequestContext};
+use axum::body::Bytes;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Connection-level headers that must not be forwarded in either direction.
+const HOP_BY_HOP: &[&str] = &[
+	"connection",
+	"keep-alive",
+	"proxy-authenticate",
+	"proxy-authorization",
+	"te",
+	"trailer",
+	"transfer-encoding",
+	"upgrade",
+	"host",
+	"content-length",
+];
+
+/// Not forwarded, so the upstream answers uncompressed and faults and
+/// recordings apply to the plain body.
+const ACCEPT_ENCODING: &str = "accept-encoding";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpstreamResponse {
+	pub status: u16,
+	/// Repeated headers are joined with ", ", except `Set-Cookie`, whose
+	/// values are kept one per line.
+	pub headers: HashMap<String, String>,
+	pub body: Bytes,
+}
+
+/// Redirects are passed back to the client rather than followed.
+fn client() -> &'static reqwest::Client {
+	static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+	CLIENT.get_or_init(|| {
+		reqwest::Client::builder()
+			.redirect(reqwest::redirect::Policy::none())
+			.build()
+			.unwrap_or_default()
+	})
+}
+
+/// Send the request to the upstream. Failures to reach it become 502, or 504
+/// on timeout.
+pub async fn forward(target: &ProxyTarget, method: &HttpMethod, request: &RequestContext) -> UpstreamResponse {
+	let method = request.method.as_ref().unwrap_or(method);
+	let method = reqwest::Method::from_bytes(method_to_string(method).as_bytes()).unwrap_or(reqwest::Method::GET);
+	let mut builder = client()
+		.request(method, upstream_url(&target.upstream, &request.path, request.raw_query.as_deref()))
+		.timeout(Duration::from_secs_f64(target.timeout_ms.max(1.0) / 1000.0))
+		.body(request.raw_body.clone());
+	for (name, value) in &request.headers {
+		if !is_hop_by_hop(name.as_str()) && name.as_str() != ACCEPT_ENCODING {
+			builder = builder.header(name.as_str(), value.as_bytes());
+		}
+	}
+
+	let response = match builder.send().await {
+		Ok(response) => response,
+		Err(err) => return upstream_error(&err),
+	};
+	let status = response.status().as_u16();
+	let mut headers: HashMap<String, String> = HashMap::new();
+	for (name, value) in response.headers() {
+		if is_hop_by_hop(name.as_str()) {
+			continue;
+		}
+		let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+		let separator = if name == reqwest::header::SET_COOKIE { "\n" } else { ", " };
+		headers
+			.entry(name.as_str().to_string())
+			.and_modify(|existing| {
+				existing.push_str(separator);
+				existing.push_str(&value);
+			})
+			.or_insert(value);
+	}
+	match response.bytes().await {
+		Ok(body) => UpstreamResponse { status, headers, body },
+		Err(err) => upstream_error(&err),
+	}
+}
+
+fn upstream_error(err: &reqwest::Error) -> UpstreamResponse {
+	let (status, message) = if err.is_timeout() {
+		(504, "upstream timed out".to_string())
+	} else {
+		(502, format!("upstream unavailable: {}", err))
+	};
+	UpstreamResponse {
+		status,
+		headers: HashMap::from([("Content-Type".to_string(), "application/json".to_string())]),
+		body: json!({ "error": message }).to_string().into(),
+	}
+}
+
+fn is_hop_by_hop(name: &str) -> bool {
+	HOP_BY_HOP.iter().any(|hop| name.eq_ignore_ascii_case(hop))
+}
+
+fn upstream_url(upstream: &str, path: &str, query: Option<&str>) -> String {
+	let mut url = format!("{}{}", upstream.trim_end_matches('/'), path);
+	if let Some(query) = query.filter(|query| !query.is_empty()) {
+		url.push('?');
+		url.push_str(query);
+	}
+	url
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_upstream_url_joins_path_and_query() {
+		assert_eq!(upstream_url("http://api.local/v1/", "/users", None), "http://api.local/v1/users");
+		assert_eq!(
+			upstream_url("http://api.local", "/search", Some("tag=b&tag=a&q=a%20b")),
+			"http://api.local/search?tag=b&tag=a&q=a%20b"
+		);
+		assert!(is_hop_by_hop("Transfer-Encoding"));
+		assert!(!is_hop_by_hop("content-type"));
+	}
+}
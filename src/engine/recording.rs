@@ -0,0 +1,99 @@
+// Traffic recording
+//
+// Exchanges forwarded by a recording proxy, grouped by route and turned into
+// a configuration with latencies fitted from what the upstream did
+
+use crate::config::{Configuration, HttpMethod, Metadata, Response};
+use crate::engine::UpstreamResponse;
+use crate::import::{RouteSummary, Traffic};
+
+/// Routes seen so far, with id-like path segments templated.
+#[derive(Debug)]
+pub struct Recording {
+	upstream: Option<String>,
+	traffic: Traffic,
+}
+
+impl Default for Recording {
+	fn default() -> Self {
+		Self {
+			upstream: None,
+			traffic: Traffic::new(true),
+		}
+	}
+}
+
+impl Recording {
+	/// Add one forwarded exchange and the time the upstream took to answer it.
+	pub fn record(&mut self, upstream: &str, method: &HttpMethod, path: &str, response: &UpstreamResponse, latency_ms: f64) {
+		self.upstream = Some(upstream.to_string());
+		// Binary bodies cannot be configured, so they are left out.
+		let response = Response {
+			status: response.status,
+			headers: response.headers.clone(),
+			body: String::from_utf8(response.body.to_vec()).unwrap_or_default(),
+		};
+		self.traffic.add(method, path, &response, latency_ms);
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.traffic.is_empty()
+	}
+
+	pub fn clear(&mut self) {
+		self.traffic.clear();
+	}
+
+	pub fn summary(&self) -> Vec<RouteSummary> {
+		self.traffic.summary()
+	}
+
+	/// Exchanges not recorded because the route limit was reached.
+	pub fn dropped(&self) -> u64 {
+		self.traffic.dropped()
+	}
+
+	/// One endpoint per recorded route.
+	pub fn configuration(&self) -> Configuration {
+		self.traffic.configuration(Metadata {
+			name: Some("recorded".to_string()),
+			description: self.upstream.as_ref().map(|upstream| format!("Recorded from {}", upstream)),
+			author: None,
+			created: Some(chrono::Utc::now().to_rfc3339()),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use axum::body::Bytes;
+	use std::collections::HashMap;
+
+	fn response(body: impl Into<Bytes>) -> UpstreamResponse {
+		UpstreamResponse {
+			status: 200,
+			headers: HashMap::new(),
+			body: body.into(),
+		}
+	}
+
+	#[test]
+	fn test_recording_names_the_upstream_and_drops_binary_bodies() {
+		let mut recording = Recording::default();
+		assert!(recording.is_empty());
+		assert_eq!(recording.configuration().metadata.description, None);
+
+		recording.record("http://api.local", &HttpMethod::Get, "/users/1", &response(r#"{"id": 1}"#), 40.0);
+		recording.record("http://api.local", &HttpMethod::Get, "/avatars/1", &response(vec![0x89, 0x50, 0xff]), 20.0);
+
+		let config = recording.configuration();
+		assert_eq!(config.metadata.name.as_deref(), Some("recorded"));
+		assert_eq!(config.metadata.description.as_deref(), Some("Recorded from http://api.local"));
+		let bodies: Vec<_> = config.endpoints.iter().map(|endpoint| endpoint.response.body.as_str()).collect();
+		assert_eq!(bodies, vec![r#"{"id": 1}"#, ""]);
+
+		recording.clear();
+		assert!(recording.is_empty());
+	}
+}
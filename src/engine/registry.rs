@@ -1,8 +1,14 @@
 // Endpoint registry
 
 use crate::config::{
-	BehaviorWindow, BurstEvent, Configuration, Endpoint, EndpointGroup, HttpMethod, TokenBucket,
+	AuthConfig, BehaviorProfile, BehaviorWindow, BurstEvent, Configuration, CronSchedule, Endpoint, EndpointGroup,
+	HttpMethod, Job, ProxyTarget, RateLimit, RateLimiter, Resource, ScheduleMode, Workflow,
 };
+use crate::engine::router::match_path;
+use crate::engine::{
+	Authenticator, CallbackLog, CapacityPool, IdempotencyStore, JobQueue, PoolOwner, Recording, ResourceStore, WorkflowState,
+};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
@@ -26,18 +32,71 @@ impl RouteKey {
 pub struct EndpointBehaviors {
 	pub windows: Vec<BehaviorWindow>,
 	pub bursts: Vec<BurstEvent>,
+	pub profiles: Vec<BehaviorProfile>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ResolvedEndpoint {
 	pub endpoint: Endpoint,
 	pub behaviors: EndpointBehaviors,
+	/// Group buckets in declaration order, then the global bucket.
+	pub shared_rate_limiters: Vec<Arc<Mutex<RateLimiter>>>,
+	/// Concurrency limits, endpoint first, then shared group limits.
+	pub concurrency: Vec<Arc<CapacityPool>>,
+	/// Capacity models, endpoint first, then shared group models.
+	pub capacity: Vec<Arc<CapacityPool>>,
+	/// Collection the endpoint's resource operation runs against.
+	pub resource: Option<Arc<Mutex<ResourceStore>>>,
+	/// State of the workflow the endpoint's variants follow.
+	pub workflow: Option<Arc<Mutex<WorkflowState>>>,
+	/// Jobs the endpoint submits or reports on.
+	pub job: Option<Arc<Mutex<JobQueue>>>,
+	/// Token issuer and checker, for endpoints with `auth` set.
+	pub auth: Option<Arc<Mutex<Authenticator>>>,
+	/// Keys seen by the endpoint, when `idempotency` is set.
+	pub idempotency: Option<Arc<Mutex<IdempotencyStore>>>,
+	/// Endpoints named in `dependencies`, each with its own dependencies attached.
+	pub dependencies: Vec<ResolvedEndpoint>,
+	/// Delivery log, for endpoints with `callbacks`.
+	pub callbacks: Option<Arc<Mutex<CallbackLog>>>,
+	/// Upstream serving the endpoint, its own or its group's.
+	pub proxy: Option<ProxyTarget>,
+	/// Where forwarded exchanges are captured, for a recording proxy.
+	pub recording: Option<Arc<Mutex<Recording>>>,
+}
+
+/// Endpoints that run a behavior window on the same clock.
+#[derive(Debug, Clone)]
+pub struct WindowClock {
+	pub loaded_at: Option<Instant>,
+	pub endpoint_ids: Vec<String>,
+}
+
+/// How a reload treats runtime state (limiter tokens, behavior clocks, pool counters).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadMode {
+	/// Carry state over wherever the config driving it is unchanged.
+	Preserve,
+	/// Rebuild all state as if the configuration were loaded for the first time.
+	Reset,
 }
 
 #[derive(Debug, Default)]
 struct RegistryInner {
 	endpoints: Vec<ResolvedEndpoint>,
 	by_route: HashMap<RouteKey, ResolvedEndpoint>,
+	loaded_at: Option<Instant>,
+	behaviors: Option<EndpointBehaviors>,
+	groups: Vec<GroupLimits>,
+	global_rate_limiter: Option<(RateLimit, Arc<Mutex<RateLimiter>>)>,
+	resources: Vec<Arc<Mutex<ResourceStore>>>,
+	workflows: Vec<Arc<Mutex<WorkflowState>>>,
+	jobs: Vec<Arc<Mutex<JobQueue>>>,
+	auth: Option<Arc<Mutex<Authenticator>>>,
+	callbacks: Arc<Mutex<CallbackLog>>,
+	recording: Arc<Mutex<Recording>>,
+	/// Serves requests no endpoint matches, when a catch-all proxy is set.
+	fallthrough: Option<ResolvedEndpoint>,
 }
 
 #[derive(Debug, Default)]
@@ -52,32 +111,186 @@ impl EndpointRegistry {
 		}
 	}
 
+	/// Load a configuration, keeping runtime state for unchanged endpoints.
 	pub fn set_config(&self, config: Configuration) {
-		let loaded_at = Instant::now();
+		self.load_config(config, ReloadMode::Preserve);
+	}
+
+	pub fn load_config(&self, mut config: Configuration, mode: ReloadMode) {
+		let now = Instant::now();
+		let previous = {
+			let inner = self.inner.read().expect("registry read lock");
+			match mode {
+				ReloadMode::Preserve => Previous {
+					endpoints: inner.endpoints.clone(),
+					loaded_at: inner.loaded_at,
+					behaviors: inner.behaviors.clone(),
+					groups: inner.groups.clone(),
+					global_rate_limiter: inner.global_rate_limiter.clone(),
+					resources: inner.resources.clone(),
+					workflows: inner.workflows.clone(),
+					jobs: inner.jobs.clone(),
+					auth: inner.auth.clone(),
+					callbacks: Some(Arc::clone(&inner.callbacks)),
+					recording: Some(Arc::clone(&inner.recording)),
+					fallthrough: inner.fallthrough.clone(),
+				},
+				ReloadMode::Reset => Previous::default(),
+			}
+		};
+
+		for window in &mut config.behavior_windows {
+			window.schedule.parsed_cron = parse_cron(window);
+		}
+		let config_behaviors = EndpointBehaviors {
+			windows: config.behavior_windows.clone(),
+			bursts: config.burst_events.clone(),
+			profiles: config.behavior_profiles.clone(),
+		};
+		let loaded_at = previous
+			.loaded_at
+			.filter(|_| {
+				previous
+					.behaviors
+					.as_ref()
+					.is_some_and(|behaviors| same_behaviors(behaviors, &config_behaviors))
+			})
+			.unwrap_or(now);
+
 		let group_index = build_group_index(&config.endpoint_groups);
+		let group_limits = build_group_limits(&config.endpoint_groups, &previous.groups);
+		let global_rate_limiter = config.rate_limit.as_ref().map(|limit| {
+			let limiter = previous
+				.global_rate_limiter
+				.as_ref()
+				.filter(|(previous_limit, _)| unchanged(previous_limit, limit))
+				.map(|(_, limiter)| Arc::clone(limiter))
+				.unwrap_or_else(|| Arc::new(Mutex::new(RateLimiter::new(limit))));
+			(limit.clone(), limiter)
+		});
+		let resources = build_resources(&config.resources, &previous.resources);
+		let workflows = build_workflows(&config.workflows, &previous.workflows);
+		let jobs = build_jobs(&config.jobs, &previous.jobs);
+		let auth = build_auth(config.auth.as_ref(), previous.auth.as_ref());
+		let callbacks = previous.callbacks.unwrap_or_default();
+		let recording = previous.recording.unwrap_or_default();
 		let mut resolved = Vec::new();
 
 		for mut endpoint in config.endpoints {
-			endpoint.loaded_at = Some(loaded_at);
-			endpoint.rate_limiter = endpoint.rate_limit.as_ref().map(|limit| {
-				Arc::new(Mutex::new(TokenBucket::new(
-					limit.requests_per_second,
-					limit.burst,
-				)))
-			});
-
 			let behaviors = resolve_behaviors(
 				&endpoint,
 				&config.behavior_windows,
 				&config.burst_events,
+				&config.behavior_profiles,
 				&group_index,
 			);
+			let old = previous
+				.endpoints
+				.iter()
+				.find(|old| old.endpoint.id == endpoint.id);
+
+			endpoint.loaded_at = old
+				.filter(|old| same_behaviors(&old.behaviors, &behaviors))
+				.and_then(|old| old.endpoint.loaded_at)
+				.or(Some(now));
+			endpoint.rate_limiter = endpoint.rate_limit.as_ref().map(|limit| {
+				old.filter(|old| unchanged(&old.endpoint.rate_limit, &endpoint.rate_limit))
+					.and_then(|old| old.endpoint.rate_limiter.clone())
+					.unwrap_or_else(|| Arc::new(Mutex::new(RateLimiter::new(limit))))
+			});
+
+			let (concurrency, capacity) = resolve_pools(&endpoint, old, &group_limits);
+			let mut shared_rate_limiters: Vec<_> = group_limits
+				.iter()
+				.filter(|limits| limits.group.endpoint_ids.contains(&endpoint.id))
+				.filter_map(|limits| limits.rate_limiter.clone())
+				.collect();
+			shared_rate_limiters.extend(global_rate_limiter.as_ref().map(|(_, limiter)| Arc::clone(limiter)));
+			let resource = endpoint.resource.as_ref().and_then(|binding| {
+				resources
+					.iter()
+					.find(|store| store.lock().expect("resource lock").resource().id == binding.collection)
+					.cloned()
+			});
+			let workflow = endpoint.workflow.as_ref().and_then(|binding| {
+				workflows
+					.iter()
+					.find(|state| state.lock().expect("workflow lock").workflow().id == binding.workflow)
+					.cloned()
+			});
+			let job = endpoint.job.as_ref().and_then(|binding| {
+				jobs.iter()
+					.find(|queue| queue.lock().expect("job lock").job().id == binding.job)
+					.cloned()
+			});
+			let endpoint_auth = endpoint.auth.and(auth.clone());
+			let endpoint_callbacks = (!endpoint.callbacks.is_empty()).then(|| Arc::clone(&callbacks));
+			let proxy = endpoint.proxy.clone().or_else(|| {
+				group_limits
+					.iter()
+					.filter(|limits| limits.group.endpoint_ids.contains(&endpoint.id))
+					.find_map(|limits| limits.group.proxy.clone())
+			});
+			let recording = proxy.as_ref().filter(|proxy| proxy.record).map(|_| Arc::clone(&recording));
+			let idempotency = endpoint.idempotency.as_ref().map(|config| {
+				old.filter(|old| unchanged(&old.endpoint.idempotency, &endpoint.idempotency))
+					.and_then(|old| old.idempotency.clone())
+					.unwrap_or_else(|| Arc::new(Mutex::new(IdempotencyStore::new(config.clone()))))
+			});
 
 			resolved.push(ResolvedEndpoint {
 				endpoint,
 				behaviors,
+				shared_rate_limiters,
+				concurrency,
+				capacity,
+				resource,
+				workflow,
+				job,
+				auth: endpoint_auth,
+				idempotency,
+				dependencies: Vec::new(),
+				callbacks: endpoint_callbacks,
+				proxy,
+				recording,
 			});
 		}
+		let fallthrough = config.proxy.as_ref().map(|proxy| {
+			let mut endpoint = proxy.endpoint();
+			let behaviors = resolve_behaviors(
+				&endpoint,
+				&config.behavior_windows,
+				&config.burst_events,
+				&config.behavior_profiles,
+				&group_index,
+			);
+			endpoint.loaded_at = previous
+				.fallthrough
+				.as_ref()
+				.filter(|old| same_behaviors(&old.behaviors, &behaviors))
+				.and_then(|old| old.endpoint.loaded_at)
+				.or(Some(now));
+			ResolvedEndpoint {
+				endpoint,
+				behaviors,
+				shared_rate_limiters: global_rate_limiter
+					.iter()
+					.map(|(_, limiter)| Arc::clone(limiter))
+					.collect(),
+				concurrency: vec![],
+				capacity: vec![],
+				resource: None,
+				workflow: None,
+				job: None,
+				auth: None,
+				idempotency: None,
+				dependencies: vec![],
+				callbacks: None,
+				proxy: Some(proxy.target()),
+				recording: proxy.record.then(|| Arc::clone(&recording)),
+			}
+		});
+		let resolved = attach_dependencies(resolved);
 
 		let mut by_route = HashMap::new();
 		for item in &resolved {
@@ -88,6 +301,17 @@ impl EndpointRegistry {
 		let mut inner = self.inner.write().expect("registry write lock");
 		inner.endpoints = resolved;
 		inner.by_route = by_route;
+		inner.loaded_at = Some(loaded_at);
+		inner.behaviors = Some(config_behaviors);
+		inner.groups = group_limits;
+		inner.global_rate_limiter = global_rate_limiter;
+		inner.resources = resources;
+		inner.workflows = workflows;
+		inner.jobs = jobs;
+		inner.auth = auth;
+		inner.callbacks = callbacks;
+		inner.recording = recording;
+		inner.fallthrough = fallthrough;
 	}
 
 	#[allow(dead_code)]
@@ -96,19 +320,31 @@ impl EndpointRegistry {
 		let mut resolved = Vec::new();
 		for mut endpoint in endpoints {
 			endpoint.loaded_at = Some(loaded_at);
-			endpoint.rate_limiter = endpoint.rate_limit.as_ref().map(|limit| {
-				Arc::new(Mutex::new(TokenBucket::new(
-					limit.requests_per_second,
-					limit.burst,
-				)))
-			});
+			endpoint.rate_limiter = endpoint
+				.rate_limit
+				.as_ref()
+				.map(|limit| Arc::new(Mutex::new(RateLimiter::new(limit))));
+			let (concurrency, capacity) = resolve_pools(&endpoint, None, &[]);
 
 			resolved.push(ResolvedEndpoint {
 				endpoint,
 				behaviors: EndpointBehaviors {
 					windows: vec![],
 					bursts: vec![],
+					profiles: vec![],
 				},
+				shared_rate_limiters: vec![],
+				concurrency,
+				capacity,
+				resource: None,
+				workflow: None,
+				job: None,
+				auth: None,
+				idempotency: None,
+				dependencies: vec![],
+				callbacks: None,
+				proxy: None,
+				recording: None,
 			});
 		}
 
@@ -119,8 +355,12 @@ impl EndpointRegistry {
 		}
 
 		let mut inner = self.inner.write().expect("registry write lock");
-		inner.endpoints = resolved;
-		inner.by_route = by_route;
+		*inner = RegistryInner {
+			endpoints: resolved,
+			by_route,
+			loaded_at: Some(loaded_at),
+			..RegistryInner::default()
+		};
 	}
 
 	pub fn get(&self, method: &HttpMethod, path: &str) -> Option<ResolvedEndpoint> {
@@ -129,7 +369,102 @@ impl EndpointRegistry {
 		inner.by_route.get(&key).cloned()
 	}
 
-	#[allow(dead_code)]
+	/// Find the endpoint serving a request path: exact paths first, then
+	/// `{param}` templates in declaration order.
+	pub fn route(&self, method: &HttpMethod, path: &str) -> Option<(ResolvedEndpoint, HashMap<String, String>)> {
+		let inner = self.inner.read().expect("registry read lock");
+		if let Some(found) = inner.by_route.get(&RouteKey::new(method, path)) {
+			return Some((found.clone(), HashMap::new()));
+		}
+		inner
+			.endpoints
+			.iter()
+			.filter(|resolved| resolved.endpoint.method == *method && resolved.endpoint.path.contains('{'))
+			.find_map(|resolved| Some((resolved.clone(), match_path(&resolved.endpoint.path, path)?)))
+	}
+
+	pub fn resources(&self) -> Vec<Arc<Mutex<ResourceStore>>> {
+		let inner = self.inner.read().expect("registry read lock");
+		inner.resources.clone()
+	}
+
+	pub fn workflows(&self) -> Vec<Arc<Mutex<WorkflowState>>> {
+		let inner = self.inner.read().expect("registry read lock");
+		inner.workflows.clone()
+	}
+
+	pub fn workflow(&self, id: &str) -> Option<Arc<Mutex<WorkflowState>>> {
+		self.workflows()
+			.into_iter()
+			.find(|state| state.lock().expect("workflow lock").workflow().id == id)
+	}
+
+	pub fn jobs(&self) -> Vec<Arc<Mutex<JobQueue>>> {
+		let inner = self.inner.read().expect("registry read lock");
+		inner.jobs.clone()
+	}
+
+	/// Catch-all proxy endpoint for unmatched requests.
+	pub fn fallthrough(&self) -> Option<ResolvedEndpoint> {
+		let inner = self.inner.read().expect("registry read lock");
+		inner.fallthrough.clone()
+	}
+
+	pub fn callbacks(&self) -> Arc<Mutex<CallbackLog>> {
+		let inner = self.inner.read().expect("registry read lock");
+		Arc::clone(&inner.callbacks)
+	}
+
+	/// Traffic captured by a recording proxy. Reloads keep it; a runtime reset clears it.
+	pub fn recording(&self) -> Arc<Mutex<Recording>> {
+		let inner = self.inner.read().expect("registry read lock");
+		Arc::clone(&inner.recording)
+	}
+
+	pub fn resource(&self, id: &str) -> Option<Arc<Mutex<ResourceStore>>> {
+		self.resources()
+			.into_iter()
+			.find(|store| store.lock().expect("resource lock").resource().id == id)
+	}
+
+	/// Every configured window with the endpoints it applies to, grouped by
+	/// the clock each endpoint runs its behaviors on. A partial reload leaves
+	/// unchanged endpoints on their old clock, so one window can run on
+	/// several timelines. Windows that apply to no endpoint get the registry clock.
+	pub fn window_clocks(&self) -> Vec<(BehaviorWindow, Vec<WindowClock>)> {
+		let inner = self.inner.read().expect("registry read lock");
+		let Some(behaviors) = &inner.behaviors else {
+			return Vec::new();
+		};
+		behaviors
+			.windows
+			.iter()
+			.map(|window| {
+				let mut clocks: Vec<WindowClock> = Vec::new();
+				let endpoints = inner.endpoints.iter().chain(inner.fallthrough.as_ref());
+				for resolved in endpoints.filter(|resolved| {
+					resolved.behaviors.windows.iter().any(|applied| unchanged(applied, window))
+				}) {
+					let loaded_at = resolved.endpoint.loaded_at;
+					match clocks.iter_mut().find(|clock| clock.loaded_at == loaded_at) {
+						Some(clock) => clock.endpoint_ids.push(resolved.endpoint.id.clone()),
+						None => clocks.push(WindowClock {
+							loaded_at,
+							endpoint_ids: vec![resolved.endpoint.id.clone()],
+						}),
+					}
+				}
+				if clocks.is_empty() {
+					clocks.push(WindowClock {
+						loaded_at: inner.loaded_at,
+						endpoint_ids: Vec::new(),
+					});
+				}
+				(window.clone(), clocks)
+			})
+			.collect()
+	}
+
 	pub fn list(&self) -> Vec<ResolvedEndpoint> {
 		let inner = self.inner.read().expect("registry read lock");
 		inner.endpoints.clone()
@@ -144,14 +479,238 @@ fn build_group_index(groups: &[EndpointGroup]) -> HashMap<String, Vec<String>> {
 	index
 }
 
+/// Runtime state from the configuration being replaced.
+#[derive(Default)]
+struct Previous {
+	endpoints: Vec<ResolvedEndpoint>,
+	loaded_at: Option<Instant>,
+	behaviors: Option<EndpointBehaviors>,
+	groups: Vec<GroupLimits>,
+	global_rate_limiter: Option<(RateLimit, Arc<Mutex<RateLimiter>>)>,
+	resources: Vec<Arc<Mutex<ResourceStore>>>,
+	workflows: Vec<Arc<Mutex<WorkflowState>>>,
+	jobs: Vec<Arc<Mutex<JobQueue>>>,
+	auth: Option<Arc<Mutex<Authenticator>>>,
+	callbacks: Option<Arc<Mutex<CallbackLog>>>,
+	recording: Option<Arc<Mutex<Recording>>>,
+	fallthrough: Option<ResolvedEndpoint>,
+}
+
+#[derive(Debug, Clone)]
+struct GroupLimits {
+	group: EndpointGroup,
+	rate_limiter: Option<Arc<Mutex<RateLimiter>>>,
+	concurrency: Option<Arc<CapacityPool>>,
+	capacity: Option<Arc<CapacityPool>>,
+}
+
+fn build_group_limits(groups: &[EndpointGroup], previous: &[GroupLimits]) -> Vec<GroupLimits> {
+	groups
+		.iter()
+		.map(|group| {
+			let owner = PoolOwner::Group(group.id.clone());
+			let old = previous.iter().find(|old| old.group.id == group.id);
+			GroupLimits {
+				group: group.clone(),
+				rate_limiter: group.rate_limit.as_ref().map(|limit| {
+					old.filter(|old| unchanged(&old.group.rate_limit, &group.rate_limit))
+						.and_then(|old| old.rate_limiter.clone())
+						.unwrap_or_else(|| Arc::new(Mutex::new(RateLimiter::new(limit))))
+				}),
+				concurrency: group.concurrency_limit.clone().map(|limit| {
+					old.filter(|old| unchanged(&old.group.concurrency_limit, &group.concurrency_limit))
+						.and_then(|old| old.concurrency.clone())
+						.unwrap_or_else(|| Arc::new(CapacityPool::from_limit(owner.clone(), limit)))
+				}),
+				capacity: group.capacity.clone().map(|model| {
+					old.filter(|old| unchanged(&old.group.capacity, &group.capacity))
+						.and_then(|old| old.capacity.clone())
+						.unwrap_or_else(|| Arc::new(CapacityPool::new(owner.clone(), model)))
+				}),
+			}
+		})
+		.collect()
+}
+
+/// Collections keep their items across reloads unless their definition changed.
+fn build_resources(
+	resources: &[Resource],
+	previous: &[Arc<Mutex<ResourceStore>>],
+) -> Vec<Arc<Mutex<ResourceStore>>> {
+	resources
+		.iter()
+		.map(|resource| {
+			previous
+				.iter()
+				.find(|store| unchanged(store.lock().expect("resource lock").resource(), resource))
+				.cloned()
+				.unwrap_or_else(|| Arc::new(Mutex::new(ResourceStore::new(resource.clone()))))
+		})
+		.collect()
+}
+
+/// Workflows keep their current states across reloads unless their definition changed.
+fn build_workflows(
+	workflows: &[Workflow],
+	previous: &[Arc<Mutex<WorkflowState>>],
+) -> Vec<Arc<Mutex<WorkflowState>>> {
+	workflows
+		.iter()
+		.map(|workflow| {
+			previous
+				.iter()
+				.find(|state| unchanged(state.lock().expect("workflow lock").workflow(), workflow))
+				.cloned()
+				.unwrap_or_else(|| Arc::new(Mutex::new(WorkflowState::new(workflow.clone()))))
+		})
+		.collect()
+}
+
+/// Submitted jobs survive reloads unless their job type changed.
+fn build_jobs(jobs: &[Job], previous: &[Arc<Mutex<JobQueue>>]) -> Vec<Arc<Mutex<JobQueue>>> {
+	jobs.iter()
+		.map(|job| {
+			previous
+				.iter()
+				.find(|queue| unchanged(queue.lock().expect("job lock").job(), job))
+				.cloned()
+				.unwrap_or_else(|| Arc::new(Mutex::new(JobQueue::new(job.clone()))))
+		})
+		.collect()
+}
+
+/// Opaque tokens stay valid across reloads unless the auth settings changed.
+fn build_auth(
+	config: Option<&AuthConfig>,
+	previous: Option<&Arc<Mutex<Authenticator>>>,
+) -> Option<Arc<Mutex<Authenticator>>> {
+	let config = config?;
+	previous
+		.filter(|authenticator| unchanged(authenticator.lock().expect("auth lock").config(), config))
+		.cloned()
+		.or_else(|| Some(Arc::new(Mutex::new(Authenticator::new(config.clone())))))
+}
+
+/// Fill in each endpoint's dependencies, deepest first.
+fn attach_dependencies(endpoints: Vec<ResolvedEndpoint>) -> Vec<ResolvedEndpoint> {
+	let mut attached = HashMap::new();
+	endpoints
+		.iter()
+		.filter_map(|item| with_dependencies(&item.endpoint.id, &endpoints, &mut attached, &mut Vec::new()))
+		.collect()
+}
+
+fn with_dependencies(
+	id: &str,
+	endpoints: &[ResolvedEndpoint],
+	attached: &mut HashMap<String, ResolvedEndpoint>,
+	visiting: &mut Vec<String>,
+) -> Option<ResolvedEndpoint> {
+	if let Some(found) = attached.get(id) {
+		return Some(found.clone());
+	}
+	let mut item = endpoints.iter().find(|item| item.endpoint.id == id)?.clone();
+	// The validator rejects cycles; leave the repeated call unresolved if one slips through.
+	if visiting.iter().any(|seen| seen == id) {
+		return None;
+	}
+	visiting.push(id.to_string());
+	if let Some(dependencies) = &item.endpoint.dependencies {
+		item.dependencies = dependencies
+			.calls
+			.iter()
+			.filter_map(|call| with_dependencies(&call.endpoint_id, endpoints, attached, visiting))
+			.collect();
+	}
+	visiting.pop();
+	attached.insert(id.to_string(), item.clone());
+	Some(item)
+}
+
+/// Pools guarding an endpoint: its own first, then those of its groups.
+/// The endpoint's own pools are reused from `old` when their config is unchanged.
+fn resolve_pools(
+	endpoint: &Endpoint,
+	old: Option<&ResolvedEndpoint>,
+	group_limits: &[GroupLimits],
+) -> (Vec<Arc<CapacityPool>>, Vec<Arc<CapacityPool>>) {
+	let owner = PoolOwner::Endpoint(endpoint.id.clone());
+	let own_pool = |pools: &[Arc<CapacityPool>]| {
+		pools.iter().find(|pool| *pool.owner() == owner).cloned()
+	};
+
+	let mut concurrency: Vec<Arc<CapacityPool>> = endpoint
+		.concurrency_limit
+		.clone()
+		.map(|limit| {
+			old.filter(|old| unchanged(&old.endpoint.concurrency_limit, &endpoint.concurrency_limit))
+				.and_then(|old| own_pool(&old.concurrency))
+				.unwrap_or_else(|| Arc::new(CapacityPool::from_limit(owner.clone(), limit)))
+		})
+		.into_iter()
+		.collect();
+	let mut capacity: Vec<Arc<CapacityPool>> = endpoint
+		.capacity
+		.clone()
+		.map(|model| {
+			old.filter(|old| unchanged(&old.endpoint.capacity, &endpoint.capacity))
+				.and_then(|old| own_pool(&old.capacity))
+				.unwrap_or_else(|| Arc::new(CapacityPool::new(owner.clone(), model)))
+		})
+		.into_iter()
+		.collect();
+
+	for limits in group_limits {
+		if !limits.group.endpoint_ids.contains(&endpoint.id) {
+			continue;
+		}
+		concurrency.extend(limits.concurrency.clone());
+		capacity.extend(limits.capacity.clone());
+	}
+
+	(concurrency, capacity)
+}
+
+/// Compare config values by their serialized form.
+fn unchanged<T: Serialize>(previous: &T, current: &T) -> bool {
+	match (serde_json::to_value(previous), serde_json::to_value(current)) {
+		(Ok(previous), Ok(current)) => previous == current,
+		_ => false,
+	}
+}
+
+/// Cron schedules are parsed once per load. Validation rejects bad ones, so a
+/// failure means the configuration skipped it; the window then never activates.
+fn parse_cron(window: &BehaviorWindow) -> Option<Arc<CronSchedule>> {
+	let schedule = &window.schedule;
+	if schedule.mode != ScheduleMode::Cron {
+		return None;
+	}
+	match CronSchedule::parse(schedule.cron.as_deref().unwrap_or_default(), schedule.timezone.as_deref()) {
+		Ok(cron) => Some(Arc::new(cron)),
+		Err(err) => {
+			tracing::warn!("behavior window {:?} never activates: {}", window.id, err);
+			None
+		}
+	}
+}
+
+fn same_behaviors(previous: &EndpointBehaviors, current: &EndpointBehaviors) -> bool {
+	unchanged(&previous.windows, &current.windows)
+		&& unchanged(&previous.bursts, &current.bursts)
+		&& unchanged(&previous.profiles, &current.profiles)
+}
+
 fn resolve_behaviors(
 	endpoint: &Endpoint,
 	behavior_windows: &[BehaviorWindow],
 	burst_events: &[BurstEvent],
+	behavior_profiles: &[BehaviorProfile],
 	group_index: &HashMap<String, Vec<String>>,
 ) -> EndpointBehaviors {
 	let mut windows = Vec::new();
 	let mut bursts = Vec::new();
+	let mut profiles = Vec::new();
 
 	for window in behavior_windows {
 		if scope_matches(endpoint, &window.scope, group_index) {
@@ -165,7 +724,17 @@ fn resolve_behaviors(
 		}
 	}
 
-	EndpointBehaviors { windows, bursts }
+	for profile in behavior_profiles {
+		if scope_matches(endpoint, &profile.scope, group_index) {
+			profiles.push(profile.clone());
+		}
+	}
+
+	EndpointBehaviors {
+		windows,
+		bursts,
+		profiles,
+	}
 }
 
 fn scope_matches(
@@ -187,7 +756,7 @@ fn scope_matches(
 	false
 }
 
-fn method_to_string(method: &HttpMethod) -> &'static str {
+pub(crate) fn method_to_string(method: &HttpMethod) -> &'static str {
 	match method {
 		HttpMethod::Get => "GET",
 		HttpMethod::Post => "POST",
@@ -223,6 +792,17 @@ mod tests {
 			error_profile: ErrorProfile::default(),
 			rate_limit: None,
 			bandwidth_cap: None,
+			capacity: None,
+			concurrency_limit: None,
+			resource: None,
+			workflow: None,
+			job: None,
+			auth: None,
+			required_scopes: vec![],
+			idempotency: None,
+			dependencies: None,
+			callbacks: vec![],
+			proxy: None,
 			loaded_at: None,
 			rate_limiter: None,
 		}
@@ -251,4 +831,84 @@ mod tests {
 		assert_eq!(list.len(), 1);
 		assert_eq!(list[0].endpoint.id, "health");
 	}
+
+	fn limited_config(requests_per_second: u32, body: &str) -> Configuration {
+		crate::config::parse_yaml(&format!(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "orders"
+    method: "GET"
+    path: "/orders"
+    latency: {{ distribution: "fixed", params: {{ delay_ms: 0 }} }}
+    response: {{ status: 200, body: "{body}" }}
+    rate_limit: {{ requests_per_second: {requests_per_second}, burst: 1 }}
+"#
+		))
+		.expect("config")
+	}
+
+	fn drain(registry: &EndpointRegistry) {
+		let resolved = registry.get(&HttpMethod::Get, "/orders").unwrap();
+		resolved.endpoint.rate_limiter.unwrap().lock().unwrap().take(None);
+	}
+
+	fn remaining(registry: &EndpointRegistry) -> f64 {
+		let resolved = registry.get(&HttpMethod::Get, "/orders").unwrap();
+		let limiter = resolved.endpoint.rate_limiter.unwrap();
+		let status = limiter.lock().unwrap().status(None, 0.0).unwrap();
+		status.remaining.floor()
+	}
+
+	#[test]
+	fn test_cron_windows_are_parsed_on_load() {
+		let registry = EndpointRegistry::new();
+		let mut config = limited_config(1, "v1");
+		config.behavior_windows = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints: []
+behavior_windows:
+  - id: "nightly"
+    scope: { global: true }
+    schedule: { mode: "cron", cron: "0 2 * * *", timezone: "Europe/Berlin", duration_ms: 60000 }
+  - id: "unvalidated"
+    scope: { global: true }
+    schedule: { mode: "cron", cron: "0 25 * * *", duration_ms: 60000 }
+"#,
+		)
+		.expect("config")
+		.behavior_windows;
+		registry.set_config(config);
+
+		let resolved = registry.get(&HttpMethod::Get, "/orders").unwrap();
+		let parsed: Vec<_> = resolved.behaviors.windows.iter().map(|window| window.schedule.parsed_cron.is_some()).collect();
+		assert_eq!(parsed, vec![true, false]);
+	}
+
+	#[test]
+	fn test_reload_preserves_unchanged_state() {
+		let registry = EndpointRegistry::new();
+		registry.set_config(limited_config(1, "v1"));
+		drain(&registry);
+		let clock = || registry.get(&HttpMethod::Get, "/orders").unwrap().endpoint.loaded_at;
+		let loaded_at = clock();
+
+		registry.set_config(limited_config(1, "v2"));
+		assert_eq!(remaining(&registry), 0.0);
+		assert_eq!(clock(), loaded_at);
+	}
+
+	#[test]
+	fn test_reload_resets_changed_or_requested_state() {
+		let registry = EndpointRegistry::new();
+		registry.set_config(limited_config(1, "v1"));
+		drain(&registry);
+		registry.set_config(limited_config(2, "v1"));
+		assert_eq!(remaining(&registry), 1.0);
+
+		drain(&registry);
+		registry.load_config(limited_config(2, "v1"), ReloadMode::Reset);
+		assert_eq!(remaining(&registry), 1.0);
+	}
 }
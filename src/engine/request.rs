@@ -0,0 +1,137 @@
+// Incoming request context
+//
+// Request details the handler needs beyond the matched endpoint
+
+use crate::config::{HttpMethod, KeySource, RateLimitKey, SessionSource, WorkflowSession};
+use axum::body::Bytes;
+use axum::http::HeaderMap;
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+	/// Absent for requests built outside the engine server.
+	pub method: Option<HttpMethod>,
+	pub path: String,
+	/// Values of the endpoint's `{param}` path segments.
+	pub path_params: HashMap<String, String>,
+	pub headers: HeaderMap,
+	pub query: HashMap<String, String>,
+	/// Query string as received, for forwarding unchanged.
+	pub raw_query: Option<String>,
+	pub client_ip: Option<IpAddr>,
+	pub body: String,
+	/// Body as received, for forwarding unchanged.
+	pub raw_body: Bytes,
+}
+
+impl RequestContext {
+	#[cfg(test)]
+	pub fn from_body(body: &str) -> Self {
+		Self {
+			body: body.to_string(),
+			..Self::default()
+		}
+	}
+
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.get(name)
+			.and_then(|value| value.to_str().ok())
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+	}
+
+	fn query_param(&self, name: &str) -> Option<&str> {
+		self.query
+			.get(name)
+			.map(String::as_str)
+			.filter(|value| !value.is_empty())
+	}
+
+	/// Client key for per-client rate limiting, if the request carries one.
+	pub fn client_key(&self, key: &RateLimitKey) -> Option<String> {
+		let name = key.name.as_deref();
+		match key.source {
+			KeySource::Header => self.header(name?).map(str::to_string),
+			KeySource::Query => self.query_param(name?).map(str::to_string),
+			KeySource::RemoteIp => self.client_ip.map(|ip| ip.to_string()),
+			KeySource::ApiKey => {
+				let header = match name {
+					Some(name) => self.header(name),
+					None => self.header("x-api-key").or_else(|| {
+						self.header("authorization")
+							.and_then(|value| value.strip_prefix("Bearer "))
+							.map(str::trim)
+					}),
+				};
+				header
+					.or_else(|| self.query_param("api_key"))
+					.map(str::to_string)
+			}
+		}
+	}
+
+	/// Workflow session key from the configured header or cookie.
+	pub fn session_key(&self, session: &WorkflowSession) -> Option<String> {
+		match session.source {
+			SessionSource::Header => self.header(&session.name).map(str::to_string),
+			SessionSource::Cookie => self
+				.headers
+				.get_all("cookie")
+				.iter()
+				.filter_map(|value| value.to_str().ok())
+				.flat_map(|value| value.split(';'))
+				.filter_map(|pair| pair.trim().split_once('='))
+				.find(|(name, _)| *name == session.name)
+				.map(|(_, value)| value.trim().to_string())
+				.filter(|value| !value.is_empty()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::MissingKeyPolicy;
+
+	fn key(source: KeySource, name: Option<&str>) -> RateLimitKey {
+		RateLimitKey {
+			source,
+			name: name.map(str::to_string),
+			max_keys: 10,
+			idle_timeout_ms: 1_000.0,
+			missing: MissingKeyPolicy::Shared,
+		}
+	}
+
+	#[test]
+	fn test_client_key_sources() {
+		let mut request = RequestContext::from_body("");
+		request.headers.insert("X-Tenant", "acme".parse().unwrap());
+		request.headers.insert("Authorization", "Bearer secret".parse().unwrap());
+		request.query.insert("client".to_string(), "cli-1".to_string());
+		request.client_ip = Some("10.0.0.7".parse().unwrap());
+
+		assert_eq!(request.client_key(&key(KeySource::Header, Some("x-tenant"))).as_deref(), Some("acme"));
+		assert_eq!(request.client_key(&key(KeySource::Query, Some("client"))).as_deref(), Some("cli-1"));
+		assert_eq!(request.client_key(&key(KeySource::RemoteIp, None)).as_deref(), Some("10.0.0.7"));
+		assert_eq!(request.client_key(&key(KeySource::ApiKey, None)).as_deref(), Some("secret"));
+		assert_eq!(request.client_key(&key(KeySource::Header, Some("x-missing"))), None);
+	}
+
+	#[test]
+	fn test_session_key_from_cookie() {
+		let mut request = RequestContext::from_body("");
+		request.headers.insert("Cookie", "theme=dark; sid=abc123".parse().unwrap());
+		let session = |source, name: &str| WorkflowSession {
+			source,
+			name: name.to_string(),
+			max_sessions: 10,
+		};
+
+		assert_eq!(request.session_key(&session(SessionSource::Cookie, "sid")).as_deref(), Some("abc123"));
+		assert_eq!(request.session_key(&session(SessionSource::Cookie, "missing")), None);
+		assert_eq!(request.session_key(&session(SessionSource::Header, "x-session")), None);
+	}
+}
@@ -0,0 +1,319 @@
+// Resource collections
+//
+// In-memory JSON collections behind create/read/list/update/delete endpoints
+
+use crate::config::{Endpoint, HttpMethod, IdStrategy, Resource, ResourceBinding, ResourceOperation};
+use crate::engine::RequestContext;
+use rand::Rng;
+use serde_json::{json, Value};
+
+/// Items of one collection, in insertion order.
+#[derive(Debug)]
+pub struct ResourceStore {
+	resource: Resource,
+	items: Vec<Value>,
+	next_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceError {
+	NotFound,
+	Conflict(String),
+	InvalidItem(String),
+}
+
+impl std::fmt::Display for ResourceError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::NotFound => write!(f, "item not found"),
+			Self::Conflict(id) => write!(f, "duplicate id '{}'", id),
+			Self::InvalidItem(message) => write!(f, "{}", message),
+		}
+	}
+}
+
+/// Outcome of an operation, ready to be written as a response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceResponse {
+	pub status: u16,
+	pub body: String,
+	/// Path of a newly created item.
+	pub location: Option<String>,
+}
+
+impl ResourceStore {
+	pub fn new(resource: Resource) -> Self {
+		let mut store = Self {
+			resource,
+			items: Vec::new(),
+			next_id: 1,
+		};
+		store.reset();
+		store
+	}
+
+	pub fn resource(&self) -> &Resource {
+		&self.resource
+	}
+
+	pub fn items(&self) -> &[Value] {
+		&self.items
+	}
+
+	/// Restore the configured seed items.
+	pub fn reset(&mut self) {
+		let seed = self.resource.seed.clone();
+		self.items.clear();
+		self.next_id = 1;
+		// The validator rejects bad seed items; skip any that slip through.
+		for item in seed {
+			let _ = self.create(item);
+		}
+	}
+
+	/// Replace the contents; nothing changes if any item is rejected.
+	pub fn replace(&mut self, items: Vec<Value>) -> Result<(), ResourceError> {
+		let previous = (std::mem::take(&mut self.items), self.next_id);
+		self.next_id = 1;
+		for item in items {
+			if let Err(err) = self.create(item) {
+				(self.items, self.next_id) = previous;
+				return Err(err);
+			}
+		}
+		Ok(())
+	}
+
+	pub fn create(&mut self, mut item: Value) -> Result<Value, ResourceError> {
+		let id_field = self.resource.id_field.clone();
+		let Some(fields) = item.as_object_mut() else {
+			return Err(ResourceError::InvalidItem("item must be a JSON object".to_string()));
+		};
+
+		match fields.get(&id_field) {
+			Some(value) => {
+				let id = id_key(value).ok_or_else(|| {
+					ResourceError::InvalidItem(format!("'{}' must be a string or number", id_field))
+				})?;
+				if self.position(&id).is_some() {
+					return Err(ResourceError::Conflict(id));
+				}
+				if let Some(number) = value.as_u64() {
+					self.next_id = self.next_id.max(number.saturating_add(1));
+				}
+			}
+			None => {
+				let id = self.generate_id()?;
+				fields.insert(id_field, id);
+			}
+		}
+		self.items.push(item.clone());
+		Ok(item)
+	}
+
+	pub fn read(&self, id: &str) -> Result<&Value, ResourceError> {
+		self.position(id)
+			.map(|index| &self.items[index])
+			.ok_or(ResourceError::NotFound)
+	}
+
+	/// Replace an item, or merge top-level fields into it when `merge` is set.
+	/// The stored id always wins over one in the body.
+	pub fn update(&mut self, id: &str, item: Value, merge: bool) -> Result<Value, ResourceError> {
+		let index = self.position(id).ok_or(ResourceError::NotFound)?;
+		let Value::Object(fields) = item else {
+			return Err(ResourceError::InvalidItem("item must be a JSON object".to_string()));
+		};
+
+		let id_field = &self.resource.id_field;
+		let stored = &mut self.items[index];
+		let id_value = stored.get(id_field).cloned().unwrap_or(Value::Null);
+		if merge {
+			if let Some(existing) = stored.as_object_mut() {
+				existing.extend(fields);
+			}
+		} else {
+			*stored = Value::Object(fields);
+		}
+		if let Some(existing) = stored.as_object_mut() {
+			existing.insert(id_field.clone(), id_value);
+		}
+		Ok(stored.clone())
+	}
+
+	pub fn delete(&mut self, id: &str) -> Result<Value, ResourceError> {
+		let index = self.position(id).ok_or(ResourceError::NotFound)?;
+		Ok(self.items.remove(index))
+	}
+
+	fn position(&self, id: &str) -> Option<usize> {
+		self.items.iter().position(|item| {
+			item.get(&self.resource.id_field)
+				.and_then(id_key)
+				.is_some_and(|key| key == id)
+		})
+	}
+
+	/// Next free sequence number, or a UUID. Once `u64::MAX` is taken the
+	/// sequence is exhausted and items need an explicit id.
+	fn generate_id(&mut self) -> Result<Value, ResourceError> {
+		match self.resource.id_strategy {
+			IdStrategy::Sequence => {
+				while self.position(&self.next_id.to_string()).is_some() {
+					self.next_id = self.next_id.checked_add(1).ok_or_else(|| {
+						ResourceError::InvalidItem(format!(
+							"the id sequence is exhausted; give the item an explicit '{}'",
+							self.resource.id_field
+						))
+					})?;
+				}
+				let id = self.next_id;
+				self.next_id = self.next_id.saturating_add(1);
+				Ok(json!(id))
+			}
+			IdStrategy::Uuid => Ok(json!(random_uuid())),
+		}
+	}
+}
+
+/// Ids compare by their string form, so `7` and `"7"` address the same item.
+fn id_key(value: &Value) -> Option<String> {
+	match value {
+		Value::String(id) => Some(id.clone()),
+		Value::Number(id) => Some(id.to_string()),
+		_ => None,
+	}
+}
+
+pub(crate) fn random_uuid() -> String {
+	let mut bytes: [u8; 16] = rand::thread_rng().gen();
+	bytes[6] = (bytes[6] & 0x0f) | 0x40;
+	bytes[8] = (bytes[8] & 0x3f) | 0x80;
+	let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+	format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+/// Run the endpoint's bound operation against the store.
+pub fn apply_operation(
+	store: &mut ResourceStore,
+	endpoint: &Endpoint,
+	binding: &ResourceBinding,
+	request: &RequestContext,
+) -> ResourceResponse {
+	let id = binding
+		.id_param
+		.as_deref()
+		.or_else(|| endpoint.path_params().last().copied())
+		.and_then(|name| request.path_params.get(name))
+		.map(String::as_str)
+		.unwrap_or_default();
+
+	let result = match binding.operation {
+		ResourceOperation::List => Ok((200, Value::Array(store.items().to_vec()), None)),
+		ResourceOperation::Read => store.read(id).map(|item| (200, item.clone(), None)),
+		ResourceOperation::Create => parse_item(&request.body).and_then(|item| {
+			let item = store.create(item)?;
+			let location = item
+				.get(&store.resource().id_field)
+				.and_then(id_key)
+				.map(|id| format!("{}/{}", request.path.trim_end_matches('/'), id));
+			Ok((201, item, location))
+		}),
+		ResourceOperation::Update => parse_item(&request.body).and_then(|item| {
+			let merge = endpoint.method == HttpMethod::Patch;
+			store.update(id, item, merge).map(|item| (200, item, None))
+		}),
+		ResourceOperation::Delete => store.delete(id).map(|_| (204, Value::Null, None)),
+	};
+
+	match result {
+		Ok((status, body, location)) => ResourceResponse {
+			status,
+			body: if status == 204 { String::new() } else { body.to_string() },
+			location,
+		},
+		Err(err) => error_response(&err, &store.resource().id),
+	}
+}
+
+fn parse_item(body: &str) -> Result<Value, ResourceError> {
+	serde_json::from_str(body).map_err(|err| ResourceError::InvalidItem(format!("invalid JSON body: {}", err)))
+}
+
+fn error_response(err: &ResourceError, collection: &str) -> ResourceResponse {
+	let (status, message) = match err {
+		ResourceError::NotFound => (404, format!("Item not found in '{}'", collection)),
+		ResourceError::Conflict(id) => (409, format!("Item '{}' already exists in '{}'", id, collection)),
+		ResourceError::InvalidItem(message) => (400, message.clone()),
+	};
+	ResourceResponse {
+		status,
+		body: json!({ "error": message }).to_string(),
+		location: None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn store(strategy: IdStrategy) -> ResourceStore {
+		ResourceStore::new(Resource {
+			id: "users".to_string(),
+			id_field: "id".to_string(),
+			id_strategy: strategy,
+			seed: vec![json!({"id": 3, "name": "ada"})],
+		})
+	}
+
+	#[test]
+	fn test_create_assigns_ids_and_rejects_duplicates() {
+		let mut users = store(IdStrategy::Sequence);
+		let created = users.create(json!({"name": "grace"})).unwrap();
+		assert_eq!(created["id"], 4);
+		assert_eq!(users.create(json!({"id": "3"})), Err(ResourceError::Conflict("3".to_string())));
+		assert!(matches!(users.create(json!([1])), Err(ResourceError::InvalidItem(_))));
+
+		let mut keyed = store(IdStrategy::Uuid);
+		let created = keyed.create(json!({})).unwrap();
+		assert_eq!(created["id"].as_str().unwrap().len(), 36);
+	}
+
+	#[test]
+	fn test_sequence_ids_near_u64_max() {
+		let mut users = store(IdStrategy::Sequence);
+		users.create(json!({"id": u64::MAX - 1})).unwrap();
+		assert_eq!(users.create(json!({"name": "last"})).unwrap()["id"], json!(u64::MAX));
+		assert!(matches!(users.create(json!({"name": "more"})), Err(ResourceError::InvalidItem(_))));
+		assert!(users.create(json!({"id": "manual", "name": "more"})).is_ok());
+
+		// A client-chosen id moves the sequence past it, here to its end.
+		let mut users = store(IdStrategy::Sequence);
+		users.create(json!({"id": u64::MAX})).unwrap();
+		assert!(matches!(users.create(json!({"name": "more"})), Err(ResourceError::InvalidItem(_))));
+	}
+
+	#[test]
+	fn test_update_delete_and_reset() {
+		let mut users = store(IdStrategy::Sequence);
+		let merged = users.update("3", json!({"id": 9, "role": "admin"}), true).unwrap();
+		assert_eq!(merged, json!({"id": 3, "name": "ada", "role": "admin"}));
+		let replaced = users.update("3", json!({"name": "lovelace"}), false).unwrap();
+		assert_eq!(replaced, json!({"id": 3, "name": "lovelace"}));
+		assert_eq!(users.update("8", json!({}), false), Err(ResourceError::NotFound));
+
+		users.delete("3").unwrap();
+		assert_eq!(users.read("3"), Err(ResourceError::NotFound));
+		users.reset();
+		assert_eq!(users.items().len(), 1);
+	}
+
+	#[test]
+	fn test_replace_is_all_or_nothing() {
+		let mut users = store(IdStrategy::Sequence);
+		assert!(users.replace(vec![json!({"id": 1}), json!({"id": 1})]).is_err());
+		assert_eq!(users.items(), &[json!({"id": 3, "name": "ada"})]);
+		users.replace(vec![json!({"id": 1}), json!({"name": "new"})]).unwrap();
+		assert_eq!(users.items()[1]["id"], 2);
+	}
+}
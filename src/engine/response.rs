@@ -4,14 +4,23 @@ use axum::body::Body;
 use axum::http::{HeaderName, HeaderValue, Response, StatusCode};
 use std::collections::HashMap;
 
-pub fn build_response(status: u16, headers: &HashMap<String, String>, body: &str) -> Response<Body> {
+/// A header value with several lines is sent as one header per line, which
+/// is how repeated headers such as `Set-Cookie` are kept in the map.
+pub fn build_response(status: u16, headers: &HashMap<String, String>, body: impl AsRef<[u8]>) -> Response<Body> {
 	let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-	let mut response = Response::new(Body::from(body.to_string()));
+	let mut response = Response::new(Body::from(body.as_ref().to_vec()));
 	*response.status_mut() = status;
 
 	for (key, value) in headers {
-		if let (Ok(name), Ok(val)) = (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(value)) {
-			response.headers_mut().insert(name, val);
+		let Ok(name) = HeaderName::from_bytes(key.as_bytes()) else {
+			continue;
+		};
+		let mut values = value.split('\n').filter_map(|line| HeaderValue::from_str(line).ok());
+		if let Some(first) = values.next() {
+			response.headers_mut().insert(name.clone(), first);
+		}
+		for value in values {
+			response.headers_mut().append(name.clone(), value);
 		}
 	}
 
@@ -35,5 +44,10 @@ mod tests {
 		let response = build_response(201, &headers, "{}");
 		assert_eq!(response.status(), StatusCode::CREATED);
 		assert_eq!(response.headers().get("Content-Type").unwrap(), "application/json");
+
+		headers.insert("Set-Cookie".to_string(), "a=1; Path=/\nb=2".to_string());
+		let response = build_response(200, &headers, [0xff, 0x00]);
+		let cookies: Vec<_> = response.headers().get_all("set-cookie").iter().collect();
+		assert_eq!(cookies, vec!["a=1; Path=/", "b=2"]);
 	}
 }
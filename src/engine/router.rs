@@ -2,6 +2,7 @@
 
 use crate::config::HttpMethod;
 use crate::engine::{EndpointRegistry, ResolvedEndpoint};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Match method + path to a configured endpoint
@@ -14,6 +15,30 @@ pub fn match_route(
 	registry.get(method, path)
 }
 
+/// Match a path against a template with `{param}` segments, returning the
+/// captured values.
+pub(crate) fn match_path(template: &str, path: &str) -> Option<HashMap<String, String>> {
+	let mut params = HashMap::new();
+	let mut template_segments = template.split('/');
+	let mut path_segments = path.split('/');
+	loop {
+		match (template_segments.next(), path_segments.next()) {
+			(None, None) => return Some(params),
+			(Some(expected), Some(actual)) => {
+				match expected.strip_prefix('{').and_then(|name| name.strip_suffix('}')) {
+					Some(name) if !actual.is_empty() => {
+						params.insert(name.to_string(), actual.to_string());
+					}
+					Some(_) => return None,
+					None if expected == actual => {}
+					None => return None,
+				}
+			}
+			_ => return None,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -40,6 +65,17 @@ mod tests {
 			error_profile: ErrorProfile::default(),
 			rate_limit: None,
 			bandwidth_cap: None,
+			capacity: None,
+			concurrency_limit: None,
+			resource: None,
+			workflow: None,
+			job: None,
+			auth: None,
+			required_scopes: vec![],
+			idempotency: None,
+			dependencies: None,
+			callbacks: vec![],
+			proxy: None,
 			loaded_at: None,
 			rate_limiter: None,
 		}
@@ -63,4 +99,13 @@ mod tests {
 		assert!(matched.is_none());
 	}
 
+	#[test]
+	fn test_match_path_captures_params() {
+		let params = match_path("/users/{user}/orders/{id}", "/users/7/orders/abc").unwrap();
+		assert_eq!(params["user"], "7");
+		assert_eq!(params["id"], "abc");
+		assert!(match_path("/users/{id}", "/users/").is_none());
+		assert!(match_path("/users/{id}", "/users/7/orders").is_none());
+		assert!(match_path("/users/{id}", "/accounts/7").is_none());
+	}
 }
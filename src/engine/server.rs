@@ -1,13 +1,14 @@
 // Engine HTTP server (port 8080)
 
 use crate::config::HttpMethod;
-use crate::engine::{handle_request, EndpointRegistry};
+use crate::engine::{handle_request, EndpointRegistry, RequestContext};
 use crate::engine::response::build_plain_text;
 use axum::body::Bytes;
-use axum::extract::{Path, State};
-use axum::http::Method;
+use axum::extract::{ConnectInfo, Path, Query, RawQuery, State};
+use axum::http::{HeaderMap, Method};
 use axum::routing::any;
 use axum::Router;
+use std::collections::HashMap;
 use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -31,16 +32,22 @@ where
 {
 	let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
 	let listener = tokio::net::TcpListener::bind(addr).await?;
-	axum::serve(listener, build_router(registry))
+	let app = build_router(registry).into_make_service_with_connect_info::<SocketAddr>();
+	axum::serve(listener, app)
 		.with_graceful_shutdown(shutdown)
 		.await?;
 	Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_all(
 	State(state): State<EngineState>,
 	method: Method,
 	Path(path): Path<String>,
+	Query(query): Query<HashMap<String, String>>,
+	RawQuery(raw_query): RawQuery,
+	connect_info: Option<ConnectInfo<SocketAddr>>,
+	headers: HeaderMap,
 	body: Bytes,
 ) -> axum::response::Response {
 	let method = match to_http_method(&method) {
@@ -54,11 +61,24 @@ async fn handle_all(
 		format!("/{}", path)
 	};
 
-	let endpoint = state.registry.get(&method, &full_path);
-	match endpoint {
-		Some(endpoint) => {
-			let request_body = String::from_utf8_lossy(&body).to_string();
-			handle_request(&endpoint, &request_body).await
+	let routed = state
+		.registry
+		.route(&method, &full_path)
+		.or_else(|| state.registry.fallthrough().map(|proxy| (proxy, HashMap::new())));
+	match routed {
+		Some((endpoint, path_params)) => {
+			let request = RequestContext {
+				method: Some(method),
+				path: full_path,
+				path_params,
+				headers,
+				query,
+				raw_query,
+				client_ip: connect_info.map(|ConnectInfo(addr)| addr.ip()),
+				body: String::from_utf8_lossy(&body).to_string(),
+				raw_body: body,
+			};
+			handle_request(&endpoint, &request).await
 		}
 		None => build_plain_text(404, "Not Found"),
 	}
@@ -84,6 +104,7 @@ mod tests {
 	use std::collections::HashMap;
 	use tower::util::ServiceExt;
 	use axum::http::{Request, StatusCode};
+	use std::time::Duration;
 
 	fn endpoint(id: &str, method: HttpMethod, path: &str) -> Endpoint {
 		Endpoint {
@@ -103,6 +124,17 @@ mod tests {
 			error_profile: ErrorProfile::default(),
 			rate_limit: None,
 			bandwidth_cap: None,
+			capacity: None,
+			concurrency_limit: None,
+			resource: None,
+			workflow: None,
+			job: None,
+			auth: None,
+			required_scopes: vec![],
+			idempotency: None,
+			dependencies: None,
+			callbacks: vec![],
+			proxy: None,
 			loaded_at: None,
 			rate_limiter: None,
 		}
@@ -147,4 +179,582 @@ mod tests {
 
 		assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 	}
+
+	#[tokio::test]
+	async fn test_resource_round_trip() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+resources:
+  - id: "users"
+endpoints:
+  - id: "create-user"
+    method: "POST"
+    path: "/users"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 201 }
+    resource: { collection: "users", operation: "create" }
+  - id: "get-user"
+    method: "GET"
+    path: "/users/{id}"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    resource: { collection: "users", operation: "read" }
+  - id: "delete-user"
+    method: "DELETE"
+    path: "/users/{id}"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 204 }
+    resource: { collection: "users", operation: "delete" }
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |method: &str, uri: &str, body: &str| {
+			let request = Request::builder()
+				.method(method)
+				.uri(uri)
+				.body(axum::body::Body::from(body.to_string()))
+				.unwrap();
+			app.clone().oneshot(request)
+		};
+
+		let created = send("POST", "/users", r#"{"name": "ada"}"#).await.unwrap();
+		assert_eq!(created.status(), StatusCode::CREATED);
+		assert_eq!(created.headers()["location"], "/users/1");
+
+		let fetched = send("GET", "/users/1", "").await.unwrap();
+		assert_eq!(fetched.status(), StatusCode::OK);
+		let body = axum::body::to_bytes(fetched.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		assert_eq!(body, serde_json::json!({"id": 1, "name": "ada"}));
+
+		let duplicate = send("POST", "/users", r#"{"id": 1}"#).await.unwrap();
+		assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+		let deleted = send("DELETE", "/users/1", "").await.unwrap();
+		assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+		let missing = send("GET", "/users/1", "").await.unwrap();
+		assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn test_workflow_advances_per_session() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+workflows:
+  - id: "export"
+    session: { source: "header", name: "X-Session" }
+endpoints:
+  - id: "export-status"
+    method: "GET"
+    path: "/export"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 202, body: "pending" }
+    workflow:
+      workflow: "export"
+      variants:
+        - { required_state: "started", new_state: "polled" }
+        - { required_state: "polled", new_state: "done" }
+        - { required_state: "done", response: { status: 200, body: "complete" } }
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let poll = |session: &str| {
+			let request = Request::builder()
+				.uri("/export")
+				.header("X-Session", session)
+				.body(axum::body::Body::empty())
+				.unwrap();
+			let app = app.clone();
+			async move {
+				let response = app.oneshot(request).await.unwrap();
+				let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+				String::from_utf8(body.to_vec()).unwrap()
+			}
+		};
+
+		assert_eq!(poll("a").await, "pending");
+		assert_eq!(poll("a").await, "pending");
+		assert_eq!(poll("b").await, "pending");
+		assert_eq!(poll("a").await, "complete");
+		assert_eq!(poll("b").await, "pending");
+	}
+
+	#[tokio::test]
+	async fn test_job_submit_and_poll() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+jobs:
+  - id: "export"
+    duration: { distribution: "fixed", params: { delay_ms: 40 } }
+    result: { status: 200, body: "report ready" }
+endpoints:
+  - id: "submit-export"
+    method: "POST"
+    path: "/exports"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 202 }
+    job: { job: "export", operation: "submit" }
+  - id: "export-status"
+    method: "GET"
+    path: "/exports/{job_id}"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    job: { job: "export", operation: "status" }
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |method: &str, uri: &str| {
+			let request = Request::builder()
+				.method(method)
+				.uri(uri)
+				.body(axum::body::Body::empty())
+				.unwrap();
+			let app = app.clone();
+			async move {
+				let response = app.oneshot(request).await.unwrap();
+				let status = response.status();
+				let location = response.headers().get("location").map(|value| value.to_str().unwrap().to_string());
+				let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+				(status, location, String::from_utf8(body.to_vec()).unwrap())
+			}
+		};
+
+		let (status, location, _) = send("POST", "/exports").await;
+		assert_eq!(status, StatusCode::ACCEPTED);
+		let location = location.unwrap();
+		assert!(location.starts_with("/exports/"));
+
+		let (status, _, body) = send("GET", &location).await;
+		assert_eq!(status, StatusCode::OK);
+		assert!(body.contains(r#""status":"running""#));
+
+		tokio::time::sleep(Duration::from_millis(60)).await;
+		assert_eq!(send("GET", &location).await.2, "report ready");
+		assert_eq!(send("GET", "/exports/unknown").await.0, StatusCode::NOT_FOUND);
+	}
+
+	#[tokio::test]
+	async fn test_token_issuance_and_protected_endpoint() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+auth:
+  clients:
+    - { client_id: "billing", client_secret: "s3cret", scopes: ["orders:read"] }
+endpoints:
+  - id: "token"
+    method: "POST"
+    path: "/oauth/token"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    auth: "token_endpoint"
+  - id: "orders"
+    method: "GET"
+    path: "/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "[]" }
+    auth: "required"
+    required_scopes: ["orders:read"]
+  - id: "refunds"
+    method: "POST"
+    path: "/refunds"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 201 }
+    auth: "required"
+    required_scopes: ["refunds:write"]
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |method: &str, uri: &str, token: Option<&str>, body: &str| {
+			let mut request = Request::builder()
+				.method(method)
+				.uri(uri)
+				.header("Content-Type", "application/x-www-form-urlencoded");
+			if let Some(token) = token {
+				request = request.header("Authorization", format!("Bearer {}", token));
+			}
+			app.clone().oneshot(request.body(axum::body::Body::from(body.to_string())).unwrap())
+		};
+
+		let issued = send("POST", "/oauth/token", None, "grant_type=client_credentials&client_id=billing&client_secret=s3cret")
+			.await
+			.unwrap();
+		assert_eq!(issued.status(), StatusCode::OK);
+		let body = axum::body::to_bytes(issued.into_body(), usize::MAX).await.unwrap();
+		let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+		let token = body["access_token"].as_str().unwrap();
+
+		let missing = send("GET", "/orders", None, "").await.unwrap();
+		assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+		assert_eq!(missing.headers()["www-authenticate"], "Bearer");
+		assert_eq!(send("GET", "/orders", Some(token), "").await.unwrap().status(), StatusCode::OK);
+		assert_eq!(send("POST", "/refunds", Some(token), "").await.unwrap().status(), StatusCode::FORBIDDEN);
+	}
+
+	#[tokio::test]
+	async fn test_idempotency_key_fails_then_replays() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+resources:
+  - id: "payments"
+endpoints:
+  - id: "create-payment"
+    method: "POST"
+    path: "/payments"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 201 }
+    resource: { collection: "payments", operation: "create" }
+    idempotency:
+      fail_attempts: { attempts: 1, status: 503 }
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |key: &str, body: &str| {
+			let request = Request::builder()
+				.method("POST")
+				.uri("/payments")
+				.header("Idempotency-Key", key)
+				.body(axum::body::Body::from(body.to_string()))
+				.unwrap();
+			app.clone().oneshot(request)
+		};
+
+		assert_eq!(send("k1", "{}").await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+		let created = send("k1", "{}").await.unwrap();
+		assert_eq!(created.status(), StatusCode::CREATED);
+		assert_eq!(created.headers()["location"], "/payments/1");
+
+		let replayed = send("k1", "{}").await.unwrap();
+		assert_eq!(replayed.status(), StatusCode::CREATED);
+		assert_eq!(replayed.headers()["location"], "/payments/1");
+		assert_eq!(replayed.headers()["idempotent-replayed"], "true");
+
+		assert_eq!(send("k2", "{}").await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+		assert_eq!(send("k2", "{}").await.unwrap().headers()["location"], "/payments/2");
+		assert_eq!(send("k1", r#"{"amount": 5}"#).await.unwrap().status(), StatusCode::UNPROCESSABLE_ENTITY);
+	}
+
+	#[tokio::test]
+	async fn test_concurrent_requests_with_one_idempotency_key() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+resources:
+  - id: "payments"
+endpoints:
+  - id: "create-payment"
+    method: "POST"
+    path: "/payments"
+    latency: { distribution: "fixed", params: { delay_ms: 200 } }
+    response: { status: 201 }
+    resource: { collection: "payments", operation: "create" }
+    idempotency: {}
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry.clone());
+		let send = || {
+			let request = Request::builder()
+				.method("POST")
+				.uri("/payments")
+				.header("Idempotency-Key", "k1")
+				.body(axum::body::Body::from("{}"))
+				.unwrap();
+			app.clone().oneshot(request)
+		};
+
+		let (first, second) = tokio::join!(send(), send());
+		let mut statuses = vec![first.unwrap().status(), second.unwrap().status()];
+		statuses.sort();
+		assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CONFLICT]);
+
+		let store = registry.resource("payments").expect("store");
+		assert_eq!(store.lock().unwrap().items().len(), 1);
+		let replayed = send().await.unwrap();
+		assert_eq!(replayed.headers()["idempotent-replayed"], "true");
+	}
+
+	#[tokio::test]
+	async fn test_idempotent_answers_release_their_slot() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "create-payment"
+    method: "POST"
+    path: "/payments"
+    latency: { distribution: "fixed", params: { delay_ms: 300 } }
+    response: { status: 201 }
+    concurrency_limit: { max_in_flight: 2, queue_size: 0 }
+    idempotency: {}
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |key: &str| {
+			let request = Request::builder()
+				.method("POST")
+				.uri("/payments")
+				.header("Idempotency-Key", key)
+				.body(axum::body::Body::from("{}"))
+				.unwrap();
+			app.clone().oneshot(request)
+		};
+
+		let first = tokio::spawn(send("k1"));
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		let polling = tokio::spawn(send("k1"));
+		tokio::time::sleep(Duration::from_millis(50)).await;
+
+		assert_eq!(send("k2").await.unwrap().status(), StatusCode::CREATED);
+		assert_eq!(polling.await.unwrap().unwrap().status(), StatusCode::CONFLICT);
+		assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::CREATED);
+	}
+
+	#[tokio::test]
+	async fn test_dependency_failures_propagate() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "database"
+    method: "GET"
+    path: "/internal/db"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    error_profile: { rate: 1.0, codes: [503] }
+  - id: "search"
+    method: "GET"
+    path: "/internal/search"
+    latency: { distribution: "fixed", params: { delay_ms: 500 } }
+    response: { status: 200 }
+  - id: "orders"
+    method: "GET"
+    path: "/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "[]" }
+    dependencies:
+      calls: [{ endpoint_id: "database" }]
+  - id: "catalog"
+    method: "GET"
+    path: "/catalog"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "full" }
+    dependencies:
+      mode: "parallel"
+      calls:
+        - { endpoint_id: "search", timeout_ms: 20, fallback: { status: 200, body: "cached" } }
+  - id: "dashboard"
+    method: "GET"
+    path: "/dashboard"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    dependencies:
+      mode: "parallel"
+      calls: [{ endpoint_id: "catalog" }, { endpoint_id: "search", timeout_ms: 20 }]
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let get = |uri: &str| {
+			let request = Request::builder().uri(uri).body(axum::body::Body::empty()).unwrap();
+			app.clone().oneshot(request)
+		};
+
+		let orders = get("/orders").await.unwrap();
+		assert_eq!(orders.status(), StatusCode::BAD_GATEWAY);
+		let body = axum::body::to_bytes(orders.into_body(), usize::MAX).await.unwrap();
+		assert!(String::from_utf8_lossy(&body).contains("dependency 'database' failed with 503"));
+
+		let catalog = get("/catalog").await.unwrap();
+		assert_eq!(catalog.status(), StatusCode::OK);
+		let body = axum::body::to_bytes(catalog.into_body(), usize::MAX).await.unwrap();
+		assert_eq!(&body[..], b"cached");
+
+		assert_eq!(get("/dashboard").await.unwrap().status(), StatusCode::GATEWAY_TIMEOUT);
+	}
+
+	#[tokio::test]
+	async fn test_dependencies_receive_the_callers_path_params() {
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+resources:
+  - id: "users"
+endpoints:
+  - id: "create-user"
+    method: "POST"
+    path: "/users"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 201 }
+    resource: { collection: "users", operation: "create" }
+  - id: "get-user"
+    method: "GET"
+    path: "/users/{id}"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200 }
+    resource: { collection: "users", operation: "read" }
+  - id: "user-orders"
+    method: "GET"
+    path: "/users/{id}/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 0 } }
+    response: { status: 200, body: "[]" }
+    dependencies:
+      calls: [{ endpoint_id: "get-user" }]
+"#,
+		)
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |method: &str, uri: &str, body: &str| {
+			let request = Request::builder()
+				.method(method)
+				.uri(uri)
+				.body(axum::body::Body::from(body.to_string()))
+				.unwrap();
+			app.clone().oneshot(request)
+		};
+
+		assert_eq!(send("POST", "/users", r#"{"name": "ada"}"#).await.unwrap().status(), StatusCode::CREATED);
+		assert_eq!(send("GET", "/users/1/orders", "").await.unwrap().status(), StatusCode::OK);
+		let missing = send("GET", "/users/2/orders", "").await.unwrap();
+		assert_eq!(missing.status(), StatusCode::BAD_GATEWAY);
+		let body = axum::body::to_bytes(missing.into_body(), usize::MAX).await.unwrap();
+		assert!(String::from_utf8_lossy(&body).contains("failed with 404"));
+	}
+
+	#[tokio::test]
+	async fn test_proxy_forwards_and_injects_faults() {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		let hits = Arc::new(AtomicUsize::new(0));
+		let upstream = axum::Router::new().fallback({
+			let hits = Arc::clone(&hits);
+			move |method: Method, uri: axum::http::Uri, body: String| async move {
+				hits.fetch_add(1, Ordering::SeqCst);
+				(
+					[("X-Upstream", "real")],
+					format!("{} {} {}", method, uri, body),
+				)
+			}
+		});
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(&format!(
+			r#"
+version: "1.0"
+proxy:
+  upstream: "http://{addr}"
+endpoint_groups:
+  - id: "real"
+    endpoint_ids: ["orders"]
+    proxy: {{ upstream: "http://{addr}/v2" }}
+endpoints:
+  - id: "orders"
+    method: "POST"
+    path: "/orders"
+    latency: {{ distribution: "fixed", params: {{ delay_ms: 0 }} }}
+    response: {{ status: 200 }}
+  - id: "flaky"
+    method: "GET"
+    path: "/flaky"
+    latency: {{ distribution: "fixed", params: {{ delay_ms: 0 }} }}
+    response: {{ status: 200 }}
+    error_profile: {{ rate: 1.0, codes: [503] }}
+    proxy: {{ upstream: "http://{addr}" }}
+"#
+		))
+		.expect("config");
+		registry.set_config(config);
+		let app = build_router(registry);
+		let send = |method: &str, uri: &str, body: &str| {
+			let request = Request::builder()
+				.method(method)
+				.uri(uri)
+				.body(axum::body::Body::from(body.to_string()))
+				.unwrap();
+			app.clone().oneshot(request)
+		};
+
+		let orders = send("POST", "/orders", "{}").await.unwrap();
+		assert_eq!(orders.status(), StatusCode::OK);
+		assert_eq!(orders.headers()["x-upstream"], "real");
+		let body = axum::body::to_bytes(orders.into_body(), usize::MAX).await.unwrap();
+		assert_eq!(&body[..], b"POST /v2/orders {}");
+
+		let unmatched = send("GET", "/anything/else?x=1", "").await.unwrap();
+		let body = axum::body::to_bytes(unmatched.into_body(), usize::MAX).await.unwrap();
+		assert_eq!(&body[..], b"GET /anything/else?x=1 ");
+
+		assert_eq!(send("GET", "/flaky", "").await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn test_proxy_forwards_bytes_cookies_and_query_unchanged() {
+		let upstream = axum::Router::new().fallback(|uri: axum::http::Uri, headers: HeaderMap, body: Bytes| async move {
+			let mut response = axum::response::Response::new(axum::body::Body::from(body));
+			let response_headers = response.headers_mut();
+			response_headers.append("set-cookie", "session=1; Path=/".parse().unwrap());
+			response_headers.append("set-cookie", "theme=dark".parse().unwrap());
+			response_headers.insert("x-query", uri.query().unwrap_or_default().parse().unwrap());
+			let compression = if headers.contains_key("accept-encoding") { "asked" } else { "none" };
+			response_headers.insert("x-compression", compression.parse().unwrap());
+			response
+		});
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+		let registry = Arc::new(EndpointRegistry::new());
+		let config = crate::config::parse_yaml(&format!(
+			"version: \"1.0\"\nproxy:\n  upstream: \"http://{addr}\"\nendpoints: []\n"
+		))
+		.expect("config");
+		registry.set_config(config);
+
+		// Gzip magic bytes, not valid UTF-8.
+		let payload: &[u8] = &[0x1f, 0x8b, 0x08, 0x00, 0xff, 0xfe];
+		let request = Request::builder()
+			.method("POST")
+			.uri("/upload?tag=b&tag=a&q=a%20b")
+			.header("Accept-Encoding", "gzip, br")
+			.body(axum::body::Body::from(payload))
+			.unwrap();
+		let response = build_router(registry).oneshot(request).await.unwrap();
+		assert_eq!(response.headers()["x-query"], "tag=b&tag=a&q=a%20b");
+		assert_eq!(response.headers()["x-compression"], "none");
+		let cookies: Vec<_> = response.headers().get_all("set-cookie").iter().collect();
+		assert_eq!(cookies, vec!["session=1; Path=/", "theme=dark"]);
+		let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+		assert_eq!(&body[..], payload);
+	}
 }
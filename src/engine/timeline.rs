@@ -0,0 +1,365 @@
+// Behavior timeline introspection
+//
+// Describes which windows and bursts apply to an endpoint at a point in time
+
+use crate::config::{BehaviorWindow, BurstEvent, ErrorProfile, LatencyConfig};
+use crate::engine::handler::{
+	active_bursts, active_profiles, active_windows, burst_range, effective_error_profile, elapsed_ms,
+	schedule_range,
+};
+use crate::engine::{
+	upcoming_burst_occurrences, upcoming_window_occurrences, CapacityPool, PoolOwner, ProfileValues,
+	ResolvedEndpoint,
+};
+use chrono::{DateTime, Utc};
+
+/// A window or burst that is currently applied to an endpoint.
+#[derive(Debug, Clone)]
+pub struct ActiveBehavior {
+	pub id: Option<String>,
+	pub priority: i32,
+	pub ramp_factor: f64,
+	pub start_ms: f64,
+	pub end_ms: f64,
+}
+
+/// A latency override together with the ramp factor it is blended in with.
+#[derive(Debug, Clone)]
+pub struct LatencyOverride {
+	pub id: Option<String>,
+	pub factor: f64,
+	pub latency: LatencyConfig,
+}
+
+/// Current keyframe values of a behavior profile.
+#[derive(Debug, Clone)]
+pub struct ActiveProfile {
+	pub id: Option<String>,
+	pub values: ProfileValues,
+}
+
+/// Current load on a capacity pool or concurrency limit guarding the endpoint.
+#[derive(Debug, Clone)]
+pub struct CapacityLoad {
+	pub owner: PoolOwner,
+	pub slots: u32,
+	pub queue_size: u32,
+	pub in_flight: usize,
+	pub queued: usize,
+	pub latency_multiplier: f64,
+}
+
+/// A scheduled occurrence of a window or burst.
+#[derive(Debug, Clone)]
+pub struct ScheduledOccurrence {
+	pub id: Option<String>,
+	pub start_ms: f64,
+	pub end_ms: f64,
+}
+
+/// Behavior applied to a single endpoint at `elapsed_ms`.
+///
+/// All `*_ms` values are offsets from the endpoint's config load time.
+#[derive(Debug, Clone)]
+pub struct EndpointTimeline {
+	pub endpoint_id: String,
+	pub elapsed_ms: f64,
+	pub active_windows: Vec<ActiveBehavior>,
+	pub active_bursts: Vec<ActiveBehavior>,
+	pub profiles: Vec<ActiveProfile>,
+	pub concurrency: Vec<CapacityLoad>,
+	pub capacity: Vec<CapacityLoad>,
+	pub base_latency: LatencyConfig,
+	pub window_latency: Option<LatencyOverride>,
+	pub burst_latency: Option<LatencyOverride>,
+	pub error_profile: ErrorProfile,
+	pub upcoming_windows: Vec<ScheduledOccurrence>,
+	pub upcoming_bursts: Vec<ScheduledOccurrence>,
+}
+
+/// Build the timeline for an endpoint as of now.
+pub fn endpoint_timeline(resolved: &ResolvedEndpoint, now: DateTime<Utc>, count: usize) -> EndpointTimeline {
+	let elapsed = elapsed_ms(&resolved.endpoint);
+	timeline_at(resolved, elapsed, now, count)
+}
+
+fn timeline_at(
+	resolved: &ResolvedEndpoint,
+	elapsed_ms: f64,
+	now: DateTime<Utc>,
+	count: usize,
+) -> EndpointTimeline {
+	let endpoint = &resolved.endpoint;
+	let behaviors = &resolved.behaviors;
+
+	let windows = active_windows(behaviors, elapsed_ms);
+	let bursts = active_bursts(behaviors, elapsed_ms);
+
+	let window_latency = windows.iter().rev().find_map(|(window, factor)| {
+		window.latency_override.as_ref().map(|latency| LatencyOverride {
+			id: window.id.clone(),
+			factor: *factor,
+			latency: latency.clone(),
+		})
+	});
+	let burst_latency = bursts.iter().rev().find_map(|(burst, factor)| {
+		burst.latency_spike.as_ref().map(|latency| LatencyOverride {
+			id: burst.id.clone(),
+			factor: *factor,
+			latency: latency.clone(),
+		})
+	});
+
+	let active_windows = windows
+		.iter()
+		.map(|(window, factor)| active_window(window, *factor, elapsed_ms))
+		.collect();
+	let active_bursts = bursts
+		.iter()
+		.map(|(burst, factor)| active_burst(burst, *factor, elapsed_ms))
+		.collect();
+
+	let profiles = active_profiles(behaviors, elapsed_ms)
+		.into_iter()
+		.map(|(profile, values)| ActiveProfile {
+			id: profile.id.clone(),
+			values,
+		})
+		.collect();
+
+	let mut upcoming_windows = behaviors
+		.windows
+		.iter()
+		.flat_map(|window| {
+			// The first occurrence may be the active one, which is filtered out below.
+			upcoming_window_occurrences(window, elapsed_ms, now, count + 1)
+				.into_iter()
+				.map(|(start_ms, end_ms)| ScheduledOccurrence {
+					id: window.id.clone(),
+					start_ms,
+					end_ms,
+				})
+		})
+		.filter(|occurrence| occurrence.start_ms > elapsed_ms)
+		.collect::<Vec<_>>();
+	sort_and_truncate(&mut upcoming_windows, count);
+
+	let mut upcoming_bursts = behaviors
+		.bursts
+		.iter()
+		.flat_map(|burst| {
+			upcoming_burst_occurrences(burst, elapsed_ms, count + 1)
+				.into_iter()
+				.map(|(start_ms, end_ms)| ScheduledOccurrence {
+					id: burst.id.clone(),
+					start_ms,
+					end_ms,
+				})
+		})
+		.filter(|occurrence| occurrence.start_ms > elapsed_ms)
+		.collect::<Vec<_>>();
+	sort_and_truncate(&mut upcoming_bursts, count);
+
+	EndpointTimeline {
+		endpoint_id: endpoint.id.clone(),
+		elapsed_ms,
+		active_windows,
+		active_bursts,
+		profiles,
+		concurrency: resolved.concurrency.iter().map(|pool| capacity_load(pool)).collect(),
+		capacity: resolved.capacity.iter().map(|pool| capacity_load(pool)).collect(),
+		base_latency: endpoint.latency.clone(),
+		window_latency,
+		burst_latency,
+		error_profile: effective_error_profile(endpoint, behaviors, elapsed_ms),
+		upcoming_windows,
+		upcoming_bursts,
+	}
+}
+
+pub fn capacity_load(pool: &CapacityPool) -> CapacityLoad {
+	CapacityLoad {
+		owner: pool.owner().clone(),
+		slots: pool.slots(),
+		queue_size: pool.queue_size(),
+		in_flight: pool.in_flight(),
+		queued: pool.queued(),
+		latency_multiplier: pool.latency_multiplier(),
+	}
+}
+
+fn active_window(window: &BehaviorWindow, factor: f64, elapsed_ms: f64) -> ActiveBehavior {
+	let (start_ms, end_ms) =
+		schedule_range(&window.schedule, elapsed_ms, window).unwrap_or((elapsed_ms, elapsed_ms));
+	ActiveBehavior {
+		id: window.id.clone(),
+		priority: window.priority,
+		ramp_factor: factor,
+		start_ms,
+		end_ms,
+	}
+}
+
+fn active_burst(burst: &BurstEvent, factor: f64, elapsed_ms: f64) -> ActiveBehavior {
+	let (start_ms, end_ms) = burst_range(&burst.frequency, burst.duration_ms, elapsed_ms, burst)
+		.unwrap_or((elapsed_ms, elapsed_ms));
+	ActiveBehavior {
+		id: burst.id.clone(),
+		priority: burst.priority,
+		ramp_factor: factor,
+		start_ms,
+		end_ms,
+	}
+}
+
+fn sort_and_truncate(occurrences: &mut Vec<ScheduledOccurrence>, count: usize) {
+	occurrences.sort_by(|a, b| a.start_ms.total_cmp(&b.start_ms));
+	occurrences.truncate(count);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{
+		BehaviorSchedule, BehaviorScope, DistributionParams, DistributionType, Endpoint, ErrorMix,
+		HttpMethod, RampConfig, Response, ScheduleMode,
+	};
+	use crate::engine::EndpointBehaviors;
+	use std::collections::HashMap;
+
+	fn latency(delay_ms: f64) -> LatencyConfig {
+		LatencyConfig {
+			distribution: DistributionType::Fixed,
+			params: DistributionParams::Fixed { delay_ms },
+		}
+	}
+
+	fn window(id: &str, start_offset_ms: f64, duration_ms: f64) -> BehaviorWindow {
+		BehaviorWindow {
+			id: Some(id.to_string()),
+			scope: BehaviorScope {
+				endpoint_id: Some("health".to_string()),
+				group_id: None,
+				global: false,
+			},
+			schedule: BehaviorSchedule {
+				mode: ScheduleMode::Fixed,
+				start_offset_ms: Some(start_offset_ms),
+				duration_ms,
+				every_ms: None,
+				jitter_ms: None,
+				max_occurrences: None,
+				min_delay_ms: None,
+				cron: None,
+				timezone: None,
+				parsed_cron: None,
+			},
+			priority: 0,
+			stack: false,
+			ramp: Some(RampConfig {
+				up_ms: Some(1_000.0),
+				down_ms: None,
+				curve: None,
+			}),
+			error_mix: ErrorMix::Override,
+			latency_override: Some(latency(500.0)),
+			error_profile_override: Some(ErrorProfile {
+				rate: 0.4,
+				codes: vec![503],
+				..ErrorProfile::default()
+			}),
+		}
+	}
+
+	fn resolved(windows: Vec<BehaviorWindow>) -> ResolvedEndpoint {
+		ResolvedEndpoint {
+			endpoint: Endpoint {
+				id: "health".to_string(),
+				method: HttpMethod::Get,
+				path: "/health".to_string(),
+				request: None,
+				latency: latency(5.0),
+				response: Response {
+					status: 200,
+					headers: HashMap::new(),
+					body: "ok".to_string(),
+				},
+				error_profile: ErrorProfile::default(),
+				rate_limit: None,
+				bandwidth_cap: None,
+				capacity: None,
+				concurrency_limit: None,
+				resource: None,
+				workflow: None,
+				job: None,
+				auth: None,
+				required_scopes: vec![],
+				idempotency: None,
+				dependencies: None,
+				callbacks: vec![],
+				proxy: None,
+				loaded_at: None,
+				rate_limiter: None,
+			},
+			behaviors: EndpointBehaviors {
+				windows,
+				bursts: vec![],
+				profiles: vec![],
+			},
+			shared_rate_limiters: vec![],
+			concurrency: vec![],
+			capacity: vec![],
+			resource: None,
+			workflow: None,
+			job: None,
+			auth: None,
+			idempotency: None,
+			dependencies: vec![],
+			callbacks: None,
+			proxy: None,
+			recording: None,
+		}
+	}
+
+	#[test]
+	fn test_timeline_reports_active_window_and_ramp() {
+		let resolved = resolved(vec![window("outage", 0.0, 10_000.0), window("later", 20_000.0, 5_000.0)]);
+		let timeline = timeline_at(&resolved, 500.0, Utc::now(), 3);
+
+		assert_eq!(timeline.active_windows.len(), 1);
+		let active = &timeline.active_windows[0];
+		assert_eq!(active.id.as_deref(), Some("outage"));
+		assert!((active.ramp_factor - 0.5).abs() < 1e-9);
+		assert_eq!((active.start_ms, active.end_ms), (0.0, 10_000.0));
+
+		let window_latency = timeline.window_latency.expect("window latency");
+		assert_eq!(window_latency.id.as_deref(), Some("outage"));
+		assert!((timeline.error_profile.rate - 0.2).abs() < 1e-9);
+
+		assert_eq!(timeline.upcoming_windows.len(), 1);
+		assert_eq!(timeline.upcoming_windows[0].id.as_deref(), Some("later"));
+	}
+
+	#[test]
+	fn test_timeline_counts_upcoming_windows_past_the_active_one() {
+		let mut recurring = window("recurring", 0.0, 2_000.0);
+		recurring.schedule.mode = ScheduleMode::Recurring;
+		recurring.schedule.every_ms = Some(10_000.0);
+		let timeline = timeline_at(&resolved(vec![recurring]), 500.0, Utc::now(), 3);
+
+		assert_eq!(timeline.active_windows.len(), 1);
+		let starts: Vec<_> = timeline.upcoming_windows.iter().map(|occurrence| occurrence.start_ms).collect();
+		assert_eq!(starts, vec![10_000.0, 20_000.0, 30_000.0]);
+	}
+
+	#[test]
+	fn test_timeline_without_active_behavior() {
+		let resolved = resolved(vec![window("outage", 0.0, 10_000.0)]);
+		let timeline = timeline_at(&resolved, 15_000.0, Utc::now(), 3);
+
+		assert!(timeline.active_windows.is_empty());
+		assert!(timeline.window_latency.is_none());
+		assert_eq!(timeline.error_profile.rate, 0.0);
+		assert!(timeline.upcoming_windows.is_empty());
+	}
+}
@@ -0,0 +1,184 @@
+// Workflow state
+//
+// Current state of each workflow, globally or per session, and the variant
+// selection that moves it along
+
+use crate::config::{StateVariant, Workflow, WorkflowBinding};
+use std::collections::HashMap;
+use std::time::Instant;
+
+#[derive(Debug)]
+pub struct WorkflowState {
+	workflow: Workflow,
+	global: String,
+	sessions: HashMap<String, Session>,
+}
+
+#[derive(Debug)]
+struct Session {
+	state: String,
+	last_seen: Instant,
+}
+
+impl WorkflowState {
+	pub fn new(workflow: Workflow) -> Self {
+		Self {
+			global: workflow.initial_state.clone(),
+			workflow,
+			sessions: HashMap::new(),
+		}
+	}
+
+	pub fn workflow(&self) -> &Workflow {
+		&self.workflow
+	}
+
+	/// State shared by requests without a session.
+	pub fn global_state(&self) -> &str {
+		&self.global
+	}
+
+	pub fn session_states(&self) -> HashMap<&str, &str> {
+		self.sessions
+			.iter()
+			.map(|(key, session)| (key.as_str(), session.state.as_str()))
+			.collect()
+	}
+
+	pub fn current(&self, session: Option<&str>) -> &str {
+		match session {
+			Some(key) => self
+				.sessions
+				.get(key)
+				.map_or(&self.workflow.initial_state, |session| &session.state),
+			None => &self.global,
+		}
+	}
+
+	/// Pick the first variant matching the current state and apply its
+	/// transition. `None` when no variant matches.
+	pub fn advance<'a>(&mut self, binding: &'a WorkflowBinding, session: Option<&str>) -> Option<&'a StateVariant> {
+		let current = self.current(session).to_string();
+		let variant = binding
+			.variants
+			.iter()
+			.find(|variant| variant.required_state.as_deref().is_none_or(|state| state == current))?;
+
+		if let Some(next) = &variant.new_state {
+			self.set(session, next.clone());
+		} else if let Some(key) = session {
+			self.touch(key, current);
+		}
+		Some(variant)
+	}
+
+	/// Back to the initial state, for one session or everything.
+	pub fn reset(&mut self, session: Option<&str>) {
+		match session {
+			Some(key) => {
+				self.sessions.remove(key);
+			}
+			None => {
+				self.global = self.workflow.initial_state.clone();
+				self.sessions.clear();
+			}
+		}
+	}
+
+	fn set(&mut self, session: Option<&str>, state: String) {
+		match session {
+			Some(key) => self.touch(key, state),
+			None => self.global = state,
+		}
+	}
+
+	fn touch(&mut self, key: &str, state: String) {
+		let max_sessions = self.workflow.session.as_ref().map_or(usize::MAX, |session| session.max_sessions);
+		if !self.sessions.contains_key(key) && self.sessions.len() >= max_sessions {
+			let oldest = self
+				.sessions
+				.iter()
+				.min_by_key(|(_, session)| session.last_seen)
+				.map(|(key, _)| key.clone());
+			if let Some(oldest) = oldest {
+				self.sessions.remove(&oldest);
+			}
+		}
+		self.sessions.insert(key.to_string(), Session {
+			state,
+			last_seen: Instant::now(),
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{SessionSource, WorkflowSession};
+
+	fn variant(required: Option<&str>, next: Option<&str>) -> StateVariant {
+		StateVariant {
+			required_state: required.map(str::to_string),
+			new_state: next.map(str::to_string),
+			response: None,
+		}
+	}
+
+	fn workflow(session: Option<WorkflowSession>) -> WorkflowState {
+		WorkflowState::new(Workflow {
+			id: "order".to_string(),
+			initial_state: "started".to_string(),
+			session,
+		})
+	}
+
+	#[test]
+	fn test_advance_follows_transitions() {
+		let binding = WorkflowBinding {
+			workflow: "order".to_string(),
+			variants: vec![
+				variant(Some("started"), Some("polled")),
+				variant(Some("polled"), Some("complete")),
+				variant(None, None),
+			],
+		};
+		let mut state = workflow(None);
+		let states: Vec<_> = (0..4)
+			.map(|_| {
+				state.advance(&binding, None);
+				state.current(None).to_string()
+			})
+			.collect();
+		assert_eq!(states, ["polled", "complete", "complete", "complete"]);
+
+		state.reset(None);
+		assert_eq!(state.global_state(), "started");
+	}
+
+	#[test]
+	fn test_sessions_are_independent_and_bounded() {
+		let binding = WorkflowBinding {
+			workflow: "order".to_string(),
+			variants: vec![variant(Some("started"), Some("done"))],
+		};
+		let mut state = workflow(Some(WorkflowSession {
+			source: SessionSource::Header,
+			name: "x-session".to_string(),
+			max_sessions: 2,
+		}));
+
+		state.advance(&binding, Some("a"));
+		assert_eq!(state.current(Some("a")), "done");
+		assert_eq!(state.current(Some("b")), "started");
+		assert!(state.advance(&binding, Some("a")).is_none());
+
+		state.advance(&binding, Some("b"));
+		state.advance(&binding, Some("c"));
+		assert_eq!(state.session_states().len(), 2);
+		assert_eq!(state.current(Some("a")), "started");
+
+		state.reset(Some("b"));
+		assert_eq!(state.current(Some("b")), "started");
+		assert_eq!(state.current(Some("c")), "done");
+	}
+}
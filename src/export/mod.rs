@@ -0,0 +1,5 @@
+// Exporters - describe the active configuration in other formats
+
+mod openapi;
+
+pub use openapi::*;
@@ -0,0 +1,395 @@
+// OpenAPI export
+//
+// Describes the simulated API surface as an OpenAPI 3 document, with the
+// simulated behavior attached as `x-simulant-*` extensions
+
+use crate::config::{
+	Configuration, Endpoint, EndpointAuth, JobOperation, Resource, ResourceOperation, Response, TokenFormat,
+};
+use crate::engine::method_to_string;
+use serde_json::{json, Map, Value};
+
+/// Where the engine listens unless told otherwise.
+pub const DEFAULT_ENGINE_URL: &str = "http://localhost:8080";
+
+/// OpenAPI document for every endpoint in `config`, served from `server_url`.
+pub fn export_openapi(config: &Configuration, server_url: &str) -> Value {
+	let mut paths = Map::new();
+	for endpoint in &config.endpoints {
+		let item = paths.entry(endpoint.path.clone()).or_insert_with(|| json!({}));
+		item[method_to_string(&endpoint.method).to_ascii_lowercase()] = operation(config, endpoint);
+	}
+
+	let mut info = json!({
+		"title": config.metadata.name.clone().unwrap_or_else(|| "Web Simulant".to_string()),
+		"version": config.version,
+	});
+	if let Some(description) = &config.metadata.description {
+		info["description"] = json!(description);
+	}
+	let mut document = json!({
+		"openapi": "3.0.3",
+		"info": info,
+		"servers": [{ "url": server_url, "description": "Web Simulant engine" }],
+		"paths": paths,
+	});
+	if let Some(auth) = &config.auth {
+		if config.endpoints.iter().any(|endpoint| endpoint.auth == Some(EndpointAuth::Required)) {
+			let mut scheme = json!({ "type": "http", "scheme": "bearer" });
+			if auth.token_format == TokenFormat::Jwt {
+				scheme["bearerFormat"] = json!("JWT");
+			}
+			document["components"] = json!({ "securitySchemes": { SECURITY_SCHEME: scheme } });
+		}
+	}
+	if let Some(limit) = &config.rate_limit {
+		document["x-simulant-rate-limit"] = extension(limit);
+	}
+	document
+}
+
+/// Name of the bearer scheme protected operations refer to.
+const SECURITY_SCHEME: &str = "bearerAuth";
+
+fn operation(config: &Configuration, endpoint: &Endpoint) -> Value {
+	let mut responses = Map::new();
+	for (status, documented) in served_responses(config, endpoint) {
+		responses.entry(status.to_string()).or_insert(documented);
+	}
+	let protected = endpoint.auth == Some(EndpointAuth::Required) && config.auth.is_some();
+	if protected {
+		responses
+			.entry("401".to_string())
+			.or_insert_with(|| json!({ "description": "Missing, invalid or expired bearer token" }));
+		if !endpoint.required_scopes.is_empty() {
+			responses
+				.entry("403".to_string())
+				.or_insert_with(|| json!({ "description": "Token lacks a required scope" }));
+		}
+	}
+	let profile = &endpoint.error_profile;
+	if profile.rate > 0.0 && !profile.error_in_payload {
+		for code in &profile.codes {
+			let error = Response {
+				status: *code,
+				headers: Default::default(),
+				body: profile.body.clone(),
+			};
+			responses
+				.entry(code.to_string())
+				.or_insert_with(|| response(&error, "Simulated error"));
+		}
+	}
+	if let Some(limit) = &endpoint.rate_limit {
+		let limited = match &limit.response {
+			Some(rejection) => Response {
+				status: rejection.status,
+				headers: rejection.headers.clone(),
+				body: rejection.body.clone(),
+			},
+			None => Response {
+				status: 429,
+				headers: Default::default(),
+				body: "Rate limit exceeded".to_string(),
+			},
+		};
+		responses
+			.entry(limited.status.to_string())
+			.or_insert_with(|| response(&limited, "Rate limit exceeded"));
+	}
+
+	let mut operation = json!({
+		"operationId": endpoint.id,
+		"responses": responses,
+		"x-simulant-latency": extension(&endpoint.latency),
+	});
+	if protected {
+		operation["security"] = json!([{ SECURITY_SCHEME: endpoint.required_scopes }]);
+	}
+	let parameters: Vec<Value> = endpoint
+		.path_params()
+		.into_iter()
+		.map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+		.collect();
+	if !parameters.is_empty() {
+		operation["parameters"] = Value::Array(parameters);
+	}
+	if let Some(body) = endpoint.request.as_ref().and_then(|request| request.body.as_deref()) {
+		let (content_type, example) = content_example(None, body);
+		operation["requestBody"] = json!({ "content": { content_type: { "example": example } } });
+	}
+	if profile.rate > 0.0 || profile.payload_corruption.is_some() {
+		operation["x-simulant-error-profile"] = extension(profile);
+	}
+	if let Some(limit) = &endpoint.rate_limit {
+		operation["x-simulant-rate-limit"] = extension(limit);
+	}
+	if let Some(cap) = &endpoint.bandwidth_cap {
+		operation["x-simulant-bandwidth-cap"] = extension(cap);
+	}
+	operation
+}
+
+/// Responses the engine serves for the endpoint: those of its token, job or
+/// resource binding, the same way the handler picks them, or the configured one.
+fn served_responses(config: &Configuration, endpoint: &Endpoint) -> Vec<(u16, Value)> {
+	if let (Some(EndpointAuth::TokenEndpoint), Some(auth)) = (endpoint.auth, &config.auth) {
+		let token = json!({ "access_token": "...", "token_type": "Bearer", "expires_in": auth.expires_in_secs, "scope": "" });
+		return vec![
+			(200, example(token, "Token issued")),
+			(400, example(json!({ "error": "invalid_request" }), "Malformed request, grant or scope")),
+			(401, example(json!({ "error": "invalid_client" }), "Unknown client or wrong secret")),
+		];
+	}
+	if let Some(binding) = &endpoint.job {
+		if let Some(job) = config.jobs.iter().find(|job| job.id == binding.job) {
+			return match binding.operation {
+				JobOperation::Submit => {
+					let mut accepted = example(json!({ "job_id": "...", "status": "pending" }), "Job accepted");
+					accepted["headers"] = json!({ "Location": { "schema": { "type": "string" } } });
+					vec![(202, accepted)]
+				}
+				JobOperation::Status => {
+					let mut served = vec![(job.result.status, response(&job.result, "Job succeeded"))];
+					let profile = &job.error_profile;
+					if profile.rate > 0.0 && !profile.error_in_payload {
+						for code in &profile.codes {
+							let failed = Response {
+								status: *code,
+								headers: Default::default(),
+								body: profile.body.clone(),
+							};
+							served.push((*code, response(&failed, "Job failed")));
+						}
+					}
+					served.push((200, example(json!({ "job_id": "...", "status": "running" }), "Job pending or running")));
+					served.push((404, example(json!({ "error": "Job not found" }), "Unknown or expired job")));
+					served
+				}
+			};
+		}
+	}
+	if let Some(binding) = &endpoint.resource {
+		if let Some(resource) = config.resources.iter().find(|resource| resource.id == binding.collection) {
+			return resource_responses(resource, binding.operation);
+		}
+	}
+	vec![(endpoint.response.status, response(&endpoint.response, "Simulated response"))]
+}
+
+fn resource_responses(resource: &Resource, operation: ResourceOperation) -> Vec<(u16, Value)> {
+	let item = resource.seed.first().cloned().unwrap_or_else(|| json!({ (resource.id_field.clone()): 1 }));
+	let not_found = (404, example(json!({ "error": "Item not found" }), "No item with this id"));
+	let invalid = (400, example(json!({ "error": "invalid JSON body" }), "Invalid item"));
+	match operation {
+		ResourceOperation::List => vec![(200, example(Value::Array(resource.seed.clone()), "Every item"))],
+		ResourceOperation::Read => vec![(200, example(item, "The item")), not_found],
+		ResourceOperation::Create => {
+			let mut created = example(item, "Item created");
+			created["headers"] = json!({ "Location": { "schema": { "type": "string" } } });
+			vec![
+				(201, created),
+				invalid,
+				(409, example(json!({ "error": "Item already exists" }), "An item with this id exists")),
+			]
+		}
+		ResourceOperation::Update => vec![(200, example(item, "Item updated")), invalid, not_found],
+		ResourceOperation::Delete => vec![(204, json!({ "description": "Item deleted" })), not_found],
+	}
+}
+
+fn example(body: Value, description: &str) -> Value {
+	json!({ "description": description, "content": { "application/json": { "example": body } } })
+}
+
+fn response(response: &Response, description: &str) -> Value {
+	let content_type = response
+		.headers
+		.iter()
+		.find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+		.map(|(_, value)| value.as_str());
+	let mut documented = json!({ "description": description });
+
+	let headers: Map<String, Value> = response
+		.headers
+		.iter()
+		.filter(|(name, _)| !name.eq_ignore_ascii_case("content-type"))
+		.map(|(name, value)| (name.clone(), json!({ "schema": { "type": "string", "example": value } })))
+		.collect();
+	if !headers.is_empty() {
+		documented["headers"] = Value::Object(headers);
+	}
+	if !response.body.is_empty() {
+		let (content_type, example) = content_example(content_type, &response.body);
+		documented["content"] = json!({ content_type: { "example": example } });
+	}
+	documented
+}
+
+/// Media type and example for a body: JSON when it parses as JSON, text otherwise.
+fn content_example(content_type: Option<&str>, body: &str) -> (String, Value) {
+	let parsed = serde_json::from_str::<Value>(body).ok();
+	match (content_type, parsed) {
+		(Some(content_type), Some(value)) if content_type.contains("json") => (content_type.to_string(), value),
+		(Some(content_type), _) => (content_type.to_string(), Value::String(body.to_string())),
+		(None, Some(value)) => ("application/json".to_string(), value),
+		(None, None) => ("text/plain".to_string(), Value::String(body.to_string())),
+	}
+}
+
+fn extension(value: &impl serde::Serialize) -> Value {
+	serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::parse_yaml;
+	use crate::import::{import_openapi, ImportOptions};
+
+	const CONFIG: &str = r#"
+version: "1.0"
+metadata: { name: "orders" }
+endpoints:
+  - id: "get-order"
+    method: "GET"
+    path: "/orders/{id}"
+    latency: { distribution: "normal", params: { mean_ms: 80, stddev_ms: 20 } }
+    response:
+      status: 200
+      headers: { Content-Type: "application/json", X-Request-Id: "abc" }
+      body: '{"id": 1, "state": "open"}'
+    error_profile: { rate: 0.05, codes: [503], body: '{"error": "busy"}' }
+  - id: "create-order"
+    method: "POST"
+    path: "/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 10 } }
+    response: { status: 201, headers: { Content-Type: "text/plain" }, body: "created" }
+    rate_limit: { requests_per_second: 5 }
+"#;
+
+	#[test]
+	fn test_export_describes_endpoints_and_behavior() {
+		let config = parse_yaml(CONFIG).expect("config");
+		let document = export_openapi(&config, DEFAULT_ENGINE_URL);
+
+		assert_eq!(document["info"]["title"], "orders");
+		let get = &document["paths"]["/orders/{id}"]["get"];
+		assert_eq!(get["operationId"], "get-order");
+		assert_eq!(get["parameters"][0]["name"], "id");
+		assert_eq!(get["responses"]["200"]["content"]["application/json"]["example"]["state"], "open");
+		assert_eq!(get["responses"]["200"]["headers"]["X-Request-Id"]["schema"]["example"], "abc");
+		assert_eq!(get["responses"]["503"]["content"]["application/json"]["example"]["error"], "busy");
+		assert_eq!(get["x-simulant-latency"]["params"]["mean_ms"], 80.0);
+		assert_eq!(get["x-simulant-error-profile"]["rate"], 0.05);
+
+		let post = &document["paths"]["/orders"]["post"];
+		assert_eq!(post["responses"]["201"]["content"]["text/plain"]["example"], "created");
+		assert!(post["responses"]["429"].is_object());
+		assert!(post.get("x-simulant-error-profile").is_none());
+	}
+
+	#[test]
+	fn test_export_documents_the_statuses_bound_endpoints_return() {
+		let config = parse_yaml(
+			r#"
+version: "1.0"
+auth: { clients: [{ client_id: "app", client_secret: "s3cret" }] }
+resources: [{ id: "orders", seed: [{ id: 1, state: "open" }] }]
+jobs:
+  - id: "export"
+    duration: { distribution: "fixed", params: { delay_ms: 100 } }
+    result: { status: 200, body: '{"url": "/files/1"}' }
+    error_profile: { rate: 0.1, codes: [502] }
+endpoints:
+  - id: "create-order"
+    method: "POST"
+    path: "/orders"
+    latency: { distribution: "fixed", params: { delay_ms: 10 } }
+    response: { status: 200 }
+    resource: { collection: "orders", operation: "create" }
+    auth: "required"
+    required_scopes: ["orders:write"]
+  - id: "delete-order"
+    method: "DELETE"
+    path: "/orders/{id}"
+    latency: { distribution: "fixed", params: { delay_ms: 10 } }
+    response: { status: 200 }
+    resource: { collection: "orders", operation: "delete" }
+  - id: "submit-export"
+    method: "POST"
+    path: "/exports"
+    latency: { distribution: "fixed", params: { delay_ms: 10 } }
+    response: { status: 200 }
+    job: { job: "export", operation: "submit" }
+  - id: "export-status"
+    method: "GET"
+    path: "/exports/{id}"
+    latency: { distribution: "fixed", params: { delay_ms: 10 } }
+    response: { status: 201 }
+    job: { job: "export", operation: "status" }
+  - id: "token"
+    method: "POST"
+    path: "/oauth/token"
+    latency: { distribution: "fixed", params: { delay_ms: 10 } }
+    response: { status: 200 }
+    auth: "token_endpoint"
+"#,
+		)
+		.expect("config");
+		let document = export_openapi(&config, DEFAULT_ENGINE_URL);
+		let statuses = |path: &str, method: &str| -> Vec<String> {
+			let mut statuses: Vec<String> = document["paths"][path][method]["responses"]
+				.as_object()
+				.expect("responses")
+				.keys()
+				.cloned()
+				.collect();
+			statuses.sort();
+			statuses
+		};
+
+		assert_eq!(statuses("/orders", "post"), ["201", "400", "401", "403", "409"]);
+		let create = &document["paths"]["/orders"]["post"];
+		assert!(create["responses"]["201"]["headers"]["Location"].is_object());
+		assert_eq!(create["responses"]["201"]["content"]["application/json"]["example"]["state"], "open");
+		assert_eq!(create["security"][0]["bearerAuth"][0], "orders:write");
+		assert_eq!(document["components"]["securitySchemes"]["bearerAuth"]["scheme"], "bearer");
+
+		assert_eq!(statuses("/orders/{id}", "delete"), ["204", "404"]);
+		assert!(document["paths"]["/orders/{id}"]["delete"].get("security").is_none());
+		assert_eq!(statuses("/exports", "post"), ["202"]);
+		assert!(document["paths"]["/exports"]["post"]["responses"]["202"]["headers"]["Location"].is_object());
+		assert_eq!(statuses("/exports/{id}", "get"), ["200", "404", "502"]);
+		assert_eq!(
+			document["paths"]["/exports/{id}"]["get"]["responses"]["200"]["content"]["application/json"]["example"]["url"],
+			"/files/1"
+		);
+		assert_eq!(statuses("/oauth/token", "post"), ["200", "400", "401"]);
+	}
+
+	#[test]
+	fn test_exported_document_imports_back() {
+		let config = parse_yaml(CONFIG).expect("config");
+		let document = serde_json::to_string(&export_openapi(&config, DEFAULT_ENGINE_URL)).unwrap();
+		let imported = import_openapi(&document, &ImportOptions::default()).expect("import").config;
+
+		let routes = |config: &Configuration| -> Vec<(String, String, u16, String)> {
+			config
+				.endpoints
+				.iter()
+				.map(|endpoint| {
+					let body = serde_json::from_str::<Value>(&endpoint.response.body)
+						.map(|value| value.to_string())
+						.unwrap_or_else(|_| endpoint.response.body.clone());
+					(endpoint.id.clone(), endpoint.path.clone(), endpoint.response.status, body)
+				})
+				.collect()
+		};
+		let mut expected = routes(&config);
+		let mut actual = routes(&imported);
+		expected.sort();
+		actual.sort();
+		assert_eq!(actual, expected);
+	}
+}
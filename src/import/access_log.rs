@@ -0,0 +1,834 @@
+// Access log fitting
+//
+// Per-route latency from nginx, envoy or JSON access logs: observed
+// quantiles, candidate distributions fitted to the samples with a
+// goodness-of-fit score each, and a configuration using the best fit
+
+use crate::config::{
+	ConfigError, Configuration, DistributionParams, DistributionType, Endpoint, HttpMethod, LatencyConfig, Metadata,
+	MixtureComponent, Response,
+};
+use crate::distributions::{
+	Distribution, ExponentialDistribution, FixedDistribution, LogNormalDistribution, NormalDistribution,
+	UniformDistribution,
+};
+use crate::engine::{match_path, method_to_string};
+use crate::import::{endpoint_id, fit_latency, template_path, unique_id, METHODS};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// nginx's `combined` format with `$request_time` appended.
+pub const NGINX_FORMAT: &str =
+	r#"$remote_addr - $remote_user [$time_local] "$request" $status $body_bytes_sent "$http_referer" "$http_user_agent" $request_time"#;
+
+/// Envoy's default access log format.
+pub const ENVOY_FORMAT: &str = r#"[%START_TIME%] "%REQ(:METHOD)% %REQ(X-ENVOY-ORIGINAL-PATH?:PATH)% %PROTOCOL%" %RESPONSE_CODE% %RESPONSE_FLAGS% %BYTES_RECEIVED% %BYTES_SENT% %DURATION% %RESP(X-ENVOY-UPSTREAM-SERVICE-TIME)% "%REQ(X-FORWARDED-FOR)%" "%REQ(USER-AGENT)%" "%REQ(X-REQUEST-ID)%" "%REQ(:AUTHORITY)%" "%UPSTREAM_HOST%""#;
+
+/// Timing fields, in order of preference, and the unit each is logged in.
+const TIMING_FIELDS: &[(&str, TimeUnit)] = &[
+	("$request_time", TimeUnit::Seconds),
+	("$upstream_response_time", TimeUnit::Seconds),
+	("%DURATION%", TimeUnit::Milliseconds),
+	("%RESPONSE_DURATION%", TimeUnit::Milliseconds),
+	("%RESP(X-ENVOY-UPSTREAM-SERVICE-TIME)%", TimeUnit::Milliseconds),
+	("duration", TimeUnit::Milliseconds),
+	("duration_ms", TimeUnit::Milliseconds),
+	("request_time", TimeUnit::Seconds),
+	("upstream_response_time", TimeUnit::Seconds),
+	("response_time", TimeUnit::Milliseconds),
+];
+const METHOD_FIELDS: &[&str] = &["$request_method", "%REQ(:METHOD)%", "method", "request_method"];
+const PATH_FIELDS: &[&str] = &[
+	"$request_uri",
+	"$uri",
+	"%REQ(X-ENVOY-ORIGINAL-PATH?:PATH)%",
+	"%REQ(:PATH)%",
+	"path",
+	"uri",
+	"request_uri",
+	"url",
+];
+const STATUS_FIELDS: &[&str] = &["$status", "%RESPONSE_CODE%", "status", "response_code", "status_code"];
+/// Fields holding a whole request line, `GET /path HTTP/1.1`.
+const REQUEST_FIELDS: &[&str] = &["$request", "request"];
+
+pub const DEFAULT_QUANTILES: &[f64] = &[0.5, 0.9, 0.95, 0.99];
+
+/// Samples kept per route; beyond this a uniform reservoir sample is kept.
+const MAX_SAMPLES: usize = 50_000;
+/// Routes with fewer samples get a fixed latency at their median.
+const MIN_FIT_SAMPLES: usize = 5;
+/// How much lower a mixture's KS statistic must be than the best single
+/// distribution's for the extra parameters to be worth it.
+const MIXTURE_MIN_GAIN: f64 = 0.02;
+const EM_ITERATIONS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+	Seconds,
+	Milliseconds,
+	Microseconds,
+}
+
+impl TimeUnit {
+	pub fn parse(value: &str) -> Option<Self> {
+		match value {
+			"s" => Some(Self::Seconds),
+			"ms" => Some(Self::Milliseconds),
+			"us" => Some(Self::Microseconds),
+			_ => None,
+		}
+	}
+
+	fn to_ms(self, value: f64) -> f64 {
+		match self {
+			Self::Seconds => value * 1000.0,
+			Self::Milliseconds => value,
+			Self::Microseconds => value / 1000.0,
+		}
+	}
+}
+
+/// How each line of the log is laid out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogFormat {
+	/// nginx `log_format` (`$variable`) or envoy (`%COMMAND%`) syntax.
+	Template(String),
+	/// One JSON object per line.
+	Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessLogOptions {
+	pub format: LogFormat,
+	/// Field with the response time; by default the first timing field the
+	/// format has, e.g. `$request_time` or `%DURATION%`.
+	pub duration_field: Option<String>,
+	/// Unit of the response time; by default seconds for nginx's timing
+	/// variables and milliseconds otherwise.
+	pub duration_unit: Option<TimeUnit>,
+	/// Reported per route, each between 0 and 1.
+	pub quantiles: Vec<f64>,
+	/// Turn numeric and UUID path segments into parameters.
+	pub template_paths: bool,
+	/// Routes of existing endpoints; matching requests are grouped under them.
+	pub routes: Vec<(HttpMethod, String)>,
+}
+
+impl Default for AccessLogOptions {
+	fn default() -> Self {
+		Self {
+			format: LogFormat::Template(NGINX_FORMAT.to_string()),
+			duration_field: None,
+			duration_unit: None,
+			quantiles: DEFAULT_QUANTILES.to_vec(),
+			template_paths: true,
+			routes: Vec::new(),
+		}
+	}
+}
+
+impl AccessLogOptions {
+	/// Read `log_format` (`nginx`, `envoy`, `json` or a template),
+	/// `duration_field`, `duration_unit` (`s`, `ms` or `us`), `quantiles`
+	/// (comma separated) and `template_paths`.
+	pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self, String> {
+		let mut options = Self::default();
+		for (key, value) in pairs {
+			match key {
+				"log_format" => {
+					options.format = match value {
+						"nginx" => LogFormat::Template(NGINX_FORMAT.to_string()),
+						"envoy" => LogFormat::Template(ENVOY_FORMAT.to_string()),
+						"json" => LogFormat::Json,
+						template if template.contains('$') || template.contains('%') => {
+							LogFormat::Template(template.to_string())
+						}
+						_ => return Err("log_format must be nginx, envoy, json or a format template".to_string()),
+					}
+				}
+				"duration_field" => options.duration_field = Some(value.to_string()),
+				"duration_unit" => {
+					options.duration_unit =
+						Some(TimeUnit::parse(value).ok_or_else(|| "duration_unit must be s, ms or us".to_string())?)
+				}
+				"quantiles" => {
+					options.quantiles = value
+						.split(',')
+						.map(|quantile| match quantile.trim().parse::<f64>() {
+							Ok(quantile) if quantile > 0.0 && quantile <= 1.0 => Ok(quantile),
+							_ => Err(format!("invalid quantile '{}': must be > 0 and <= 1", quantile)),
+						})
+						.collect::<Result<_, _>>()?;
+				}
+				"template_paths" => {
+					options.template_paths = value.parse().map_err(|_| "template_paths must be true or false".to_string())?
+				}
+				_ => {}
+			}
+		}
+		Ok(options)
+	}
+}
+
+/// A fitted configuration, with how well each candidate fitted each route.
+#[derive(Debug, Clone)]
+pub struct FittedLog {
+	pub config: Configuration,
+	pub routes: Vec<RouteFit>,
+	pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteFit {
+	pub method: String,
+	pub path: String,
+	pub count: u64,
+	/// Observed latency in milliseconds by quantile, e.g. `p99`.
+	pub quantiles: BTreeMap<String, f64>,
+	/// Best fit first.
+	pub candidates: Vec<CandidateFit>,
+	pub selected: LatencyConfig,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateFit {
+	pub latency: LatencyConfig,
+	/// Kolmogorov-Smirnov statistic: the largest gap between the observed
+	/// and the fitted cumulative distribution, 0 for a perfect fit.
+	pub ks_statistic: f64,
+}
+
+impl RouteFit {
+	/// One line for a terminal.
+	pub fn summary(&self) -> String {
+		let quantiles: Vec<String> = self.quantiles.iter().map(|(name, ms)| format!("{}={}ms", name, ms)).collect();
+		let candidates: Vec<String> = self
+			.candidates
+			.iter()
+			.map(|candidate| format!("{} {}", distribution_name(&candidate.latency.distribution), candidate.ks_statistic))
+			.collect();
+		format!(
+			"{} {} n={} {} selected={} ks: {}",
+			self.method,
+			self.path,
+			self.count,
+			quantiles.join(" "),
+			distribution_name(&self.selected.distribution),
+			if candidates.is_empty() { "-".to_string() } else { candidates.join(", ") }
+		)
+	}
+}
+
+fn distribution_name(distribution: &DistributionType) -> String {
+	serde_json::to_value(distribution)
+		.ok()
+		.and_then(|value| value.as_str().map(str::to_string))
+		.unwrap_or_default()
+}
+
+struct LogLine {
+	method: HttpMethod,
+	path: String,
+	status: u16,
+	duration_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Literal(String),
+	Field(String),
+}
+
+struct RouteSamples {
+	method: HttpMethod,
+	path: String,
+	seen: u64,
+	samples_ms: Vec<f64>,
+	statuses: BTreeMap<u16, u64>,
+}
+
+/// Group the log's requests by route and fit each route's latency.
+pub fn fit_access_log(content: &str, options: &AccessLogOptions) -> Result<FittedLog, ConfigError> {
+	let tokens = match &options.format {
+		LogFormat::Template(template) => Some(tokenize(template)),
+		LogFormat::Json => None,
+	};
+	let mut warnings = Vec::new();
+	let mut unparsed = Vec::new();
+	let mut routes: Vec<RouteSamples> = Vec::new();
+	let mut index: HashMap<(String, String), usize> = HashMap::new();
+	// Seeded so the same log always fits the same way.
+	let mut rng = StdRng::seed_from_u64(0);
+
+	for (number, line) in content.lines().enumerate() {
+		let line = line.trim_end();
+		if line.is_empty() {
+			continue;
+		}
+		let fields = match &tokens {
+			Some(tokens) => match_template(tokens, line),
+			None => json_fields(line),
+		};
+		let Some(line) = fields.and_then(|fields| log_line(&fields, options)) else {
+			unparsed.push(number + 1);
+			continue;
+		};
+
+		let path = route_path(&line, options);
+		let key = (method_to_string(&line.method).to_string(), path.clone());
+		let position = *index.entry(key).or_insert_with(|| {
+			routes.push(RouteSamples {
+				method: line.method.clone(),
+				path,
+				seen: 0,
+				samples_ms: Vec::new(),
+				statuses: BTreeMap::new(),
+			});
+			routes.len() - 1
+		});
+		let route = &mut routes[position];
+		route.seen += 1;
+		*route.statuses.entry(line.status).or_default() += 1;
+		if route.samples_ms.len() < MAX_SAMPLES {
+			route.samples_ms.push(line.duration_ms);
+		} else {
+			let slot = rng.gen_range(0..route.seen) as usize;
+			if slot < MAX_SAMPLES {
+				route.samples_ms[slot] = line.duration_ms;
+			}
+		}
+	}
+
+	if routes.is_empty() {
+		return Err(ConfigError::ParseError(format!(
+			"no line matched the log format ({} line(s) read)",
+			unparsed.len()
+		)));
+	}
+	if let Some(first) = unparsed.first() {
+		warnings.push(format!(
+			"{} line(s) did not match the log format or had no timing, the first being line {}",
+			unparsed.len(),
+			first
+		));
+	}
+
+	let mut ids = HashSet::new();
+	let mut fits = Vec::new();
+	let mut endpoints = Vec::new();
+	for mut route in routes {
+		route.samples_ms.sort_by(f64::total_cmp);
+		let fit = fit_route(&route, &options.quantiles);
+		if fit.candidates.is_empty() {
+			warnings.push(format!(
+				"{} {}: {} request(s) are too few to fit, using the median",
+				fit.method, fit.path, route.seen
+			));
+		}
+		let id = unique_id(&mut ids, endpoint_id(&route.method, &route.path));
+		endpoints.push(Endpoint::new(
+			id,
+			route.method.clone(),
+			route.path.clone(),
+			fit.selected.clone(),
+			Response {
+				status: typical_status(&route.statuses),
+				headers: HashMap::new(),
+				body: String::new(),
+			},
+		));
+		fits.push(fit);
+	}
+
+	let metadata = Metadata {
+		name: Some("access-log".to_string()),
+		description: Some(format!("Latencies fitted from {} logged request(s)", fits.iter().map(|fit| fit.count).sum::<u64>())),
+		author: None,
+		created: Some(chrono::Utc::now().to_rfc3339()),
+	};
+	Ok(FittedLog {
+		config: Configuration::from_endpoints(metadata, endpoints),
+		routes: fits,
+		warnings,
+	})
+}
+
+/// `fitted` with each endpoint `active` already has replaced by the active
+/// endpoint carrying the fitted latency, so that merging it into `active`
+/// changes only latencies there.
+pub fn refit_latencies(active: &Configuration, mut fitted: Configuration) -> Configuration {
+	for endpoint in &mut fitted.endpoints {
+		let existing = active
+			.endpoints
+			.iter()
+			.find(|existing| existing.method == endpoint.method && existing.path == endpoint.path);
+		if let Some(existing) = existing {
+			let latency = endpoint.latency.clone();
+			*endpoint = existing.clone();
+			endpoint.latency = latency;
+		}
+	}
+	fitted
+}
+
+/// `$name` and `%NAME%` fields between literal text.
+fn tokenize(template: &str) -> Vec<Token> {
+	let mut tokens = Vec::new();
+	let mut literal = String::new();
+	let mut chars = template.char_indices().peekable();
+	while let Some((start, c)) = chars.next() {
+		let end = match c {
+			'$' => {
+				let mut end = start + 1;
+				while let Some(&(index, next)) = chars.peek() {
+					if !(next.is_ascii_alphanumeric() || next == '_') {
+						break;
+					}
+					end = index + next.len_utf8();
+					chars.next();
+				}
+				(end > start + 1).then_some(end)
+			}
+			'%' => template[start + 1..].find('%').map(|offset| {
+				let end = start + 1 + offset + 1;
+				while chars.peek().is_some_and(|&(index, _)| index < end) {
+					chars.next();
+				}
+				end
+			}),
+			_ => None,
+		};
+		match end {
+			Some(end) => {
+				if !literal.is_empty() {
+					tokens.push(Token::Literal(std::mem::take(&mut literal)));
+				}
+				tokens.push(Token::Field(template[start..end].to_string()));
+			}
+			None => literal.push(c),
+		}
+	}
+	if !literal.is_empty() {
+		tokens.push(Token::Literal(literal));
+	}
+	tokens
+}
+
+/// Field values of a line laid out as `tokens`. A field runs up to the
+/// literal text after it, or up to the next space when another field follows.
+fn match_template(tokens: &[Token], line: &str) -> Option<HashMap<String, String>> {
+	let mut fields = HashMap::new();
+	let mut rest = line;
+	for (index, token) in tokens.iter().enumerate() {
+		match token {
+			Token::Literal(literal) => rest = rest.strip_prefix(literal.as_str())?,
+			Token::Field(name) => {
+				let end = match tokens.get(index + 1) {
+					Some(Token::Literal(next)) => rest.find(next.as_str())?,
+					Some(Token::Field(_)) => rest.find(' ').unwrap_or(rest.len()),
+					None => rest.len(),
+				};
+				fields.insert(name.clone(), rest[..end].to_string());
+				rest = &rest[end..];
+			}
+		}
+	}
+	Some(fields)
+}
+
+fn json_fields(line: &str) -> Option<HashMap<String, String>> {
+	let Value::Object(object) = serde_json::from_str::<Value>(line).ok()? else {
+		return None;
+	};
+	Some(
+		object
+			.into_iter()
+			.map(|(key, value)| {
+				let value = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+				(key, value)
+			})
+			.collect(),
+	)
+}
+
+fn log_line(fields: &HashMap<String, String>, options: &AccessLogOptions) -> Option<LogLine> {
+	let find = |names: &[&str]| names.iter().find_map(|name| fields.get(*name)).map(String::as_str);
+	let request = find(REQUEST_FIELDS).map(|request| request.split_whitespace().collect::<Vec<_>>());
+	let method = find(METHOD_FIELDS).or_else(|| request.as_ref().and_then(|request| request.first().copied()))?;
+	let path = find(PATH_FIELDS).or_else(|| request.as_ref().and_then(|request| request.get(1).copied()))?;
+	let (_, method) = METHODS.iter().find(|(name, _)| name.eq_ignore_ascii_case(method))?;
+	let status = find(STATUS_FIELDS)?.trim().parse().ok()?;
+
+	let (value, unit) = match &options.duration_field {
+		Some(field) => {
+			let default = TIMING_FIELDS.iter().find(|(name, _)| name == field).map(|(_, unit)| *unit);
+			(fields.get(field)?, default.unwrap_or(TimeUnit::Milliseconds))
+		}
+		None => TIMING_FIELDS.iter().find_map(|(name, unit)| Some((fields.get(*name)?, *unit)))?,
+	};
+	let unit = options.duration_unit.unwrap_or(unit);
+	Some(LogLine {
+		method: method.clone(),
+		path: path.split(['?', '#']).next().unwrap_or("/").to_string(),
+		status,
+		duration_ms: unit.to_ms(duration(value)?),
+	})
+}
+
+/// A logged duration; nginx lists one per upstream tried, e.g. `0.010, 0.250`.
+fn duration(value: &str) -> Option<f64> {
+	let parts: Option<Vec<f64>> = value
+		.split(',')
+		.map(|part| part.trim().parse::<f64>().ok().filter(|part| part.is_finite() && *part >= 0.0))
+		.collect();
+	Some(parts?.iter().sum())
+}
+
+fn route_path(line: &LogLine, options: &AccessLogOptions) -> String {
+	let known = options
+		.routes
+		.iter()
+		.find(|(method, path)| *method == line.method && match_path(path, &line.path).is_some());
+	match known {
+		Some((_, path)) => path.clone(),
+		None if options.template_paths => template_path(&line.path),
+		None => line.path.clone(),
+	}
+}
+
+/// Most frequent 2xx status, else the most frequent status.
+fn typical_status(statuses: &BTreeMap<u16, u64>) -> u16 {
+	let most = |success: bool| {
+		statuses
+			.iter()
+			.filter(|(status, _)| !success || (200..300).contains(*status))
+			.max_by_key(|(_, count)| **count)
+			.map(|(status, _)| *status)
+	};
+	most(true).or_else(|| most(false)).unwrap_or(200)
+}
+
+fn fit_route(route: &RouteSamples, quantiles: &[f64]) -> RouteFit {
+	let samples = &route.samples_ms;
+	let observed = quantiles
+		.iter()
+		.map(|quantile| (quantile_name(*quantile), round(quantile_of(samples, *quantile), 10.0)))
+		.collect();
+	let median = quantile_of(samples, 0.5);
+	let spread = samples.first() != samples.last();
+
+	let mut candidates = Vec::new();
+	if samples.len() >= MIN_FIT_SAMPLES && spread {
+		let latencies = [
+			Some(fit_normal(samples)),
+			Some(fit_latency(samples)),
+			Some(fit_exponential(samples)),
+			fit_mixture(samples),
+		];
+		candidates = latencies
+			.into_iter()
+			.flatten()
+			.map(|latency| CandidateFit {
+				ks_statistic: round(ks_statistic(samples, &latency), 10_000.0),
+				latency,
+			})
+			.collect();
+		candidates.sort_by(|a, b| a.ks_statistic.total_cmp(&b.ks_statistic));
+	}
+
+	let is_mixture = |candidate: &&CandidateFit| candidate.latency.distribution == DistributionType::Mixture;
+	let single = candidates.iter().find(|candidate| !is_mixture(candidate));
+	let mixture = candidates.iter().find(is_mixture);
+	let selected = match (single, mixture) {
+		(Some(single), Some(mixture)) if mixture.ks_statistic + MIXTURE_MIN_GAIN <= single.ks_statistic => {
+			mixture.latency.clone()
+		}
+		(Some(single), _) => single.latency.clone(),
+		_ => LatencyConfig {
+			distribution: DistributionType::Fixed,
+			params: DistributionParams::Fixed { delay_ms: round(median, 10.0) },
+		},
+	};
+	RouteFit {
+		method: method_to_string(&route.method).to_string(),
+		path: route.path.clone(),
+		count: route.seen,
+		quantiles: observed,
+		candidates,
+		selected,
+	}
+}
+
+/// `p50`, `p99.9`, ...
+fn quantile_name(quantile: f64) -> String {
+	format!("p{}", round(quantile * 100.0, 1000.0))
+}
+
+/// Linear interpolation between the closest ranks of sorted samples.
+fn quantile_of(sorted: &[f64], quantile: f64) -> f64 {
+	if sorted.is_empty() {
+		return 0.0;
+	}
+	let position = quantile.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+	let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+	sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+fn fit_normal(samples: &[f64]) -> LatencyConfig {
+	let (mean_ms, variance) = moments(samples);
+	LatencyConfig {
+		distribution: DistributionType::Normal,
+		params: DistributionParams::Normal {
+			mean_ms: round(mean_ms, 10.0),
+			stddev_ms: round(variance.sqrt(), 10.0),
+		},
+	}
+}
+
+fn fit_exponential(samples: &[f64]) -> LatencyConfig {
+	let (mean_ms, _) = moments(samples);
+	LatencyConfig {
+		distribution: DistributionType::Exponential,
+		params: DistributionParams::Exponential {
+			rate: round(1.0 / mean_ms.max(0.1), 1_000_000.0),
+		},
+	}
+}
+
+/// Two log-normal components fitted by expectation-maximization on the
+/// logarithms, e.g. a fast cached path and a slow one. `None` when the
+/// samples do not separate into two groups.
+fn fit_mixture(sorted: &[f64]) -> Option<LatencyConfig> {
+	let logs: Vec<f64> = sorted.iter().map(|sample| sample.max(0.1).ln()).collect();
+	let (lower, upper) = logs.split_at(logs.len() / 2);
+	let (mean_a, variance_a) = moments(lower);
+	let (mean_b, variance_b) = moments(upper);
+	let mut weights = [0.5, 0.5];
+	let mut means = [mean_a, mean_b];
+	let floor = 1e-4;
+	let mut variances = [variance_a.max(floor), variance_b.max(floor)];
+
+	let mut responsibilities = vec![0.0; logs.len()];
+	for _ in 0..EM_ITERATIONS {
+		for (log, responsibility) in logs.iter().zip(responsibilities.iter_mut()) {
+			let a = weights[0] * gaussian_density(*log, means[0], variances[0]);
+			let b = weights[1] * gaussian_density(*log, means[1], variances[1]);
+			*responsibility = if a + b > 0.0 { a / (a + b) } else { 0.5 };
+		}
+		for component in 0..2 {
+			let share = |r: f64| if component == 0 { r } else { 1.0 - r };
+			let total: f64 = responsibilities.iter().map(|r| share(*r)).sum();
+			if total < 1e-9 {
+				return None;
+			}
+			let mean = logs.iter().zip(&responsibilities).map(|(log, r)| share(*r) * log).sum::<f64>() / total;
+			let variance =
+				logs.iter().zip(&responsibilities).map(|(log, r)| share(*r) * (log - mean).powi(2)).sum::<f64>() / total;
+			weights[component] = total / logs.len() as f64;
+			means[component] = mean;
+			variances[component] = variance.max(floor);
+		}
+	}
+	if weights.iter().any(|weight| *weight < 0.01) {
+		return None;
+	}
+
+	let mut components: Vec<MixtureComponent> = (0..2)
+		.map(|component| {
+			let mean_ms = (means[component] + variances[component] / 2.0).exp();
+			let stddev_ms = mean_ms * (variances[component].exp() - 1.0).sqrt();
+			MixtureComponent {
+				weight: round(weights[component], 1000.0),
+				distribution: DistributionType::LogNormal,
+				params: Box::new(DistributionParams::LogNormal {
+					mean_ms: round(mean_ms, 10.0).max(0.1),
+					stddev_ms: round(stddev_ms, 10.0),
+				}),
+			}
+		})
+		.collect();
+	components.sort_by(|a, b| mean_of(&a.params).total_cmp(&mean_of(&b.params)));
+	Some(LatencyConfig {
+		distribution: DistributionType::Mixture,
+		params: DistributionParams::Mixture { components },
+	})
+}
+
+fn mean_of(params: &DistributionParams) -> f64 {
+	match params {
+		DistributionParams::LogNormal { mean_ms, .. } => *mean_ms,
+		_ => 0.0,
+	}
+}
+
+fn gaussian_density(x: f64, mean: f64, variance: f64) -> f64 {
+	(-(x - mean).powi(2) / (2.0 * variance)).exp() / (2.0 * std::f64::consts::PI * variance).sqrt()
+}
+
+/// Mean and population variance.
+fn moments(values: &[f64]) -> (f64, f64) {
+	if values.is_empty() {
+		return (0.0, 0.0);
+	}
+	let mean = values.iter().sum::<f64>() / values.len() as f64;
+	let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / values.len() as f64;
+	(mean, variance)
+}
+
+fn ks_statistic(sorted: &[f64], latency: &LatencyConfig) -> f64 {
+	let n = sorted.len() as f64;
+	sorted
+		.iter()
+		.enumerate()
+		.map(|(index, sample)| {
+			let fitted = cdf(&latency.distribution, &latency.params, *sample);
+			(fitted - index as f64 / n).max((index + 1) as f64 / n - fitted)
+		})
+		.fold(0.0, f64::max)
+}
+
+/// Cumulative probability of `ms` under a configured distribution, as the
+/// engine would sample it.
+fn cdf(distribution: &DistributionType, params: &DistributionParams, ms: f64) -> f64 {
+	match (distribution, params) {
+		(_, DistributionParams::Fixed { delay_ms }) => FixedDistribution::new(*delay_ms).cdf(ms),
+		(DistributionType::LogNormal, DistributionParams::Normal { mean_ms, stddev_ms })
+		| (_, DistributionParams::LogNormal { mean_ms, stddev_ms }) => {
+			LogNormalDistribution::new(*mean_ms, *stddev_ms).cdf(ms)
+		}
+		(_, DistributionParams::Normal { mean_ms, stddev_ms }) => NormalDistribution::new(*mean_ms, *stddev_ms).cdf(ms),
+		(_, DistributionParams::Exponential { rate }) => ExponentialDistribution::new(*rate).cdf(ms),
+		(_, DistributionParams::Uniform { min_ms, max_ms }) => UniformDistribution::new(*min_ms, *max_ms).cdf(ms),
+		(_, DistributionParams::Mixture { components }) => {
+			let total: f64 = components.iter().map(|component| component.weight.max(0.0)).sum();
+			if total <= 0.0 {
+				return 1.0;
+			}
+			components
+				.iter()
+				.map(|component| component.weight.max(0.0) * cdf(&component.distribution, &component.params, ms))
+				.sum::<f64>()
+				/ total
+		}
+	}
+}
+
+fn round(value: f64, scale: f64) -> f64 {
+	(value * scale).round() / scale
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::validate;
+	use rand_distr::{Distribution as RandDistribution, LogNormal};
+
+	fn nginx_line(method: &str, path: &str, status: u16, seconds: f64) -> String {
+		format!(
+			r#"10.0.0.1 - - [18/Oct/2026:10:00:00 +0000] "{} {} HTTP/1.1" {} 512 "-" "curl/8.0" {:.3}"#,
+			method, path, status, seconds
+		)
+	}
+
+	#[test]
+	fn test_tokenize_and_match_envoy() {
+		let tokens = tokenize(r#"[%START_TIME%] "%REQ(:METHOD)% %REQ(:PATH)%" %DURATION%"#);
+		assert_eq!(tokens[1], Token::Field("%START_TIME%".to_string()));
+		let fields = match_template(&tokens, r#"[2026-10-18T10:00:00.000Z] "GET /health?deep=1" 12"#).expect("match");
+		assert_eq!(fields["%REQ(:PATH)%"], "/health?deep=1");
+		assert_eq!(fields["%DURATION%"], "12");
+		assert!(match_template(&tokens, "garbage").is_none());
+	}
+
+	#[test]
+	fn test_fit_nginx_log() {
+		let mut rng = StdRng::seed_from_u64(7);
+		let slow = LogNormal::new(80f64.ln(), 0.5).unwrap();
+		let mut log = Vec::new();
+		for index in 0..2000 {
+			log.push(nginx_line("GET", &format!("/users/{}?expand=1", index % 50), 200, slow.sample(&mut rng) / 1000.0));
+		}
+		for _ in 0..3 {
+			log.push(nginx_line("POST", "/users", 201, 0.030));
+		}
+		log.push("not a log line".to_string());
+		let fitted = fit_access_log(&log.join("\n"), &AccessLogOptions::default()).expect("fit");
+
+		assert!(validate(&fitted.config).is_ok());
+		assert_eq!(fitted.config.endpoints.len(), 2);
+		let users = &fitted.routes[0];
+		assert_eq!((users.path.as_str(), users.count), ("/users/{id}", 2000));
+		assert!((70.0..90.0).contains(&users.quantiles["p50"]), "{:?}", users.quantiles);
+		assert_eq!(users.candidates.len(), 4);
+		assert_eq!(users.selected.distribution, DistributionType::LogNormal);
+		let normal = users.candidates.iter().find(|candidate| candidate.latency.distribution == DistributionType::Normal);
+		assert!(users.candidates[0].ks_statistic < normal.unwrap().ks_statistic);
+
+		let create = &fitted.routes[1];
+		assert!(create.candidates.is_empty());
+		assert!(matches!(create.selected.params, DistributionParams::Fixed { delay_ms } if delay_ms == 30.0));
+		assert_eq!(fitted.config.endpoints[1].response.status, 201);
+		assert!(fitted.warnings.iter().any(|warning| warning.starts_with("1 line(s) did not match")));
+	}
+
+	#[test]
+	fn test_fit_bimodal_json_log_as_mixture() {
+		let mut rng = StdRng::seed_from_u64(11);
+		let fast = LogNormal::new(5f64.ln(), 0.2).unwrap();
+		let slow = LogNormal::new(200f64.ln(), 0.3).unwrap();
+		let log: Vec<String> = (0..3000)
+			.map(|index| {
+				let duration = if index % 4 == 0 { slow.sample(&mut rng) } else { fast.sample(&mut rng) };
+				format!(r#"{{"method": "GET", "path": "/search", "response_code": 200, "duration": {:.2}}}"#, duration)
+			})
+			.collect();
+		let options = AccessLogOptions::from_pairs([("log_format", "json"), ("quantiles", "0.5,0.999")]).expect("options");
+		let fitted = fit_access_log(&log.join("\n"), &options).expect("fit");
+
+		let search = &fitted.routes[0];
+		assert_eq!(search.quantiles.keys().collect::<Vec<_>>(), vec!["p50", "p99.9"]);
+		assert_eq!(search.selected.distribution, DistributionType::Mixture);
+		let DistributionParams::Mixture { components } = &search.selected.params else {
+			panic!("not a mixture");
+		};
+		assert!((components[0].weight - 0.75).abs() < 0.03, "{:?}", components);
+		assert!(validate(&fitted.config).is_ok());
+	}
+
+	#[test]
+	fn test_known_routes_and_refit() {
+		let log = [
+			nginx_line("GET", "/orders/abc", 200, 0.010),
+			nginx_line("GET", "/orders/def", 200, 0.020),
+		]
+		.join("\n");
+		let active = crate::config::parse_yaml(
+			r#"
+version: "1.0"
+endpoints:
+  - id: "order"
+    method: "GET"
+    path: "/orders/{orderId}"
+    latency: { distribution: "fixed", params: { delay_ms: 1 } }
+    response: { status: 200, body: '{"id": "abc"}' }
+"#,
+		)
+		.expect("config");
+		let options = AccessLogOptions {
+			routes: vec![(HttpMethod::Get, "/orders/{orderId}".to_string())],
+			..AccessLogOptions::default()
+		};
+		let fitted = fit_access_log(&log, &options).expect("fit");
+		assert_eq!(fitted.routes[0].path, "/orders/{orderId}");
+
+		let refitted = refit_latencies(&active, fitted.config);
+		assert_eq!(refitted.endpoints[0].id, "order");
+		assert_eq!(refitted.endpoints[0].response.body, r#"{"id": "abc"}"#);
+		assert!(matches!(refitted.endpoints[0].latency.params, DistributionParams::Fixed { delay_ms } if delay_ms == 15.0));
+		assert!(AccessLogOptions::from_pairs([("log_format", "apache")]).is_err());
+	}
+}
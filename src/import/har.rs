@@ -0,0 +1,245 @@
+// HAR import
+//
+// Endpoints from the entries of an HTTP Archive, as saved by browser dev
+// tools or proxies. Entries are grouped by method and path, and each route's
+// latency and error rate are fitted from the captured exchanges
+
+use crate::config::{ConfigError, Metadata, Response};
+use crate::import::{parse_document, Imported, Traffic, METHODS};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Which entries become endpoints.
+#[derive(Debug, Clone)]
+pub struct HarOptions {
+	/// Hosts to keep, with or without a port; empty keeps every host.
+	pub hosts: Vec<String>,
+	/// Turn numeric and UUID path segments into parameters.
+	pub template_paths: bool,
+}
+
+impl Default for HarOptions {
+	fn default() -> Self {
+		Self {
+			hosts: Vec::new(),
+			template_paths: true,
+		}
+	}
+}
+
+impl HarOptions {
+	/// Read `host` (comma separated, may repeat) and `template_paths`.
+	pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self, String> {
+		let mut options = Self::default();
+		for (key, value) in pairs {
+			match key {
+				"host" => options.hosts.extend(
+					value.split(',').map(str::trim).filter(|host| !host.is_empty()).map(str::to_ascii_lowercase),
+				),
+				"template_paths" => {
+					options.template_paths = value.parse().map_err(|_| "template_paths must be true or false".to_string())?
+				}
+				_ => {}
+			}
+		}
+		Ok(options)
+	}
+
+	fn keeps(&self, host: &str) -> bool {
+		let hostname = host.rsplit_once(':').map(|(name, _)| name).unwrap_or(host);
+		self.hosts.is_empty() || self.hosts.iter().any(|kept| kept == host || kept == hostname)
+	}
+}
+
+/// Build a configuration from a HAR 1.2 document.
+pub fn import_har(content: &str, options: &HarOptions) -> Result<Imported, ConfigError> {
+	let document = parse_document(content)?;
+	let Some(entries) = document.pointer("/log/entries").and_then(Value::as_array) else {
+		return Err(ConfigError::ParseError("not a HAR document: 'log.entries' is missing".to_string()));
+	};
+
+	let mut warnings = Vec::new();
+	let mut filtered: BTreeMap<String, usize> = BTreeMap::new();
+	let mut traffic = Traffic::new(options.template_paths);
+	for (index, entry) in entries.iter().enumerate() {
+		let text = |pointer: &str| entry.pointer(pointer).and_then(Value::as_str).unwrap_or_default();
+		let (method_name, url) = (text("/request/method"), text("/request/url"));
+		let Some((host, path)) = split_url(url) else {
+			warnings.push(format!("entry {}: URL '{}' is not absolute, skipped", index, url));
+			continue;
+		};
+		if !options.keeps(&host) {
+			*filtered.entry(host).or_default() += 1;
+			continue;
+		}
+		let Some((_, method)) = METHODS.iter().find(|(name, _)| name.eq_ignore_ascii_case(method_name)) else {
+			warnings.push(format!("{} {}: method not supported, skipped", method_name, path));
+			continue;
+		};
+		let status = entry.pointer("/response/status").and_then(Value::as_u64).unwrap_or(0);
+		let Ok(status @ 100..=599) = u16::try_from(status) else {
+			warnings.push(format!("{} {}: no response was captured, skipped", method_name, path));
+			continue;
+		};
+
+		let response = Response {
+			status,
+			headers: headers(entry),
+			body: body(entry, &format!("{} {}", method_name, path), &mut warnings),
+		};
+		traffic.add(method, &path, &response, latency_ms(entry));
+	}
+
+	for (host, count) in filtered {
+		warnings.push(format!("{} entries for host '{}' skipped by the host filter", count, host));
+	}
+	if traffic.dropped() > 0 {
+		warnings.push(format!("{} entries skipped beyond the route limit", traffic.dropped()));
+	}
+	if traffic.is_empty() {
+		warnings.push("no entries were imported".to_string());
+	}
+
+	let creator = document.pointer("/log/creator/name").and_then(Value::as_str).unwrap_or("a HAR file");
+	let metadata = Metadata {
+		name: document.pointer("/log/pages/0/title").and_then(Value::as_str).map(str::to_string),
+		description: Some(format!("Imported from {} ({} entries)", creator, entries.len())),
+		author: None,
+		created: Some(chrono::Utc::now().to_rfc3339()),
+	};
+	Ok(Imported {
+		config: traffic.configuration(metadata),
+		warnings,
+		report: None,
+	})
+}
+
+/// Lowercase host (with any port) and path, without query or fragment.
+fn split_url(url: &str) -> Option<(String, String)> {
+	let (_, rest) = url.split_once("://")?;
+	let rest = rest.split(['?', '#']).next().unwrap_or_default();
+	let (host, path) = match rest.find('/') {
+		Some(start) => rest.split_at(start),
+		None => (rest, "/"),
+	};
+	let host = host.rsplit_once('@').map(|(_, host)| host).unwrap_or(host);
+	Some((host.to_ascii_lowercase(), path.to_string()))
+}
+
+/// Server time for the exchange: `timings.wait` when recorded, else the
+/// entry's total `time`. HAR uses -1 for timings that were not measured.
+fn latency_ms(entry: &Value) -> f64 {
+	let wait = entry.pointer("/timings/wait").and_then(Value::as_f64).filter(|wait| *wait >= 0.0);
+	wait.or_else(|| entry.get("time").and_then(Value::as_f64)).unwrap_or(0.0).max(0.0)
+}
+
+/// Response headers, leaving out HTTP/2 pseudo-headers.
+fn headers(entry: &Value) -> HashMap<String, String> {
+	let list = entry.pointer("/response/headers").and_then(Value::as_array).cloned().unwrap_or_default();
+	list.iter()
+		.filter_map(|header| {
+			let name = header.get("name").and_then(Value::as_str)?;
+			let value = header.get("value").and_then(Value::as_str)?;
+			(!name.starts_with(':')).then(|| (name.to_string(), value.to_string()))
+		})
+		.collect()
+}
+
+fn body(entry: &Value, route: &str, warnings: &mut Vec<String>) -> String {
+	let Some(content) = entry.pointer("/response/content") else {
+		return String::new();
+	};
+	let text = content.get("text").and_then(Value::as_str).unwrap_or_default();
+	if content.get("encoding").and_then(Value::as_str) != Some("base64") {
+		return text.to_string();
+	}
+	match STANDARD.decode(text.trim()).ok().and_then(|bytes| String::from_utf8(bytes).ok()) {
+		Some(body) => body,
+		None => {
+			warnings.push(format!("{}: binary response body left out", route));
+			String::new()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{validate, DistributionParams, DistributionType, HttpMethod};
+	use serde_json::json;
+
+	fn entry(method: &str, url: &str, status: u16, wait: f64, body: &str) -> Value {
+		json!({
+			"time": wait + 5.0,
+			"request": { "method": method, "url": url, "headers": [] },
+			"response": {
+				"status": status,
+				"headers": [
+					{ "name": "Content-Type", "value": "application/json" },
+					{ "name": "Date", "value": "Tue, 01 Sep 2026 10:00:00 GMT" }
+				],
+				"content": { "mimeType": "application/json", "text": body }
+			},
+			"timings": { "send": 1, "wait": wait, "receive": 2 }
+		})
+	}
+
+	fn har(entries: Vec<Value>) -> String {
+		json!({ "log": { "version": "1.2", "creator": { "name": "Firefox" }, "entries": entries } }).to_string()
+	}
+
+	#[test]
+	fn test_import_har_groups_entries_by_route() {
+		let content = har(vec![
+			entry("GET", "https://api.example.com/users/1?expand=true", 200, 80.0, r#"{"id": 1}"#),
+			entry("GET", "https://api.example.com/users/2", 200, 120.0, r#"{"id": 2}"#),
+			entry("GET", "https://api.example.com/users/3", 404, 20.0, r#"{"error": "missing"}"#),
+			entry("GET", "https://api.example.com/users/4", 200, 95.0, r#"{"id": 4}"#),
+			entry("POST", "https://api.example.com/users", 201, 30.0, "{}"),
+			entry("GET", "https://cdn.example.com/logo.png", 200, 5.0, ""),
+			entry("CONNECT", "https://api.example.com/tunnel", 200, 5.0, ""),
+		]);
+		let options = HarOptions {
+			hosts: vec!["api.example.com".to_string()],
+			..HarOptions::default()
+		};
+		let Imported { config, warnings, .. } = import_har(&content, &options).expect("import");
+		assert!(validate(&config).is_ok());
+		assert_eq!(config.endpoints.len(), 2);
+
+		let users = &config.endpoints[0];
+		assert_eq!((users.method.clone(), users.path.as_str()), (HttpMethod::Get, "/users/{id}"));
+		assert_eq!(users.response.body, r#"{"id": 1}"#);
+		assert!(!users.response.headers.contains_key("Date"));
+		assert_eq!(users.latency.distribution, DistributionType::LogNormal);
+		assert_eq!(users.error_profile.rate, 0.25);
+		assert_eq!(users.error_profile.codes, vec![404]);
+		assert_eq!(users.error_profile.body, r#"{"error": "missing"}"#);
+		assert_eq!(config.endpoints[1].path, "/users");
+
+		assert!(warnings.iter().any(|warning| warning.contains("CONNECT /tunnel")));
+		assert!(warnings.iter().any(|warning| warning.contains("'cdn.example.com' skipped by the host filter")));
+	}
+
+	#[test]
+	fn test_import_har_options() {
+		let content = har(vec![
+			entry("GET", "http://localhost:3000/orders/7", 200, -1.0, "{}"),
+			entry("GET", "http://localhost:3000/orders/8", 200, 40.0, "{}"),
+		]);
+		let options = HarOptions::from_pairs([("host", "localhost"), ("template_paths", "false")]).expect("options");
+		let config = import_har(&content, &options).expect("import").config;
+		let paths: Vec<&str> = config.endpoints.iter().map(|endpoint| endpoint.path.as_str()).collect();
+		assert_eq!(paths, vec!["/orders/7", "/orders/8"]);
+		// An unmeasured wait falls back to the entry's total time.
+		assert!(matches!(
+			config.endpoints[0].latency.params,
+			DistributionParams::Fixed { delay_ms } if delay_ms == 4.0
+		));
+
+		assert!(HarOptions::from_pairs([("template_paths", "maybe")]).is_err());
+		assert!(import_har(r#"{"openapi": "3.0.0"}"#, &HarOptions::default()).is_err());
+	}
+}
@@ -0,0 +1,292 @@
+// Importers - build configurations from existing API descriptions
+//
+// Each importer turns a foreign format into a `Configuration`, filling in the
+// latency and errors the source does not describe from `ImportOptions`
+
+mod access_log;
+mod har;
+mod openapi;
+mod postman;
+mod traffic;
+mod wiremock;
+
+pub use access_log::*;
+pub use har::*;
+pub use openapi::*;
+pub use postman::*;
+pub use traffic::*;
+pub use wiremock::*;
+
+use crate::config::{
+	ConfigError, Configuration, DistributionParams, DistributionType, ErrorProfile, HttpMethod, LatencyConfig,
+};
+use crate::engine::method_to_string;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+
+/// Methods an endpoint can have, by their lowercase names.
+const METHODS: &[(&str, HttpMethod)] = &[
+	("get", HttpMethod::Get),
+	("post", HttpMethod::Post),
+	("put", HttpMethod::Put),
+	("delete", HttpMethod::Delete),
+	("patch", HttpMethod::Patch),
+	("head", HttpMethod::Head),
+	("options", HttpMethod::Options),
+];
+
+/// Defaults applied to every imported endpoint.
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+	pub latency: LatencyConfig,
+	/// Applied as given, except that empty `codes` are filled from the error
+	/// statuses the source documents.
+	pub error_profile: ErrorProfile,
+}
+
+impl Default for ImportOptions {
+	fn default() -> Self {
+		Self {
+			latency: LatencyConfig {
+				distribution: DistributionType::Fixed,
+				params: DistributionParams::Fixed { delay_ms: 50.0 },
+			},
+			error_profile: ErrorProfile::default(),
+		}
+	}
+}
+
+impl ImportOptions {
+	/// Read `latency_ms`, `latency_stddev_ms`, `error_rate` and `error_codes`
+	/// (comma separated). A stddev makes the latency normal instead of fixed.
+	pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self, String> {
+		let mut options = Self::default();
+		let mut latency_ms = 50.0;
+		let mut stddev_ms = None;
+		for (key, value) in pairs {
+			match key {
+				"latency_ms" => latency_ms = parse_number(key, value)?,
+				"latency_stddev_ms" => stddev_ms = Some(parse_number(key, value)?),
+				"error_rate" => options.error_profile.rate = parse_number(key, value)?,
+				"error_codes" => {
+					options.error_profile.codes = value
+						.split(',')
+						.map(|code| code.trim().parse::<u16>().map_err(|_| format!("invalid status code in error_codes: {}", code)))
+						.collect::<Result<_, _>>()?;
+				}
+				_ => {}
+			}
+		}
+		options.latency = match stddev_ms {
+			Some(stddev_ms) => LatencyConfig {
+				distribution: DistributionType::Normal,
+				params: DistributionParams::Normal { mean_ms: latency_ms, stddev_ms },
+			},
+			None => LatencyConfig {
+				distribution: DistributionType::Fixed,
+				params: DistributionParams::Fixed { delay_ms: latency_ms },
+			},
+		};
+		Ok(options)
+	}
+
+	/// The default error profile for one endpoint, given the error statuses
+	/// its source documents.
+	fn error_profile(&self, documented: &[u16]) -> ErrorProfile {
+		let mut profile = self.error_profile.clone();
+		if profile.rate > 0.0 && profile.codes.is_empty() {
+			profile.codes = if documented.is_empty() { vec![500] } else { documented.to_vec() };
+		}
+		profile
+	}
+}
+
+fn parse_number(key: &str, value: &str) -> Result<f64, String> {
+	value.trim().parse().map_err(|_| format!("{} must be a number", key))
+}
+
+/// How an imported configuration meets the active one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+	/// Add imported endpoints, replacing active ones with the same id or route.
+	Merge,
+	/// Use the imported configuration as is.
+	Replace,
+}
+
+impl ImportMode {
+	pub fn parse(value: &str) -> Option<Self> {
+		match value {
+			"merge" => Some(Self::Merge),
+			"replace" => Some(Self::Replace),
+			_ => None,
+		}
+	}
+}
+
+/// An imported configuration and what could not be carried over.
+#[derive(Debug, Clone)]
+pub struct Imported {
+	pub config: Configuration,
+	pub warnings: Vec<String>,
+	/// Per-stub conversion details, for formats migrated from other mock servers.
+	pub report: Option<ConversionReport>,
+}
+
+/// What happened to each stub of a mock server definition.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConversionReport {
+	/// Stubs read from the source.
+	pub total: usize,
+	pub converted: usize,
+	pub skipped: usize,
+	/// Source features that were ignored or approximated, with the stubs using them.
+	pub unsupported: BTreeMap<String, Vec<String>>,
+}
+
+impl ConversionReport {
+	fn unsupported(&mut self, feature: impl Into<String>, stub: &str) {
+		let stubs = self.unsupported.entry(feature.into()).or_default();
+		if !stubs.iter().any(|existing| existing == stub) {
+			stubs.push(stub.to_string());
+		}
+	}
+
+	/// One line per unsupported feature, e.g. for a terminal.
+	pub fn lines(&self) -> Vec<String> {
+		let mut lines = vec![format!(
+			"{} of {} stub(s) converted, {} skipped",
+			self.converted, self.total, self.skipped
+		)];
+		lines.extend(
+			self.unsupported
+				.iter()
+				.map(|(feature, stubs)| format!("{} ({}): {}", feature, stubs.len(), stubs.join(", "))),
+		);
+		lines
+	}
+}
+
+/// Imported endpoints on top of `active`. Everything else in `active`, its
+/// groups, behaviors and metadata included, is kept.
+pub fn merge_config(mut active: Configuration, imported: Configuration) -> Configuration {
+	active.endpoints.retain(|existing| {
+		!imported.endpoints.iter().any(|endpoint| {
+			endpoint.id == existing.id || (endpoint.method == existing.method && endpoint.path == existing.path)
+		})
+	});
+	active.endpoints.extend(imported.endpoints);
+	active
+}
+
+/// Parse a JSON or YAML document. YAML keys that are not strings, such as
+/// response codes, become strings.
+pub fn parse_document(content: &str) -> Result<Value, ConfigError> {
+	if content.trim_start().starts_with('{') {
+		if let Ok(value) = serde_json::from_str(content) {
+			return Ok(value);
+		}
+	}
+	serde_yaml::from_str::<serde_yaml::Value>(content)
+		.map(yaml_to_json)
+		.map_err(|e| ConfigError::ParseError(e.to_string()))
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+	match value {
+		serde_yaml::Value::Null => Value::Null,
+		serde_yaml::Value::Bool(value) => Value::Bool(value),
+		serde_yaml::Value::Number(number) => serde_json::to_value(number).unwrap_or(Value::Null),
+		serde_yaml::Value::String(value) => Value::String(value),
+		serde_yaml::Value::Sequence(items) => Value::Array(items.into_iter().map(yaml_to_json).collect()),
+		serde_yaml::Value::Mapping(mapping) => Value::Object(
+			mapping
+				.into_iter()
+				.map(|(key, value)| {
+					let key = match key {
+						serde_yaml::Value::String(key) => key,
+						other => serde_yaml::to_string(&other).unwrap_or_default().trim().to_string(),
+					};
+					(key, yaml_to_json(value))
+				})
+				.collect(),
+		),
+		serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+	}
+}
+
+/// Lowercase alphanumeric words of `name` joined by dashes, e.g. `list-pets`
+/// for `list_Pets`.
+pub fn slug(name: &str) -> String {
+	name.split(|c: char| !c.is_ascii_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.map(|word| word.to_ascii_lowercase())
+		.collect::<Vec<_>>()
+		.join("-")
+}
+
+/// Id for an endpoint known only by its route.
+pub fn endpoint_id(method: &HttpMethod, path: &str) -> String {
+	let path = slug(path);
+	let method = method_to_string(method).to_ascii_lowercase();
+	if path.is_empty() {
+		format!("{}-root", method)
+	} else {
+		format!("{}-{}", method, path)
+	}
+}
+
+/// `id`, or `id-2`, `id-3`, ... when it is already taken.
+pub fn unique_id(taken: &mut HashSet<String>, id: String) -> String {
+	let mut candidate = id.clone();
+	let mut suffix = 2;
+	while !taken.insert(candidate.clone()) {
+		candidate = format!("{}-{}", id, suffix);
+		suffix += 1;
+	}
+	candidate
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{Endpoint, Metadata, Response};
+	use std::collections::HashMap;
+
+	fn endpoint(id: &str, path: &str) -> Endpoint {
+		let response = Response {
+			status: 200,
+			headers: HashMap::new(),
+			body: id.to_string(),
+		};
+		Endpoint::new(id.to_string(), HttpMethod::Get, path.to_string(), ImportOptions::default().latency, response)
+	}
+
+	#[test]
+	fn test_merge_replaces_by_id_or_route() {
+		let active = Configuration::from_endpoints(
+			Metadata::default(),
+			vec![endpoint("health", "/health"), endpoint("users", "/users"), endpoint("old", "/orders")],
+		);
+		let imported = Configuration::from_endpoints(
+			Metadata::default(),
+			vec![endpoint("users", "/v2/users"), endpoint("list-orders", "/orders")],
+		);
+		let merged = merge_config(active, imported);
+		let ids: Vec<_> = merged.endpoints.iter().map(|endpoint| endpoint.id.as_str()).collect();
+		assert_eq!(ids, vec!["health", "users", "list-orders"]);
+		assert_eq!(merged.endpoints[1].path, "/v2/users");
+	}
+
+	#[test]
+	fn test_options_from_pairs() {
+		let options = ImportOptions::from_pairs([("latency_ms", "120"), ("latency_stddev_ms", "30"), ("error_rate", "0.1")])
+			.expect("options");
+		assert_eq!(options.latency.distribution, DistributionType::Normal);
+		assert_eq!(options.error_profile(&[]).codes, vec![500]);
+		assert_eq!(options.error_profile(&[404, 503]).codes, vec![404, 503]);
+		assert!(ImportOptions::from_pairs([("error_codes", "500,abc")]).is_err());
+	}
+}
@@ -0,0 +1,370 @@
+// OpenAPI import
+//
+// One endpoint per operation of an OpenAPI 3.0/3.1 document, answering with
+// the documented example or a body generated from the response schema
+
+use crate::config::{ConfigError, Configuration, Endpoint, Metadata, Response};
+use crate::import::{endpoint_id, parse_document, slug, unique_id, ImportOptions, Imported, METHODS};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Nesting followed when generating bodies, so recursive schemas terminate.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Build a configuration from an OpenAPI document in YAML or JSON.
+pub fn import_openapi(content: &str, options: &ImportOptions) -> Result<Imported, ConfigError> {
+	let document = parse_document(content)?;
+	let version = document.get("openapi").and_then(Value::as_str).unwrap_or_default();
+	if !version.starts_with("3.") {
+		return Err(ConfigError::ParseError(
+			"not an OpenAPI 3 document: 'openapi' must be 3.0.x or 3.1.x".to_string(),
+		));
+	}
+
+	let mut warnings = Vec::new();
+	let base_path = base_path(&document, &mut warnings);
+	let mut ids = HashSet::new();
+	let mut endpoints = Vec::new();
+	let paths = document.get("paths").and_then(Value::as_object).cloned().unwrap_or_default();
+	for (path, item) in &paths {
+		let item = resolve(&document, item);
+		if item.get("trace").is_some() {
+			warnings.push(format!("TRACE {}: method not supported, skipped", path));
+		}
+		for (name, method) in METHODS {
+			let Some(operation) = item.get(*name) else {
+				continue;
+			};
+			let full_path = format!("{}{}", base_path, path);
+			let id = operation
+				.get("operationId")
+				.and_then(Value::as_str)
+				.map(slug)
+				.filter(|id| !id.is_empty())
+				.unwrap_or_else(|| endpoint_id(method, &full_path));
+			let (response, documented_errors) = operation_response(&document, operation);
+			let mut endpoint = Endpoint::new(
+				unique_id(&mut ids, id),
+				method.clone(),
+				full_path,
+				options.latency.clone(),
+				response,
+			);
+			endpoint.error_profile = options.error_profile(&documented_errors);
+			endpoints.push(endpoint);
+		}
+	}
+	if endpoints.is_empty() {
+		warnings.push("document has no operations".to_string());
+	}
+
+	let info = document.get("info");
+	let text = |key: &str| info.and_then(|info| info.get(key)).and_then(Value::as_str).map(str::to_string);
+	let metadata = Metadata {
+		name: text("title"),
+		description: text("description"),
+		author: None,
+		created: Some(chrono::Utc::now().to_rfc3339()),
+	};
+	Ok(Imported {
+		config: Configuration::from_endpoints(metadata, endpoints),
+		warnings,
+		report: None,
+	})
+}
+
+/// Path of the first server URL, e.g. `/v1` for `https://api.example.com/v1`.
+fn base_path(document: &Value, warnings: &mut Vec<String>) -> String {
+	let Some(url) = document.pointer("/servers/0/url").and_then(Value::as_str) else {
+		return String::new();
+	};
+	if url.contains('{') {
+		warnings.push(format!("server URL '{}' has variables; paths are imported without its base path", url));
+		return String::new();
+	}
+	let path = match url.split_once("://") {
+		Some((_, rest)) => rest.find('/').map(|start| &rest[start..]).unwrap_or(""),
+		None => url,
+	};
+	path.trim_end_matches('/').to_string()
+}
+
+/// The success response, else the first documented one, plus the error
+/// statuses the operation lists.
+fn operation_response(document: &Value, operation: &Value) -> (Response, Vec<u16>) {
+	let responses = operation.get("responses").and_then(Value::as_object).cloned().unwrap_or_default();
+	let mut documented: Vec<(u16, &Value)> = responses
+		.iter()
+		.filter_map(|(code, response)| Some((status_for(code)?, response)))
+		.collect();
+	documented.sort_by_key(|(status, _)| *status);
+	let errors = documented.iter().map(|(status, _)| *status).filter(|status| *status >= 400).collect();
+
+	let chosen = documented
+		.iter()
+		.find(|(status, _)| (200..300).contains(status))
+		.or_else(|| documented.first())
+		.map(|(status, response)| (*status, *response))
+		.or_else(|| responses.get("default").map(|response| (200, response)));
+	let Some((status, response)) = chosen else {
+		return (
+			Response {
+				status: 200,
+				headers: HashMap::new(),
+				body: String::new(),
+			},
+			errors,
+		);
+	};
+
+	let response = resolve(document, response);
+	let mut headers = HashMap::new();
+	let mut body = String::new();
+	if let Some((media_type, media)) = pick_media(&response) {
+		if let Some(example) = media_example(document, media) {
+			headers.insert("Content-Type".to_string(), media_type.to_string());
+			body = match example {
+				Value::String(text) if !media_type.contains("json") => text,
+				other => other.to_string(),
+			};
+		}
+	}
+	(Response { status, headers, body }, errors)
+}
+
+/// `200`, or the lowest code of a range like `2XX`. `default` is not a status.
+fn status_for(code: &str) -> Option<u16> {
+	if let Ok(status) = code.parse::<u16>() {
+		return Some(status);
+	}
+	let class = code.strip_suffix("XX").or_else(|| code.strip_suffix("xx"))?;
+	class.parse::<u16>().ok().filter(|class| (1..=5).contains(class)).map(|class| class * 100)
+}
+
+/// JSON content when offered, else the first media type.
+fn pick_media(response: &Value) -> Option<(&str, &Value)> {
+	let content = response.get("content")?.as_object()?;
+	content
+		.iter()
+		.find(|(media_type, _)| media_type.contains("json"))
+		.or_else(|| content.iter().next())
+		.map(|(media_type, media)| (media_type.as_str(), media))
+}
+
+fn media_example(document: &Value, media: &Value) -> Option<Value> {
+	if let Some(example) = media.get("example") {
+		return Some(example.clone());
+	}
+	let first = media.get("examples").and_then(Value::as_object).and_then(|examples| examples.values().next());
+	if let Some(example) = first.map(|example| resolve(document, example)) {
+		if let Some(value) = example.get("value") {
+			return Some(value.clone());
+		}
+	}
+	media.get("schema").map(|schema| schema_example(document, schema, 0))
+}
+
+/// An instance of `schema`: its own example or default when given, otherwise
+/// built from the types and formats it declares.
+fn schema_example(document: &Value, schema: &Value, depth: usize) -> Value {
+	let schema = resolve(document, schema);
+	if depth > MAX_SCHEMA_DEPTH {
+		return Value::Null;
+	}
+	for key in ["example", "default", "const"] {
+		if let Some(value) = schema.get(key) {
+			return value.clone();
+		}
+	}
+	if let Some(value) = schema.get("examples").and_then(Value::as_array).and_then(|examples| examples.first()) {
+		return value.clone();
+	}
+	if let Some(value) = schema.get("enum").and_then(Value::as_array).and_then(|values| values.first()) {
+		return value.clone();
+	}
+	if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+		let mut merged = Map::new();
+		for part in parts {
+			if let Value::Object(fields) = schema_example(document, part, depth + 1) {
+				merged.extend(fields);
+			}
+		}
+		return Value::Object(merged);
+	}
+	for key in ["oneOf", "anyOf"] {
+		if let Some(first) = schema.get(key).and_then(Value::as_array).and_then(|options| options.first()) {
+			return schema_example(document, first, depth + 1);
+		}
+	}
+
+	match schema_type(&schema) {
+		Some("object") => {
+			let properties = schema.get("properties").and_then(Value::as_object).cloned().unwrap_or_default();
+			Value::Object(
+				properties
+					.iter()
+					.map(|(name, property)| (name.clone(), schema_example(document, property, depth + 1)))
+					.collect(),
+			)
+		}
+		Some("array") => match schema.get("items") {
+			Some(items) => Value::Array(vec![schema_example(document, items, depth + 1)]),
+			None => Value::Array(vec![]),
+		},
+		Some("string") => Value::String(string_example(schema.get("format").and_then(Value::as_str))),
+		Some("integer") => schema.get("minimum").cloned().unwrap_or(Value::from(0)),
+		Some("number") => schema.get("minimum").cloned().unwrap_or(Value::from(0.0)),
+		Some("boolean") => Value::Bool(true),
+		_ => Value::Null,
+	}
+}
+
+/// The declared type; 3.1 type lists use their first non-null entry, and
+/// schemas with properties but no type are objects.
+fn schema_type(schema: &Value) -> Option<&str> {
+	match schema.get("type") {
+		Some(Value::String(kind)) => Some(kind),
+		Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).find(|kind| *kind != "null"),
+		_ => schema.get("properties").map(|_| "object"),
+	}
+}
+
+fn string_example(format: Option<&str>) -> String {
+	match format {
+		Some("date-time") => "2026-01-01T00:00:00Z",
+		Some("date") => "2026-01-01",
+		Some("uuid") => "00000000-0000-4000-8000-000000000000",
+		Some("email") => "user@example.com",
+		Some("uri") | Some("url") => "https://example.com",
+		Some("ipv4") => "192.0.2.1",
+		_ => "string",
+	}
+	.to_string()
+}
+
+/// Follow local `$ref`s (`#/components/...`). Unresolvable references give `null`.
+fn resolve(document: &Value, value: &Value) -> Value {
+	let mut current = value.clone();
+	for _ in 0..MAX_SCHEMA_DEPTH {
+		let Some(reference) = current.get("$ref").and_then(Value::as_str) else {
+			return current;
+		};
+		current = reference
+			.strip_prefix('#')
+			.and_then(|pointer| document.pointer(pointer))
+			.cloned()
+			.unwrap_or(Value::Null);
+	}
+	current
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::validate;
+
+	const SPEC: &str = r##"
+openapi: 3.0.3
+info:
+  title: Pets
+servers:
+  - url: https://pets.example.com/v1
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        200:
+          description: ok
+          content:
+            application/json:
+              schema:
+                type: array
+                items: { $ref: "#/components/schemas/Pet" }
+        503:
+          description: unavailable
+    post:
+      responses:
+        "201":
+          description: created
+          content:
+            application/json:
+              example: { id: 7, name: "Rex" }
+  /pets/{petId}:
+    get:
+      responses:
+        "2XX":
+          description: ok
+          content:
+            application/json:
+              schema: { $ref: "#/components/schemas/Pet" }
+        "404":
+          description: missing
+    delete:
+      responses:
+        "204": { description: gone }
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        id: { type: integer, format: int64 }
+        name: { type: string }
+        born: { type: string, format: date }
+        owner: { $ref: "#/components/schemas/Owner" }
+    Owner:
+      allOf:
+        - type: object
+          properties:
+            email: { type: string, format: email }
+        - type: object
+          properties:
+            tag: { type: [string, "null"], enum: [vip, regular] }
+"##;
+
+	#[test]
+	fn test_import_openapi_operations() {
+		let options = ImportOptions::from_pairs([("error_rate", "0.05")]).expect("options");
+		let imported = import_openapi(SPEC, &options).expect("import");
+		let config = imported.config;
+		assert!(validate(&config).is_ok());
+		assert_eq!(config.metadata.name.as_deref(), Some("Pets"));
+
+		let summary: Vec<_> = config
+			.endpoints
+			.iter()
+			.map(|endpoint| (endpoint.id.as_str(), endpoint.path.as_str(), endpoint.response.status))
+			.collect();
+		assert_eq!(
+			summary,
+			vec![
+				("listpets", "/v1/pets", 200),
+				("post-v1-pets", "/v1/pets", 201),
+				("get-v1-pets-petid", "/v1/pets/{petId}", 200),
+				("delete-v1-pets-petid", "/v1/pets/{petId}", 204),
+			]
+		);
+
+		let pets: Value = serde_json::from_str(&config.endpoints[0].response.body).unwrap();
+		assert_eq!(
+			pets,
+			serde_json::json!([{
+				"id": 0,
+				"name": "string",
+				"born": "2026-01-01",
+				"owner": {"email": "user@example.com", "tag": "vip"}
+			}])
+		);
+		assert_eq!(config.endpoints[1].response.body, r#"{"id":7,"name":"Rex"}"#);
+		assert_eq!(config.endpoints[0].error_profile.codes, vec![503]);
+		assert_eq!(config.endpoints[2].error_profile.codes, vec![404]);
+		assert_eq!(config.endpoints[3].error_profile.codes, vec![500]);
+		assert!(config.endpoints[3].response.body.is_empty());
+	}
+
+	#[test]
+	fn test_import_rejects_swagger_2() {
+		let err = import_openapi("swagger: \"2.0\"\npaths: {}\n", &ImportOptions::default()).unwrap_err();
+		assert!(err.to_string().contains("OpenAPI 3"));
+	}
+}
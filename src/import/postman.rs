@@ -0,0 +1,298 @@
+// Postman import
+//
+// Endpoints from the saved examples of a Postman collection (v2.0/v2.1), the
+// way a Postman mock server would answer them. What a Postman mock does
+// beyond serving one example per route is listed in the conversion report
+
+use crate::config::{ConfigError, Configuration, Endpoint, Metadata, Response};
+use crate::engine::method_to_string;
+use crate::import::traffic::stable_headers;
+use crate::import::{endpoint_id, parse_document, slug, unique_id, ConversionReport, ImportOptions, Imported, METHODS};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Requests of a collection, depth first, with their folder names.
+struct Request<'a> {
+	name: String,
+	item: &'a Value,
+}
+
+/// Build a configuration from a Postman collection. Every request with a
+/// saved example becomes an endpoint serving its first 2xx example; the
+/// error statuses of the other examples feed `options`' error profile.
+pub fn import_postman(content: &str, options: &ImportOptions) -> Result<Imported, ConfigError> {
+	let document = parse_document(content)?;
+	let (Some(info), Some(items)) = (document.get("info"), document.get("item").and_then(Value::as_array)) else {
+		return Err(ConfigError::ParseError(
+			"not a Postman collection: 'info' and 'item' are required".to_string(),
+		));
+	};
+	let mut requests = Vec::new();
+	collect(items, "", &mut requests);
+
+	let mut report = ConversionReport {
+		total: requests.len(),
+		..ConversionReport::default()
+	};
+	let mut warnings = Vec::new();
+	let mut ids = HashSet::new();
+	let mut routes: HashMap<(String, String), String> = HashMap::new();
+	let mut endpoints = Vec::new();
+	for Request { name, item } in &requests {
+		let mut endpoint = match convert(item, name, options, &mut report) {
+			Ok(endpoint) => endpoint,
+			Err(reason) => {
+				warnings.push(format!("{}: {}, skipped", name, reason));
+				report.skipped += 1;
+				continue;
+			}
+		};
+		let route = (method_to_string(&endpoint.method).to_string(), endpoint.path.clone());
+		if let Some(winner) = routes.get(&route) {
+			warnings.push(format!("{}: {} {} is already served by '{}', skipped", name, route.0, route.1, winner));
+			report.unsupported("several requests for one method and path (first kept)", name);
+			report.skipped += 1;
+			continue;
+		}
+		let id = item
+			.get("name")
+			.and_then(Value::as_str)
+			.map(slug)
+			.filter(|id| !id.is_empty())
+			.unwrap_or_else(|| endpoint_id(&endpoint.method, &endpoint.path));
+		endpoint.id = unique_id(&mut ids, id);
+		routes.insert(route, endpoint.id.clone());
+		endpoints.push(endpoint);
+		report.converted += 1;
+	}
+	if endpoints.is_empty() {
+		warnings.push("no requests with saved examples were found".to_string());
+	}
+
+	let description = match info.get("description") {
+		Some(Value::String(description)) => Some(description.clone()),
+		Some(description) => description.get("content").and_then(Value::as_str).map(str::to_string),
+		None => None,
+	};
+	let metadata = Metadata {
+		name: info.get("name").and_then(Value::as_str).map(str::to_string),
+		description,
+		author: None,
+		created: Some(chrono::Utc::now().to_rfc3339()),
+	};
+	Ok(Imported {
+		config: Configuration::from_endpoints(metadata, endpoints),
+		warnings,
+		report: Some(report),
+	})
+}
+
+fn collect<'a>(items: &'a [Value], folder: &str, requests: &mut Vec<Request<'a>>) {
+	for item in items {
+		let name = item.get("name").and_then(Value::as_str).unwrap_or("unnamed");
+		let name = if folder.is_empty() { name.to_string() } else { format!("{} / {}", folder, name) };
+		match item.get("item").and_then(Value::as_array) {
+			Some(children) => collect(children, &name, requests),
+			None if item.get("request").is_some() => requests.push(Request { name, item }),
+			None => {}
+		}
+	}
+}
+
+/// One request as an endpoint, with a placeholder id.
+fn convert(item: &Value, name: &str, options: &ImportOptions, report: &mut ConversionReport) -> Result<Endpoint, String> {
+	let examples = item.get("response").and_then(Value::as_array).cloned().unwrap_or_default();
+	let is_success = |example: &&Value| example.get("code").and_then(Value::as_u64).is_some_and(|code| (200..300).contains(&code));
+	let Some(example) = examples.iter().find(is_success).or(examples.first()) else {
+		report.unsupported("requests without saved examples (skipped)", name);
+		return Err("no saved examples".to_string());
+	};
+	if examples.len() > 1 {
+		report.unsupported("additional examples (only one example per request is served)", name);
+	}
+	if item.get("event").and_then(Value::as_array).is_some_and(|events| !events.is_empty()) {
+		report.unsupported("scripts (ignored)", name);
+	}
+
+	// The mock server matches the example's own request, which may differ
+	// from the request it was saved under.
+	let request = example.get("originalRequest").filter(|request| request.is_object()).or(item.get("request"));
+	let request = request.ok_or("no request")?;
+	let method_name = match request {
+		Value::String(_) => "GET",
+		request => request.get("method").and_then(Value::as_str).unwrap_or("GET"),
+	};
+	let Some((_, method)) = METHODS.iter().find(|(name, _)| name.eq_ignore_ascii_case(method_name)) else {
+		return Err(format!("method {} not supported", method_name));
+	};
+	// A request may be given as just its URL.
+	let url = match request {
+		Value::String(_) => Some(request),
+		request => request.get("url"),
+	};
+	let path = url.map(|url| path(url, name, report)).unwrap_or_else(|| "/".to_string());
+
+	let headers = example.get("header").and_then(Value::as_array).cloned().unwrap_or_default();
+	let headers: HashMap<String, String> = headers
+		.iter()
+		.filter(|header| !header.get("disabled").and_then(Value::as_bool).unwrap_or(false))
+		.filter_map(|header| {
+			let key = header.get("key").and_then(Value::as_str)?;
+			let value = header.get("value").and_then(Value::as_str).unwrap_or_default();
+			Some((key.to_string(), value.to_string()))
+		})
+		.collect();
+	let body = example.get("body").and_then(Value::as_str).unwrap_or_default().to_string();
+	if body.contains("{{") {
+		report.unsupported("variables in example bodies (served literally)", name);
+	}
+
+	let documented: Vec<u16> = examples
+		.iter()
+		.filter_map(|example| example.get("code").and_then(Value::as_u64))
+		.filter(|code| (400..600).contains(code))
+		.filter_map(|code| u16::try_from(code).ok())
+		.collect();
+	let mut endpoint = Endpoint::new(
+		String::new(),
+		method.clone(),
+		path,
+		options.latency.clone(),
+		Response {
+			status: example.get("code").and_then(Value::as_u64).and_then(|code| u16::try_from(code).ok()).unwrap_or(200),
+			headers: stable_headers(&headers),
+			body,
+		},
+	);
+	endpoint.error_profile = options.error_profile(&documented);
+	Ok(endpoint)
+}
+
+/// Route path of a Postman URL, given as a string or as an object. `:name`
+/// and `{{name}}` segments become `{name}` parameters.
+fn path(url: &Value, name: &str, report: &mut ConversionReport) -> String {
+	let (segments, has_query) = match url {
+		Value::String(raw) => raw_segments(raw),
+		url => match url.get("path") {
+			Some(Value::Array(path)) => (
+				path.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+				url.get("query").and_then(Value::as_array).is_some_and(|query| !query.is_empty()),
+			),
+			Some(Value::String(path)) => raw_segments(&format!("/{}", path.trim_start_matches('/'))),
+			_ => raw_segments(url.get("raw").and_then(Value::as_str).unwrap_or("/")),
+		},
+	};
+	if has_query {
+		report.unsupported("query parameters (not matched)", name);
+	}
+
+	let segments: Vec<String> = segments
+		.iter()
+		.filter(|segment| !segment.is_empty())
+		.map(|segment| {
+			if let Some(variable) = segment.strip_prefix(':') {
+				format!("{{{}}}", variable)
+			} else if let Some(variable) = segment.strip_prefix("{{").and_then(|rest| rest.strip_suffix("}}")) {
+				format!("{{{}}}", variable)
+			} else {
+				segment.clone()
+			}
+		})
+		.collect();
+	format!("/{}", segments.join("/"))
+}
+
+/// Path segments of a raw URL such as `{{baseUrl}}/users/:id?expand=1`, and
+/// whether it has a query string.
+fn raw_segments(raw: &str) -> (Vec<String>, bool) {
+	let (raw, query) = raw.split_once('?').unwrap_or((raw, ""));
+	let raw = raw.split('#').next().unwrap_or_default();
+	let path = if let Some((_, rest)) = raw.split_once("://") {
+		rest.find('/').map(|start| &rest[start..]).unwrap_or("")
+	} else if raw.starts_with('/') {
+		raw
+	} else {
+		// Host, possibly a `{{baseUrl}}` variable, then the path.
+		raw.find('/').map(|start| &raw[start..]).unwrap_or("")
+	};
+	(path.split('/').map(str::to_string).collect(), !query.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::{validate, HttpMethod};
+
+	const COLLECTION: &str = r#"{
+		"info": { "name": "Shop", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+		"item": [
+			{
+				"name": "Users",
+				"item": [
+					{
+						"name": "Get user",
+						"request": { "method": "GET", "url": { "raw": "{{baseUrl}}/users/:id", "host": ["{{baseUrl}}"], "path": ["users", ":id"] } },
+						"response": [
+							{ "name": "Missing", "code": 404, "header": [], "body": "{\"error\": \"not found\"}" },
+							{
+								"name": "Found",
+								"code": 200,
+								"header": [
+									{ "key": "Content-Type", "value": "application/json" },
+									{ "key": "Date", "value": "Tue, 01 Sep 2026 10:00:00 GMT" }
+								],
+								"body": "{\"id\": 1}"
+							}
+						]
+					}
+				]
+			},
+			{
+				"name": "Search",
+				"request": { "method": "GET", "url": "https://shop.example.com/search?q=shoes" },
+				"response": [{ "code": 200, "body": "[]" }],
+				"event": [{ "listen": "test", "script": { "exec": ["pm.test()"] } }]
+			},
+			{ "name": "Delete cart", "request": { "method": "DELETE", "url": "{{baseUrl}}/carts/{{cartId}}" } }
+		]
+	}"#;
+
+	#[test]
+	fn test_import_postman_examples() {
+		let options = ImportOptions {
+			error_profile: crate::config::ErrorProfile {
+				rate: 0.1,
+				..Default::default()
+			},
+			..ImportOptions::default()
+		};
+		let Imported { config, report, .. } = import_postman(COLLECTION, &options).expect("import");
+		assert!(validate(&config).is_ok());
+		assert_eq!(config.metadata.name.as_deref(), Some("Shop"));
+		assert_eq!(config.endpoints.len(), 2);
+
+		let user = &config.endpoints[0];
+		assert_eq!((user.id.as_str(), user.path.as_str()), ("get-user", "/users/{id}"));
+		assert_eq!((user.response.status, user.response.body.as_str()), (200, r#"{"id": 1}"#));
+		assert!(!user.response.headers.contains_key("Date"));
+		assert_eq!(user.error_profile.codes, vec![404]);
+		assert_eq!(config.endpoints[1].path, "/search");
+		assert_eq!(config.endpoints[1].method, HttpMethod::Get);
+
+		let report = report.expect("report");
+		assert_eq!((report.total, report.converted, report.skipped), (3, 2, 1));
+		assert_eq!(report.unsupported["requests without saved examples (skipped)"], vec!["Delete cart"]);
+		assert_eq!(report.unsupported["scripts (ignored)"], vec!["Search"]);
+		assert!(report.unsupported.contains_key("query parameters (not matched)"));
+	}
+
+	#[test]
+	fn test_raw_url_paths() {
+		let mut report = ConversionReport::default();
+		let path = |raw: &str, report: &mut ConversionReport| path(&Value::String(raw.to_string()), "r", report);
+		assert_eq!(path("{{baseUrl}}/carts/{{cartId}}/items", &mut report), "/carts/{cartId}/items");
+		assert_eq!(path("http://localhost:3000", &mut report), "/");
+		assert_eq!(path("/orders/:orderId#top", &mut report), "/orders/{orderId}");
+		assert!(report.unsupported.is_empty());
+	}
+}
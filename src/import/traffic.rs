@@ -0,0 +1,345 @@
+// Observed traffic
+//
+// Request/response exchanges grouped by route, turned into endpoints whose
+// latency and error rate are fitted from what was observed. Shared by the
+// recording proxy and the HAR importer
+
+use crate::config::{
+	Configuration, DistributionParams, DistributionType, Endpoint, ErrorProfile, HttpMethod, LatencyConfig, Metadata,
+	Response,
+};
+use crate::engine::method_to_string;
+use crate::import::{endpoint_id, unique_id};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Latency samples kept per route; later exchanges still count towards statuses.
+const MAX_SAMPLES: usize = 10_000;
+/// Routes kept; exchanges for further routes are only counted as dropped.
+const MAX_ROUTES: usize = 1_000;
+
+/// Response headers that describe one exchange or its transport rather than the route.
+const VOLATILE_HEADERS: &[&str] = &[
+	"date",
+	"etag",
+	"last-modified",
+	"set-cookie",
+	"age",
+	"expires",
+	"content-length",
+	"content-encoding",
+	"transfer-encoding",
+	"connection",
+	"keep-alive",
+];
+
+/// Routes seen so far, in the order they were first requested.
+#[derive(Debug)]
+pub struct Traffic {
+	template_paths: bool,
+	routes: Vec<RouteTraffic>,
+	/// Position of each route in `routes`, by method and path.
+	index: HashMap<(&'static str, String), usize>,
+	dropped: u64,
+}
+
+#[derive(Debug, Clone)]
+struct RouteTraffic {
+	method: HttpMethod,
+	path: String,
+	latencies_ms: Vec<f64>,
+	statuses: BTreeMap<u16, u64>,
+	/// First successful response, else the first response.
+	response: Response,
+	/// First non-2xx response, used as the error body.
+	error: Option<Response>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteSummary {
+	pub method: String,
+	pub path: String,
+	pub count: u64,
+	pub statuses: BTreeMap<u16, u64>,
+	pub mean_ms: f64,
+}
+
+impl Traffic {
+	/// With `template_paths`, numeric and UUID segments become parameters so
+	/// requests for different records share a route.
+	pub fn new(template_paths: bool) -> Self {
+		Self {
+			template_paths,
+			routes: Vec::new(),
+			index: HashMap::new(),
+			dropped: 0,
+		}
+	}
+
+	/// Add one exchange and the time the server took to answer it.
+	pub fn add(&mut self, method: &HttpMethod, path: &str, response: &Response, latency_ms: f64) {
+		let path = if self.template_paths { template_path(path) } else { path.to_string() };
+		let key = (method_to_string(method), path);
+		let index = match self.index.get(&key) {
+			Some(index) => *index,
+			None if self.routes.len() >= MAX_ROUTES => {
+				self.dropped += 1;
+				return;
+			}
+			None => {
+				let path = key.1.clone();
+				self.index.insert(key, self.routes.len());
+				self.routes.push(RouteTraffic {
+					method: method.clone(),
+					path,
+					latencies_ms: Vec::new(),
+					statuses: BTreeMap::new(),
+					response: response.clone(),
+					error: None,
+				});
+				self.routes.len() - 1
+			}
+		};
+
+		let route = &mut self.routes[index];
+		if route.latencies_ms.len() < MAX_SAMPLES {
+			route.latencies_ms.push(latency_ms.max(0.0));
+		}
+		*route.statuses.entry(response.status).or_default() += 1;
+		if is_success(response.status) && !is_success(route.response.status) {
+			route.response = response.clone();
+		}
+		if !is_success(response.status) && route.error.is_none() {
+			route.error = Some(response.clone());
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.routes.is_empty()
+	}
+
+	/// Exchanges left out because `MAX_ROUTES` routes were already kept.
+	pub fn dropped(&self) -> u64 {
+		self.dropped
+	}
+
+	pub fn clear(&mut self) {
+		self.routes.clear();
+		self.index.clear();
+		self.dropped = 0;
+	}
+
+	pub fn summary(&self) -> Vec<RouteSummary> {
+		self.routes
+			.iter()
+			.map(|route| RouteSummary {
+				method: method_to_string(&route.method).to_string(),
+				path: route.path.clone(),
+				count: route.statuses.values().sum(),
+				statuses: route.statuses.clone(),
+				mean_ms: mean(&route.latencies_ms),
+			})
+			.collect()
+	}
+
+	/// One endpoint per route.
+	pub fn configuration(&self, metadata: Metadata) -> Configuration {
+		let mut ids = HashSet::new();
+		let endpoints = self
+			.routes
+			.iter()
+			.map(|route| {
+				let id = unique_id(&mut ids, endpoint_id(&route.method, &route.path));
+				let mut endpoint = Endpoint::new(
+					id,
+					route.method.clone(),
+					route.path.clone(),
+					fit_latency(&route.latencies_ms),
+					Response {
+						status: route.response.status,
+						headers: stable_headers(&route.response.headers),
+						body: route.response.body.clone(),
+					},
+				);
+				endpoint.error_profile = error_profile(route);
+				endpoint
+			})
+			.collect();
+		Configuration::from_endpoints(metadata, endpoints)
+	}
+}
+
+fn is_success(status: u16) -> bool {
+	(200..300).contains(&status)
+}
+
+/// Share of non-2xx responses, for routes that also answered successfully.
+fn error_profile(route: &RouteTraffic) -> ErrorProfile {
+	let total: u64 = route.statuses.values().sum();
+	let failures: u64 = route
+		.statuses
+		.iter()
+		.filter(|(status, _)| !is_success(**status))
+		.map(|(_, count)| count)
+		.sum();
+	if failures == 0 || !is_success(route.response.status) {
+		return ErrorProfile::default();
+	}
+	ErrorProfile {
+		rate: round(failures as f64 / total as f64, 1000.0),
+		codes: route.statuses.keys().copied().filter(|status| !is_success(*status)).collect(),
+		body: route.error.as_ref().map(|error| error.body.clone()).unwrap_or_default(),
+		..ErrorProfile::default()
+	}
+}
+
+pub(crate) fn stable_headers(headers: &HashMap<String, String>) -> HashMap<String, String> {
+	headers
+		.iter()
+		.filter(|(name, _)| !VOLATILE_HEADERS.iter().any(|volatile| name.eq_ignore_ascii_case(volatile)))
+		.map(|(name, value)| (name.clone(), value.clone()))
+		.collect()
+}
+
+/// Log-normal fitted by maximum likelihood; fixed when there is no spread.
+pub fn fit_latency(samples_ms: &[f64]) -> LatencyConfig {
+	let mean_ms = mean(samples_ms);
+	let spread = samples_ms.iter().any(|sample| (sample - mean_ms).abs() > f64::EPSILON);
+	if !spread {
+		return LatencyConfig {
+			distribution: DistributionType::Fixed,
+			params: DistributionParams::Fixed { delay_ms: round(mean_ms, 10.0) },
+		};
+	}
+
+	// Sub-millisecond samples are floored so the logarithm stays finite.
+	let logs: Vec<f64> = samples_ms.iter().map(|sample| sample.max(0.1).ln()).collect();
+	let mu = mean(&logs);
+	let sigma_sq = logs.iter().map(|log| (log - mu).powi(2)).sum::<f64>() / logs.len() as f64;
+	let mean_ms = (mu + sigma_sq / 2.0).exp();
+	let stddev_ms = mean_ms * (sigma_sq.exp() - 1.0).sqrt();
+	LatencyConfig {
+		distribution: DistributionType::LogNormal,
+		params: DistributionParams::LogNormal {
+			mean_ms: round(mean_ms, 10.0),
+			stddev_ms: round(stddev_ms, 10.0),
+		},
+	}
+}
+
+fn mean(values: &[f64]) -> f64 {
+	if values.is_empty() {
+		return 0.0;
+	}
+	values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn round(value: f64, scale: f64) -> f64 {
+	(value * scale).round() / scale
+}
+
+/// Replace numeric and UUID segments with `{id}`, `{id2}`, ...
+pub fn template_path(path: &str) -> String {
+	let mut params = 0;
+	let segments: Vec<String> = path
+		.split('/')
+		.map(|segment| {
+			if !is_numeric(segment) && !is_uuid(segment) {
+				return segment.to_string();
+			}
+			params += 1;
+			match params {
+				1 => "{id}".to_string(),
+				n => format!("{{id{}}}", n),
+			}
+		})
+		.collect();
+	segments.join("/")
+}
+
+fn is_numeric(segment: &str) -> bool {
+	!segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn is_uuid(segment: &str) -> bool {
+	segment.len() == 36
+		&& segment.char_indices().all(|(index, c)| match index {
+			8 | 13 | 18 | 23 => c == '-',
+			_ => c.is_ascii_hexdigit(),
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::validate;
+
+	fn response(status: u16, body: &str) -> Response {
+		Response {
+			status,
+			headers: HashMap::from([
+				("content-type".to_string(), "application/json".to_string()),
+				("date".to_string(), "Tue, 01 Sep 2026 10:00:00 GMT".to_string()),
+			]),
+			body: body.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_template_path() {
+		assert_eq!(template_path("/users/42/orders/7"), "/users/{id}/orders/{id2}");
+		assert_eq!(
+			template_path("/files/0b6a3c1e-9d2f-4c57-8e3b-2a1f6d9c4e70/meta"),
+			"/files/{id}/meta"
+		);
+		assert_eq!(template_path("/v2/users"), "/v2/users");
+		assert_eq!(endpoint_id(&HttpMethod::Get, "/users/{id}"), "get-users-id");
+		assert_eq!(endpoint_id(&HttpMethod::Get, "/"), "get-root");
+	}
+
+	#[test]
+	fn test_traffic_builds_a_valid_configuration() {
+		let mut traffic = Traffic::new(true);
+		traffic.add(&HttpMethod::Get, "/users/1", &response(503, r#"{"error": "busy"}"#), 40.0);
+		for (index, latency) in [80.0, 100.0, 120.0, 95.0].into_iter().enumerate() {
+			let path = format!("/users/{}", index + 2);
+			traffic.add(&HttpMethod::Get, &path, &response(200, r#"{"id": 2}"#), latency);
+		}
+		traffic.add(&HttpMethod::Post, "/users", &response(201, "{}"), 30.0);
+
+		let config = traffic.configuration(Metadata::default());
+		assert!(validate(&config).is_ok());
+		assert_eq!(config.endpoints.len(), 2);
+
+		let users = &config.endpoints[0];
+		assert_eq!((users.id.as_str(), users.path.as_str()), ("get-users-id", "/users/{id}"));
+		assert_eq!(users.response.status, 200);
+		assert_eq!(users.response.body, r#"{"id": 2}"#);
+		assert!(!users.response.headers.contains_key("date"));
+		assert_eq!(users.latency.distribution, DistributionType::LogNormal);
+		assert_eq!(users.error_profile.rate, 0.2);
+		assert_eq!(users.error_profile.codes, vec![503]);
+		assert_eq!(config.endpoints[1].latency.distribution, DistributionType::Fixed);
+
+		let mut untemplated = Traffic::new(false);
+		untemplated.add(&HttpMethod::Get, "/users/1", &response(200, "{}"), 10.0);
+		untemplated.add(&HttpMethod::Get, "/users/2", &response(200, "{}"), 10.0);
+		assert_eq!(untemplated.summary().len(), 2);
+	}
+
+	#[test]
+	fn test_traffic_keeps_a_limited_number_of_routes() {
+		let mut traffic = Traffic::new(false);
+		for index in 0..MAX_ROUTES + 5 {
+			traffic.add(&HttpMethod::Get, &format!("/posts/slug-{}", index), &response(200, "{}"), 10.0);
+		}
+		traffic.add(&HttpMethod::Get, "/posts/slug-0", &response(200, "{}"), 20.0);
+		assert_eq!(traffic.summary().len(), MAX_ROUTES);
+		assert_eq!(traffic.summary()[0].count, 2);
+		assert_eq!(traffic.dropped(), 5);
+
+		traffic.clear();
+		assert!(traffic.is_empty());
+		assert_eq!(traffic.dropped(), 0);
+	}
+}
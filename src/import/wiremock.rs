@@ -0,0 +1,465 @@
+// WireMock import
+//
+// Endpoints from WireMock stub mappings: the request's method and URL, the
+// response, delays and faults. Matching and response features without an
+// equivalent are listed in the conversion report
+
+use crate::config::{
+	BodyMatchType, ConfigError, Configuration, CorruptionMode, DistributionParams, DistributionType, Endpoint,
+	ErrorProfile, HttpMethod, LatencyConfig, Metadata, PayloadCorruption, ProxyTarget, RequestMatch, Response,
+};
+use crate::engine::method_to_string;
+use crate::import::{
+	endpoint_id, parse_document, slug, unique_id, ConversionReport, ImportOptions, Imported, METHODS,
+};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// WireMock's priority for stubs that do not set one; lower numbers win.
+const DEFAULT_PRIORITY: i64 = 5;
+
+const REQUEST_KEYS: &[&str] = &["method", "url", "urlPath", "urlPattern", "urlPathPattern", "urlPathTemplate", "bodyPatterns"];
+const RESPONSE_KEYS: &[&str] = &[
+	"status",
+	"statusMessage",
+	"headers",
+	"body",
+	"jsonBody",
+	"base64Body",
+	"fixedDelayMilliseconds",
+	"delayDistribution",
+	"fault",
+	"proxyBaseUrl",
+];
+const MAPPING_KEYS: &[&str] = &["id", "uuid", "name", "request", "response", "priority", "persistent", "metadata", "insertionIndex"];
+
+/// Build a configuration from WireMock mappings: a single stub, an array of
+/// stubs, or a `{"mappings": [...]}` export. `options` supply the latency of
+/// stubs without a delay and the error profile of every endpoint.
+pub fn import_wiremock(content: &str, options: &ImportOptions) -> Result<Imported, ConfigError> {
+	let document = parse_document(content)?;
+	let mut mappings = match &document {
+		Value::Array(mappings) => mappings.clone(),
+		Value::Object(object) if object.contains_key("mappings") => {
+			object.get("mappings").and_then(Value::as_array).cloned().unwrap_or_default()
+		}
+		Value::Object(object) if object.contains_key("request") => vec![document.clone()],
+		_ => {
+			return Err(ConfigError::ParseError(
+				"not WireMock mappings: expected a stub, an array of stubs or a 'mappings' list".to_string(),
+			))
+		}
+	};
+	// Stubs compete for the same route in priority order, then in file order.
+	mappings.sort_by_key(|mapping| mapping.get("priority").and_then(Value::as_i64).unwrap_or(DEFAULT_PRIORITY));
+
+	let mut report = ConversionReport {
+		total: mappings.len(),
+		..ConversionReport::default()
+	};
+	let mut warnings = Vec::new();
+	let mut ids = HashSet::new();
+	let mut routes: HashMap<(String, String), String> = HashMap::new();
+	let mut endpoints = Vec::new();
+	for (index, mapping) in mappings.iter().enumerate() {
+		let stub = stub_name(mapping, index);
+		match convert(mapping, &stub, options, &mut report) {
+			Ok(mut endpoint) => {
+				let route = (method_to_string(&endpoint.method).to_string(), endpoint.path.clone());
+				if let Some(winner) = routes.get(&route) {
+					warnings.push(format!("{}: {} {} is already served by '{}', skipped", stub, route.0, route.1, winner));
+					report.unsupported("several stubs for one method and path (first by priority kept)", &stub);
+					report.skipped += 1;
+					continue;
+				}
+				let id = mapping
+					.get("name")
+					.and_then(Value::as_str)
+					.map(slug)
+					.filter(|id| !id.is_empty())
+					.unwrap_or_else(|| endpoint_id(&endpoint.method, &endpoint.path));
+				endpoint.id = unique_id(&mut ids, id);
+				routes.insert(route, endpoint.id.clone());
+				endpoints.push(endpoint);
+				report.converted += 1;
+			}
+			Err(reason) => {
+				warnings.push(format!("{}: {}, skipped", stub, reason));
+				report.skipped += 1;
+			}
+		}
+	}
+	if endpoints.is_empty() {
+		warnings.push("no stubs were imported".to_string());
+	}
+
+	let metadata = Metadata {
+		name: None,
+		description: Some(format!("Imported from {} WireMock stub(s)", report.total)),
+		author: None,
+		created: Some(chrono::Utc::now().to_rfc3339()),
+	};
+	Ok(Imported {
+		config: Configuration::from_endpoints(metadata, endpoints),
+		warnings,
+		report: Some(report),
+	})
+}
+
+/// The stub's name, else its method and URL, for messages.
+fn stub_name(mapping: &Value, index: usize) -> String {
+	if let Some(name) = mapping.get("name").and_then(Value::as_str) {
+		return name.to_string();
+	}
+	let request = mapping.get("request");
+	let method = request.and_then(|request| request.get("method")).and_then(Value::as_str).unwrap_or("ANY");
+	let url = ["url", "urlPath", "urlPathTemplate", "urlPattern", "urlPathPattern"]
+		.iter()
+		.find_map(|key| request.and_then(|request| request.get(*key)).and_then(Value::as_str));
+	match url {
+		Some(url) => format!("{} {}", method, url),
+		None => format!("stub {}", index),
+	}
+}
+
+/// One stub as an endpoint, with a placeholder id.
+fn convert(mapping: &Value, stub: &str, options: &ImportOptions, report: &mut ConversionReport) -> Result<Endpoint, String> {
+	let request = mapping.get("request").ok_or("no request")?;
+	let response = mapping.get("response").cloned().unwrap_or_else(|| Value::Object(Default::default()));
+	unknown_keys(mapping, MAPPING_KEYS, "", stub, report);
+	unknown_keys(request, REQUEST_KEYS, "request.", stub, report);
+	unknown_keys(&response, RESPONSE_KEYS, "response.", stub, report);
+
+	let method_name = request.get("method").and_then(Value::as_str).unwrap_or("ANY");
+	let method = match METHODS.iter().find(|(name, _)| name.eq_ignore_ascii_case(method_name)) {
+		Some((_, method)) => method.clone(),
+		None if method_name == "ANY" => {
+			report.unsupported("request.method ANY (imported as GET)", stub);
+			HttpMethod::Get
+		}
+		None => return Err(format!("method {} not supported", method_name)),
+	};
+
+	let mut endpoint = Endpoint::new(
+		String::new(),
+		method,
+		path(request, stub, report)?,
+		latency(&response, options, stub, report),
+		Response {
+			status: response.get("status").and_then(Value::as_u64).and_then(|status| u16::try_from(status).ok()).unwrap_or(200),
+			headers: headers(&response),
+			body: body(&response, stub, report),
+		},
+	);
+	endpoint.request = body_match(request, stub, report);
+	endpoint.error_profile = options.error_profile(&[]);
+	if let Some(fault) = response.get("fault").and_then(Value::as_str) {
+		apply_fault(&mut endpoint.error_profile, fault, stub, report);
+	}
+	if let Some(upstream) = response.get("proxyBaseUrl").and_then(Value::as_str) {
+		endpoint.proxy = Some(ProxyTarget {
+			upstream: upstream.to_string(),
+			timeout_ms: 30_000.0,
+			record: false,
+		});
+	}
+	Ok(endpoint)
+}
+
+fn unknown_keys(object: &Value, known: &[&str], prefix: &str, stub: &str, report: &mut ConversionReport) {
+	for key in object.as_object().into_iter().flat_map(|object| object.keys()) {
+		if !known.contains(&key.as_str()) {
+			report.unsupported(format!("{}{} (ignored)", prefix, key), stub);
+		}
+	}
+}
+
+/// Route path from whichever URL matcher the stub uses. Patterns are
+/// converted when every segment is a literal or a simple wildcard.
+fn path(request: &Value, stub: &str, report: &mut ConversionReport) -> Result<String, String> {
+	let text = |key: &str| request.get(key).and_then(Value::as_str);
+	if let Some(path) = text("urlPath").or(text("urlPathTemplate")) {
+		return Ok(path.to_string());
+	}
+	if let Some(url) = text("url") {
+		let (path, query) = url.split_once('?').unwrap_or((url, ""));
+		if !query.is_empty() {
+			report.unsupported("query string in request.url (not matched)", stub);
+		}
+		return Ok(path.to_string());
+	}
+	if let Some(pattern) = text("urlPathPattern").or(text("urlPattern")) {
+		let path = pattern_path(pattern).ok_or_else(|| format!("URL pattern '{}' cannot be expressed as a path", pattern))?;
+		report.unsupported("URL patterns (converted to path parameters)", stub);
+		return Ok(path);
+	}
+	Ok("/".to_string())
+}
+
+/// `/users/[0-9]+/orders` becomes `/users/{id}/orders`.
+fn pattern_path(pattern: &str) -> Option<String> {
+	let pattern = pattern.trim_start_matches('^').trim_end_matches('$');
+	let pattern = pattern.split("\\?").next().unwrap_or(pattern);
+	let mut params = 0;
+	let segments: Option<Vec<String>> = pattern
+		.split('/')
+		.map(|segment| {
+			let literal = segment.replace("\\.", ".").replace("\\-", "-");
+			if !literal.contains(|c: char| "\\.*+?[](){}|^$".contains(c)) {
+				return Some(literal);
+			}
+			if !is_wildcard(segment) {
+				return None;
+			}
+			params += 1;
+			Some(match params {
+				1 => "{id}".to_string(),
+				n => format!("{{id{}}}", n),
+			})
+		})
+		.collect();
+	segments.map(|segments| segments.join("/")).filter(|path| path.starts_with('/'))
+}
+
+/// A character class or `.` repeated, e.g. `[0-9]+`, `\d+`, `[^/]*`, `.+`.
+fn is_wildcard(segment: &str) -> bool {
+	let Some(class) = segment.strip_suffix('+').or_else(|| segment.strip_suffix('*')) else {
+		return false;
+	};
+	matches!(class, "." | "\\d" | "\\w")
+		|| (class.starts_with('[') && class.ends_with(']') && class.len() > 2 && !class[1..].contains('['))
+}
+
+fn latency(response: &Value, options: &ImportOptions, stub: &str, report: &mut ConversionReport) -> LatencyConfig {
+	let fixed_ms = response.get("fixedDelayMilliseconds").and_then(Value::as_f64);
+	let Some(distribution) = response.get("delayDistribution") else {
+		return match fixed_ms {
+			Some(delay_ms) => LatencyConfig {
+				distribution: DistributionType::Fixed,
+				params: DistributionParams::Fixed { delay_ms },
+			},
+			None => options.latency.clone(),
+		};
+	};
+
+	// WireMock adds a fixed delay on top of a sampled one.
+	let offset_ms = fixed_ms.unwrap_or(0.0);
+	let number = |key: &str| distribution.get(key).and_then(Value::as_f64);
+	match distribution.get("type").and_then(Value::as_str).unwrap_or_default() {
+		kind @ ("lognormal" | "truncatedLognormal") => {
+			if kind == "truncatedLognormal" {
+				report.unsupported("delayDistribution truncatedLognormal (imported without the cap)", stub);
+			}
+			// WireMock's parameters are the median and the sigma of the
+			// underlying normal; the configuration wants mean and stddev.
+			let median = number("median").unwrap_or(0.0);
+			let sigma = number("sigma").unwrap_or(0.0);
+			let mean_ms = median * (sigma * sigma / 2.0).exp();
+			let stddev_ms = mean_ms * ((sigma * sigma).exp() - 1.0).sqrt();
+			LatencyConfig {
+				distribution: DistributionType::LogNormal,
+				params: DistributionParams::LogNormal {
+					mean_ms: round(mean_ms + offset_ms),
+					stddev_ms: round(stddev_ms),
+				},
+			}
+		}
+		"uniform" => LatencyConfig {
+			distribution: DistributionType::Uniform,
+			params: DistributionParams::Uniform {
+				min_ms: number("lower").unwrap_or(0.0) + offset_ms,
+				max_ms: number("upper").unwrap_or(0.0) + offset_ms,
+			},
+		},
+		other => {
+			report.unsupported(format!("delayDistribution {} (ignored)", other), stub);
+			LatencyConfig {
+				distribution: DistributionType::Fixed,
+				params: DistributionParams::Fixed { delay_ms: offset_ms },
+			}
+		}
+	}
+}
+
+fn round(value: f64) -> f64 {
+	(value * 10.0).round() / 10.0
+}
+
+/// Header values given as lists are joined with commas.
+fn headers(response: &Value) -> HashMap<String, String> {
+	let object = response.get("headers").and_then(Value::as_object).cloned().unwrap_or_default();
+	object
+		.into_iter()
+		.map(|(name, value)| {
+			let value = match value {
+				Value::String(value) => value,
+				Value::Array(values) => values.iter().map(text).collect::<Vec<_>>().join(", "),
+				other => text(&other),
+			};
+			(name, value)
+		})
+		.collect()
+}
+
+fn text(value: &Value) -> String {
+	value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())
+}
+
+fn body(response: &Value, stub: &str, report: &mut ConversionReport) -> String {
+	if let Some(body) = response.get("body") {
+		return text(body);
+	}
+	if let Some(json) = response.get("jsonBody") {
+		return json.to_string();
+	}
+	if let Some(encoded) = response.get("base64Body").and_then(Value::as_str) {
+		match STANDARD.decode(encoded).ok().and_then(|bytes| String::from_utf8(bytes).ok()) {
+			Some(body) => return body,
+			None => report.unsupported("binary response.base64Body (left out)", stub),
+		}
+	}
+	if response.get("bodyFileName").is_some() {
+		report.unsupported("response.bodyFileName (body left empty)", stub);
+	}
+	String::new()
+}
+
+/// The first body pattern, when it has an equivalent.
+fn body_match(request: &Value, stub: &str, report: &mut ConversionReport) -> Option<RequestMatch> {
+	let patterns = request.get("bodyPatterns").and_then(Value::as_array)?;
+	if patterns.len() > 1 {
+		report.unsupported("request.bodyPatterns beyond the first (ignored)", stub);
+	}
+	let pattern = patterns.first()?.as_object()?;
+	let (operator, expected) = pattern.iter().next()?;
+	let (body_match, body) = match operator.as_str() {
+		"equalTo" => (BodyMatchType::Exact, text(expected)),
+		"contains" => (BodyMatchType::Contains, text(expected)),
+		"equalToJson" => {
+			report.unsupported("request.bodyPatterns equalToJson (matched as exact text)", stub);
+			(BodyMatchType::Exact, text(expected))
+		}
+		other => {
+			report.unsupported(format!("request.bodyPatterns {} (ignored)", other), stub);
+			return None;
+		}
+	};
+	Some(RequestMatch {
+		body_match,
+		body: Some(body),
+	})
+}
+
+/// Connection-level faults become the closest error the engine can serve.
+fn apply_fault(profile: &mut ErrorProfile, fault: &str, stub: &str, report: &mut ConversionReport) {
+	match fault {
+		"EMPTY_RESPONSE" | "CONNECTION_RESET_BY_PEER" => {
+			*profile = ErrorProfile {
+				rate: 1.0,
+				codes: vec![502],
+				..ErrorProfile::default()
+			};
+			report.unsupported(format!("fault {} (served as an empty 502)", fault), stub);
+		}
+		"MALFORMED_RESPONSE_CHUNK" => {
+			profile.payload_corruption = Some(PayloadCorruption {
+				rate: 1.0,
+				mode: CorruptionMode::Truncate,
+				truncate_ratio: Some(0.5),
+				replacement: None,
+			});
+			report.unsupported("fault MALFORMED_RESPONSE_CHUNK (served as a truncated body)", stub);
+		}
+		"RANDOM_DATA_THEN_CLOSE" => {
+			profile.payload_corruption = Some(PayloadCorruption {
+				rate: 1.0,
+				mode: CorruptionMode::Replace,
+				truncate_ratio: None,
+				replacement: Some("\u{1b}\u{7f}#%&@!".to_string()),
+			});
+			report.unsupported("fault RANDOM_DATA_THEN_CLOSE (served as a garbage body)", stub);
+		}
+		other => report.unsupported(format!("fault {} (ignored)", other), stub),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::validate;
+
+	const MAPPINGS: &str = r#"{
+		"mappings": [
+			{
+				"name": "Get user",
+				"request": { "method": "GET", "urlPathPattern": "/users/[0-9]+", "headers": { "Accept": { "equalTo": "application/json" } } },
+				"response": {
+					"status": 200,
+					"jsonBody": { "id": 1, "name": "Ada" },
+					"headers": { "Content-Type": "application/json", "Vary": ["Accept", "Origin"] },
+					"delayDistribution": { "type": "lognormal", "median": 80, "sigma": 0.4 }
+				}
+			},
+			{
+				"request": { "method": "POST", "url": "/orders?source=web", "bodyPatterns": [{ "contains": "sku" }] },
+				"response": { "status": 201, "body": "created", "fixedDelayMilliseconds": 120 },
+				"scenarioName": "checkout"
+			},
+			{
+				"priority": 1,
+				"request": { "method": "GET", "urlPath": "/flaky" },
+				"response": { "fault": "CONNECTION_RESET_BY_PEER" }
+			},
+			{
+				"request": { "method": "GET", "urlPath": "/flaky" },
+				"response": { "status": 200 }
+			},
+			{
+				"request": { "method": "GET", "urlPattern": "/files/(a|b)/.*" },
+				"response": { "status": 200 }
+			}
+		]
+	}"#;
+
+	#[test]
+	fn test_import_wiremock_mappings() {
+		let Imported { config, report, .. } = import_wiremock(MAPPINGS, &ImportOptions::default()).expect("import");
+		assert!(validate(&config).is_ok());
+		let ids: Vec<&str> = config.endpoints.iter().map(|endpoint| endpoint.id.as_str()).collect();
+		assert_eq!(ids, vec!["get-flaky", "get-user", "post-orders"]);
+
+		let flaky = &config.endpoints[0];
+		assert_eq!((flaky.error_profile.rate, flaky.error_profile.codes.clone()), (1.0, vec![502]));
+
+		let user = &config.endpoints[1];
+		assert_eq!(user.path, "/users/{id}");
+		assert_eq!(user.response.body, r#"{"id":1,"name":"Ada"}"#);
+		assert_eq!(user.response.headers["Vary"], "Accept, Origin");
+		match user.latency.params {
+			DistributionParams::LogNormal { mean_ms, stddev_ms } => assert_eq!((mean_ms, stddev_ms), (86.7, 36.1)),
+			ref other => panic!("unexpected latency {:?}", other),
+		}
+
+		let order = &config.endpoints[2];
+		assert_eq!(order.path, "/orders");
+		assert!(matches!(order.latency.params, DistributionParams::Fixed { delay_ms } if delay_ms == 120.0));
+		assert_eq!(order.request.as_ref().map(|request| request.body_match.clone()), Some(BodyMatchType::Contains));
+
+		let report = report.expect("report");
+		assert_eq!((report.total, report.converted, report.skipped), (5, 3, 2));
+		assert_eq!(report.unsupported["request.headers (ignored)"], vec!["Get user"]);
+		assert_eq!(report.unsupported["scenarioName (ignored)"], vec!["POST /orders?source=web"]);
+		assert!(report.unsupported.contains_key("fault CONNECTION_RESET_BY_PEER (served as an empty 502)"));
+		assert!(report.unsupported.contains_key("several stubs for one method and path (first by priority kept)"));
+	}
+
+	#[test]
+	fn test_pattern_path() {
+		assert_eq!(pattern_path("^/api/v1/items/\\d+/tags/[a-z-]+$").as_deref(), Some("/api/v1/items/{id}/tags/{id2}"));
+		assert_eq!(pattern_path("/health\\?verbose=.*").as_deref(), Some("/health"));
+		assert_eq!(pattern_path("/files/(a|b)"), None);
+		assert_eq!(pattern_path(".*"), None);
+	}
+}
@@ -6,13 +6,21 @@ use anyhow::Result;
 use tracing::info;
 use std::sync::Arc;
 
+mod cli;
 mod config;
 mod distributions;
 mod engine;
+mod export;
 mod control_plane;
+mod import;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::run(&args)? {
+        return Ok(());
+    }
+
     // Initialize logging
     tracing_subscriber::fmt()
         .with_env_filter(